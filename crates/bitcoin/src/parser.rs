@@ -424,43 +424,41 @@ fn parse_transaction_output(raw_output: &[u8]) -> Result<(TransactionOutput, usi
     ))
 }
 
+/// Extracts the address spent by an input from its signature script. Supports
+/// P2PKH (`<sig> <pubkey>`), P2SH including multisig (`OP_0 <sig>... <redeem script>`)
+/// and P2SH-P2WPKH (`<OP_0 <20-byte key hash>>`), where the address is the hash of
+/// the redeem script, i.e. the last data push.
 pub(crate) fn extract_address_hash_scriptsig(input_script: &[u8]) -> Result<Address, Error> {
-    let mut parser = BytesParser::new(input_script);
-    let mut p2pkh = true;
-
-    // Multisig OBOE hack -> p2sh
-    if *input_script.get(0).ok_or(Error::EOS)? == OpCode::Op0 as u8 {
-        parser.parse::<u8>()?;
-        p2pkh = false;
+    let pushes = parse_script_pushes(input_script)?;
+    match pushes.as_slice() {
+        // compressed or uncompressed public key
+        [sig, pub_key] if !sig.is_empty() && (pub_key.len() == 33 || pub_key.len() == 65) => Ok(
+            Address::P2PKH(H160::from_slice(&Hash160::hash(pub_key).to_vec())),
+        ),
+        [.., redeem_script] if !redeem_script.is_empty() => Ok(Address::P2SH(H160::from_slice(
+            &Hash160::hash(redeem_script).to_vec(),
+        ))),
+        _ => Err(Error::UnsupportedInputFormat),
     }
+}
 
-    let sig_size: u64 = parser.parse::<CompactUint>()?.value;
-
-    // P2WPKH-P2SH (SegWit)
-    if parser.next()? == OpCode::Op0 as u8 {
-        // NOTE: we probably will not reach this as `extract_address`
-        // will first check the witness and get the `P2WPKHv0`
-        let sig = parser.read(sig_size as usize)?;
-        return Ok(Address::P2SH(H160::from_slice(
-            &Hash160::hash(&sig).to_vec(),
-        )));
-    }
-
-    let _sig = parser.read(sig_size as usize)?;
-
-    let redeem_script_size: u64 = parser.parse::<CompactUint>()?.value;
-
-    // if not p2sh, redeem script is just 33-byte pubkey
-    if p2pkh && redeem_script_size != 33 {
-        return Err(Error::UnsupportedInputFormat);
-    }
-    let redeem_script = parser.read(redeem_script_size as usize)?;
-    let hash = H160::from_slice(&Hash160::hash(&redeem_script).to_vec());
-    return Ok(if p2pkh {
-        Address::P2PKH(hash)
-    } else {
-        Address::P2SH(hash)
-    });
+/// Parses a script that only consists of data pushes, such as a signature script,
+/// into the pushed data. `OP_0` pushes empty data.
+fn parse_script_pushes(script: &[u8]) -> Result<Vec<Vec<u8>>, Error> {
+    let mut parser = BytesParser::new(script);
+    let mut pushes = Vec::new();
+    while parser.next().is_ok() {
+        let op_code: u8 = parser.parse()?;
+        let size = match op_code {
+            0x00..=0x4b => op_code as usize,
+            x if x == OpCode::OpPushData1 as u8 => parser.parse::<u8>()? as usize,
+            x if x == OpCode::OpPushData2 as u8 => parser.parse::<u16>()? as usize,
+            x if x == OpCode::OpPushData4 as u8 => parser.parse::<u32>()? as usize,
+            _ => return Err(Error::UnsupportedInputFormat),
+        };
+        pushes.push(parser.read(size)?);
+    }
+    Ok(pushes)
 }
 
 pub(crate) fn extract_op_return_data(output_script: &[u8]) -> Result<Vec<u8>, Error> {
@@ -771,6 +769,40 @@ pub(crate) mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_extract_address_hash_scriptsig_p2sh_multisig() {
+        // 2-of-3 multisig with compressed keys, pushed with OP_PUSHDATA1
+        let mut redeem_script = vec![OpCode::Op2 as u8];
+        for _ in 0..3 {
+            redeem_script.push(33);
+            redeem_script.extend_from_slice(&[2; 33]);
+        }
+        redeem_script.push(OpCode::Op3 as u8);
+        redeem_script.push(OpCode::OpCheckMultisig as u8);
+        assert_eq!(redeem_script.len(), 105);
+
+        let mut script = vec![OpCode::Op0 as u8];
+        for _ in 0..2 {
+            script.push(71);
+            script.extend_from_slice(&[1; 71]);
+        }
+        script.push(OpCode::OpPushData1 as u8);
+        script.push(redeem_script.len() as u8);
+        script.extend_from_slice(&redeem_script);
+
+        let expected = Address::P2SH(H160::from_slice(&Hash160::hash(&redeem_script).to_vec()));
+        assert_eq!(extract_address_hash_scriptsig(&script).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_extract_address_hash_scriptsig_fails_with_non_push_op_code() {
+        let script = vec![OpCode::OpDup as u8];
+        assert!(matches!(
+            extract_address_hash_scriptsig(&script),
+            Err(Error::UnsupportedInputFormat)
+        ));
+    }
+
     /*
     #[test]
     fn test_extract_address_invalid_p2pkh_fails() {
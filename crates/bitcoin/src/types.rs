@@ -234,6 +234,7 @@ pub const MAX_OPRETURN_SIZE: usize = 83;
 /// Bitcoin Basic Block Headers
 // TODO: Figure out how to set a pointer to the ChainIndex mapping instead
 #[derive(Encode, Decode, Default, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockHeader {
     pub merkle_root: H256Le,
     pub target: U256,
@@ -247,6 +248,18 @@ impl BlockHeader {
     pub fn hash(&self) -> Result<H256Le, Error> {
         Ok(sha256d_le(&self.try_format()?))
     }
+
+    /// Returns the expected number of hashes required to find a block
+    /// with this header's target, i.e. `2**256 / (target + 1)`.
+    /// See `GetBlockProof` in Bitcoin Core.
+    pub fn work(&self) -> U256 {
+        if self.target.is_zero() {
+            return U256::zero();
+        }
+        // 2**256 does not fit into a U256, but as 2**256 >= target + 1
+        // it is equal to (~target / (target + 1)) + 1
+        (!self.target / self.target.saturating_add(U256::one())) + U256::one()
+    }
 }
 
 /// Bitcoin transaction input
@@ -268,12 +281,18 @@ impl TransactionInput {
         self.witness = witness;
     }
 
+    /// Extracts the address spent by this input. Supports P2PKH, P2SH
+    /// (including multisig), P2WPKH and P2SH-P2WPKH.
     pub fn extract_address(&self) -> Result<Address, Error> {
-        // Witness
-        if !self.witness.is_empty() && self.flags == 0 {
-            return Ok(Address::P2WPKHv0(H160::from_slice(
-                &Hash160::hash(&self.witness[1]).to_vec(),
-            )));
+        // P2WPKH has an empty signature script, P2SH-P2WPKH pushes the
+        // witness program and is handled like any other P2SH input
+        if !self.witness.is_empty() && self.flags == 0 && self.script.is_empty() {
+            return match self.witness.as_slice() {
+                [_sig, pub_key] => Ok(Address::P2WPKHv0(H160::from_slice(
+                    &Hash160::hash(pub_key).to_vec(),
+                ))),
+                _ => Err(Error::UnsupportedInputFormat),
+            };
         }
 
         // P2PKH or P2SH
@@ -487,12 +506,15 @@ fn generate_coinbase_transaction(
 
 /// Representation of a Bitcoin blockchain
 #[derive(Encode, Decode, Default, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockChain {
     pub chain_id: u32,
     pub start_height: u32,
     pub max_height: u32,
     pub no_data: BTreeSet<u32>,
     pub invalid: BTreeSet<u32>,
+    /// Accumulated work of all blocks up to and including `max_height`
+    pub chain_work: U256,
 }
 
 impl BlockChain {
@@ -509,6 +531,7 @@ impl BlockChain {
 
 /// Represents a bitcoin 32 bytes hash digest encoded in little-endian
 #[derive(Encode, Decode, Default, PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct H256Le {
     content: [u8; 32],
 }
@@ -994,14 +1017,14 @@ mod tests {
     }
 
     #[test]
-    fn extract_witness_address_p2wpkh() {
+    fn extract_witness_address_p2sh_p2wpkh() {
         let raw_tx = "0200000000010140d43a99926d43eb0e619bf0b3d83b4a31f60c176beecfb9d35bf45e54d0f7420100000017160014a4b4ca48de0b3fffc15404a1acdc8dbaae226955ffffffff0100e1f5050000000017a9144a1154d50b03292b3024370901711946cb7cccc387024830450221008604ef8f6d8afa892dee0f31259b6ce02dd70c545cfcfed8148179971876c54a022076d771d6e91bed212783c9b06e0de600fab2d518fad6f15a2b191d7fbd262a3e0121039d25ab79f41f75ceaf882411fd41fa670a4c672c23ffaf0e361a969cde0692e800000000";
         let tx_bytes = hex::decode(&raw_tx).unwrap();
         let transaction = parse_transaction(&tx_bytes).unwrap();
 
-        let address = Address::P2WPKHv0(H160([
-            164, 180, 202, 72, 222, 11, 63, 255, 193, 84, 4, 161, 172, 220, 141, 186, 174, 34, 105,
-            85,
+        // hash of the redeem script 0x0014a4b4ca48de0b3fffc15404a1acdc8dbaae226955
+        let address = Address::P2SH(H160([
+            41, 40, 244, 58, 241, 141, 45, 96, 232, 168, 67, 84, 13, 128, 134, 179, 5, 52, 19, 57,
         ]));
 
         let extr_address = transaction.inputs[0].extract_address().unwrap();
@@ -1010,14 +1033,15 @@ mod tests {
     }
 
     #[test]
-    fn extract_witness_address_p2wsh() {
+    fn extract_witness_address_p2sh_p2wpkh_multiple_inputs() {
         let raw_tx = "020000000001027113554199c88273f7f04d18a0dca69145ea863f31519a790b346579b9b55f090100000017160014d6ad6711da30f4349a0d8c387a515bff10ecd507fdffffff90a9eb7550a8308c629014f3f685d2d72e9e7de6bd199c3a9615b567206889430100000017160014cce6d8dffda77f56e237389f48417f10659c2e42fdffffff0228641c000000000017a914d980c4240e77b76d48051c791f68831d23ad3e8687400d03000000000017a914e9c3dd0c07aac76179ebc76a6c78d4d67c6c160a870248304502210088b0fb4b40af9620f785f265c2e2f7436018391d9db34eee3bc1ebd796fbce96022015151182eaa595e090c8030d9f979b920aae276c385dfc66ac2d77160a27453b01210266dd88be116711227e2e953daa008cca45ce5cc0aa4b584c20ae6ddb9ce0212d0247304402204b2fdd767ab93b30a43042c3287ae78d06d1418084fe88350b0aaf06bebe02fe02202d850fc5887d948307fdade871de3714d867610643f6507e511d14dad86fe3ce012102593012612326b4c07e6f0234bac5ff62b5ed12afe77e2900474ca36b3bfa528075f50700";
         let tx_bytes = hex::decode(&raw_tx).unwrap();
         let transaction = parse_transaction(&tx_bytes).unwrap();
 
-        let address = Address::P2WPKHv0(H160([
-            214, 173, 103, 17, 218, 48, 244, 52, 154, 13, 140, 56, 122, 81, 91, 255, 16, 236, 213,
-            7,
+        // hash of the redeem script 0x0014d6ad6711da30f4349a0d8c387a515bff10ecd507
+        let address = Address::P2SH(H160([
+            26, 213, 153, 191, 208, 237, 65, 126, 50, 238, 133, 163, 77, 172, 220, 49, 89, 116, 75,
+            4,
         ]));
 
         let extr_address = transaction.inputs[0].extract_address().unwrap();
@@ -1025,6 +1049,22 @@ mod tests {
         assert_eq!(&extr_address, &address);
     }
 
+    #[test]
+    fn extract_witness_address_p2wpkh() {
+        let pub_key =
+            hex::decode("039d25ab79f41f75ceaf882411fd41fa670a4c672c23ffaf0e361a969cde0692e8")
+                .unwrap();
+        let input = TransactionInputBuilder::new()
+            .with_script(&[])
+            .add_witness(&[1; 72])
+            .add_witness(&pub_key)
+            .build();
+
+        let address = Address::P2WPKHv0(H160::from_slice(&Hash160::hash(&pub_key).to_vec()));
+
+        assert_eq!(input.extract_address().unwrap(), address);
+    }
+
     #[test]
     fn decode_and_generate_coinbase_transaction() {
         // testnet - 1896103
@@ -1055,4 +1095,22 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_block_header_work() {
+        let mut header = BlockHeader::default();
+
+        // mainnet genesis target (0x1d00ffff)
+        header.target = U256::from_dec_str(
+            "26959535291011309493156476344723991336010898738574164086137773096960",
+        )
+        .unwrap();
+        assert_eq!(header.work(), U256::from(4295032833u64));
+
+        header.target = U256::max_value();
+        assert_eq!(header.work(), U256::one());
+
+        header.target = U256::zero();
+        assert_eq!(header.work(), U256::zero());
+    }
 }
@@ -0,0 +1,46 @@
+//! Runtime API definition for the BTC-Relay Module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait BtcRelayApi<H256Le, RichBlockHeader, BlockChain, TransactionConsumer, VerificationProfile> where
+        H256Le: Codec,
+        RichBlockHeader: Codec,
+        BlockChain: Codec,
+        TransactionConsumer: Codec,
+        VerificationProfile: Codec,
+    {
+        /// Get the hash of the best block
+        fn get_best_block() -> H256Le;
+
+        /// Get the height of the best block
+        fn get_best_block_height() -> u32;
+
+        /// Get a block header from its hash
+        fn get_block_header(block_hash: H256Le) -> Result<RichBlockHeader, DispatchError>;
+
+        /// Get a main chain block header from its height
+        fn get_block_header_at_height(block_height: u32) -> Result<RichBlockHeader, DispatchError>;
+
+        /// Get all tracked chains, starting with the main chain followed by the forks
+        fn get_chains() -> Vec<BlockChain>;
+
+        /// Check if the inclusion of a transaction could currently be verified
+        fn is_transaction_verifiable(
+            tx_id: H256Le,
+            raw_merkle_proof: Vec<u8>,
+            confirmations: Option<u32>
+        ) -> DispatchResult;
+
+        /// Get the output indices of a transaction that have been used, with the
+        /// request that used each of them
+        fn get_transaction_consumers(tx_id: H256Le) -> Vec<(u32, TransactionConsumer)>;
+
+        /// Get the checks currently performed by BTC-Relay
+        fn get_verification_profile() -> VerificationProfile;
+    }
+}
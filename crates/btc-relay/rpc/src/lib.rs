@@ -0,0 +1,229 @@
+//! RPC interface for the BTC-Relay Module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, DispatchError};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as BtcRelayClient;
+pub use module_btc_relay_rpc_runtime_api::BtcRelayApi as BtcRelayRuntimeApi;
+
+#[rpc]
+pub trait BtcRelayApi<
+    BlockHash,
+    H256Le,
+    RichBlockHeader,
+    BlockChain,
+    TransactionConsumer,
+    VerificationProfile,
+>
+{
+    #[rpc(name = "btcRelay_getBestBlock")]
+    fn get_best_block(&self, at: Option<BlockHash>) -> Result<H256Le>;
+
+    #[rpc(name = "btcRelay_getBestBlockHeight")]
+    fn get_best_block_height(&self, at: Option<BlockHash>) -> Result<u32>;
+
+    #[rpc(name = "btcRelay_getBlockHeader")]
+    fn get_block_header(
+        &self,
+        block_hash: H256Le,
+        at: Option<BlockHash>,
+    ) -> Result<RichBlockHeader>;
+
+    #[rpc(name = "btcRelay_getBlockHeaderAtHeight")]
+    fn get_block_header_at_height(
+        &self,
+        block_height: u32,
+        at: Option<BlockHash>,
+    ) -> Result<RichBlockHeader>;
+
+    #[rpc(name = "btcRelay_getChains")]
+    fn get_chains(&self, at: Option<BlockHash>) -> Result<Vec<BlockChain>>;
+
+    #[rpc(name = "btcRelay_isTransactionVerifiable")]
+    fn is_transaction_verifiable(
+        &self,
+        tx_id: H256Le,
+        raw_merkle_proof: Vec<u8>,
+        confirmations: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<()>;
+
+    #[rpc(name = "btcRelay_getTransactionConsumers")]
+    fn get_transaction_consumers(
+        &self,
+        tx_id: H256Le,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(u32, TransactionConsumer)>>;
+
+    #[rpc(name = "btcRelay_getVerificationProfile")]
+    fn get_verification_profile(&self, at: Option<BlockHash>) -> Result<VerificationProfile>;
+}
+
+/// A struct that implements the [`BtcRelayApi`].
+pub struct BtcRelay<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> BtcRelay<C, B> {
+    /// Create new `BtcRelay` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        BtcRelay {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+pub enum Error {
+    RuntimeError,
+}
+
+impl From<Error> for i64 {
+    fn from(e: Error) -> i64 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+fn runtime_error<E: std::fmt::Debug>(e: E, msg: &str) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(Error::RuntimeError.into()),
+        message: msg.into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+fn handle_response<T, E: std::fmt::Debug>(
+    result: std::result::Result<std::result::Result<T, DispatchError>, E>,
+    msg: &str,
+) -> Result<T> {
+    result.map_or_else(
+        |e| Err(runtime_error(e, msg)),
+        |result| result.map_err(|e| runtime_error(e, msg)),
+    )
+}
+
+impl<C, Block, H256Le, RichBlockHeader, BlockChain, TransactionConsumer, VerificationProfile>
+    BtcRelayApi<
+        <Block as BlockT>::Hash,
+        H256Le,
+        RichBlockHeader,
+        BlockChain,
+        TransactionConsumer,
+        VerificationProfile,
+    > for BtcRelay<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: BtcRelayRuntimeApi<
+        Block,
+        H256Le,
+        RichBlockHeader,
+        BlockChain,
+        TransactionConsumer,
+        VerificationProfile,
+    >,
+    H256Le: Codec,
+    RichBlockHeader: Codec,
+    BlockChain: Codec,
+    TransactionConsumer: Codec,
+    VerificationProfile: Codec,
+{
+    fn get_best_block(&self, at: Option<<Block as BlockT>::Hash>) -> Result<H256Le> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_best_block(&at)
+            .map_err(|e| runtime_error(e, "Unable to fetch best block."))
+    }
+
+    fn get_best_block_height(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_best_block_height(&at)
+            .map_err(|e| runtime_error(e, "Unable to fetch best block height."))
+    }
+
+    fn get_block_header(
+        &self,
+        block_hash: H256Le,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<RichBlockHeader> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        handle_response(
+            api.get_block_header(&at, block_hash),
+            "Unable to fetch block header.",
+        )
+    }
+
+    fn get_block_header_at_height(
+        &self,
+        block_height: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<RichBlockHeader> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        handle_response(
+            api.get_block_header_at_height(&at, block_height),
+            "Unable to fetch block header.",
+        )
+    }
+
+    fn get_chains(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<BlockChain>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_chains(&at)
+            .map_err(|e| runtime_error(e, "Unable to fetch chains."))
+    }
+
+    fn is_transaction_verifiable(
+        &self,
+        tx_id: H256Le,
+        raw_merkle_proof: Vec<u8>,
+        confirmations: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<()> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        handle_response(
+            api.is_transaction_verifiable(&at, tx_id, raw_merkle_proof, confirmations),
+            "Transaction is not verifiable.",
+        )
+    }
+
+    fn get_transaction_consumers(
+        &self,
+        tx_id: H256Le,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(u32, TransactionConsumer)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_transaction_consumers(&at, tx_id)
+            .map_err(|e| runtime_error(e, "Unable to fetch transaction consumers."))
+    }
+
+    fn get_verification_profile(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<VerificationProfile> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_verification_profile(&at)
+            .map_err(|e| runtime_error(e, "Unable to fetch verification profile."))
+    }
+}
@@ -25,6 +25,26 @@ fn mine_genesis<T: Config>(account_id: T::AccountId, address: &BtcAddress, heigh
     block
 }
 
+fn mine_block<T: Config>(
+    account_id: T::AccountId,
+    prev: &Block,
+    address: &BtcAddress,
+    height: u32,
+) -> Block {
+    let block = BlockBuilder::new()
+        .with_previous_hash(prev.header.hash().unwrap())
+        .with_version(2)
+        .with_coinbase(address, 50, height)
+        .with_timestamp(1588813835)
+        .mine(U256::from(2).pow(254.into()))
+        .unwrap();
+
+    let block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
+    BtcRelay::<T>::_store_block_header(account_id, block_header, u32::MAX).unwrap();
+
+    block
+}
+
 fn mine_block_with_one_tx<T: Config>(
     account_id: T::AccountId,
     prev: Block,
@@ -67,7 +87,7 @@ fn mine_block_with_one_tx<T: Config>(
         .unwrap();
 
     let block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
-    BtcRelay::<T>::_store_block_header(account_id, block_header).unwrap();
+    BtcRelay::<T>::_store_block_header(account_id, block_header, u32::MAX).unwrap();
 
     (block, transaction)
 }
@@ -88,7 +108,7 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(origin), block_header, height.into())
     verify {
-        assert_eq!(BestBlockHeight::get(), height);
+        assert_eq!(<BestBlockHeight>::get(), height);
     }
 
     store_block_header {
@@ -120,7 +140,73 @@ benchmarks! {
         let raw_block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap())
             .expect("could not serialize block header");
 
-    }: _(RawOrigin::Signed(origin), raw_block_header)
+    }: _(RawOrigin::Signed(origin), raw_block_header, 0)
+
+    swap_main_blockchain {
+        let f in 1 .. 100;
+
+        let origin: T::AccountId = account("Origin", 0, 0);
+        BtcRelay::<T>::register_authorized_relayer(origin.clone());
+        <StableBitcoinConfirmations>::put(0);
+
+        let address = BtcAddress::P2PKH(H160::from([0; 20]));
+        let fork_address = BtcAddress::P2PKH(H160::from([1; 20]));
+        let genesis = mine_genesis::<T>(origin.clone(), &address, 0);
+
+        // main chain and fork with equal work
+        let mut main_chain_tip = genesis.clone();
+        let mut fork_tip = genesis;
+        for height in 1..=f {
+            main_chain_tip = mine_block::<T>(origin.clone(), &main_chain_tip, &address, height);
+            fork_tip = mine_block::<T>(origin.clone(), &fork_tip, &fork_address, height);
+        }
+
+        // the next fork block replaces f main chain blocks
+        let block = BlockBuilder::new()
+            .with_previous_hash(fork_tip.header.hash().unwrap())
+            .with_version(2)
+            .with_coinbase(&fork_address, 50, f + 1)
+            .with_timestamp(1588813835)
+            .mine(U256::from(2).pow(254.into())).unwrap();
+        let block_hash = block.header.hash().unwrap();
+
+        let raw_block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap())
+            .expect("could not serialize block header");
+
+    }: store_block_header(RawOrigin::Signed(origin), raw_block_header, f)
+    verify {
+        assert_eq!(<BestBlock>::get(), block_hash);
+    }
+
+    invalidate_block {
+        let f in 1 .. 100;
+        let c in 1 .. 100;
+
+        let origin: T::AccountId = account("Origin", 0, 0);
+        BtcRelay::<T>::register_authorized_relayer(origin.clone());
+
+        let address = BtcAddress::P2PKH(H160::from([0; 20]));
+        let fork_address = BtcAddress::P2PKH(H160::from([1; 20]));
+        let genesis = mine_genesis::<T>(origin.clone(), &address, 0);
+
+        // f invalid main chain blocks and a competing fork block
+        let mut main_chain_tip = genesis.clone();
+        for height in 1..=f {
+            main_chain_tip = mine_block::<T>(origin.clone(), &main_chain_tip, &address, height);
+        }
+        let fork_block = mine_block::<T>(origin.clone(), &genesis, &fork_address, 1);
+        // c - 1 forks that are checked for descendants of the invalid block
+        for i in 2..c {
+            let address = BtcAddress::P2PKH(H160::from([i as u8; 20]));
+            mine_block::<T>(origin.clone(), &fork_block, &address, 2);
+        }
+        let invalid_block_hash = BtcRelay::<T>::get_block_hash(MAIN_CHAIN_ID, 1).unwrap();
+
+    }: _(RawOrigin::Root, invalid_block_hash, f + c, c)
+    verify {
+        assert_ne!(<BestBlock>::get(), invalid_block_hash);
+        assert!(<InvalidatedBlocks>::contains_key(invalid_block_hash));
+    }
 
     verify_and_validate_transaction {
         let origin: T::AccountId = account("Origin", 0, 0);
@@ -179,6 +265,39 @@ benchmarks! {
 
     }: _(RawOrigin::Signed(origin), raw_tx, value.into(), address, Some(op_return))
 
+    set_checkpoint {
+        let checkpoint = Checkpoint {
+            block_height: 0,
+            block_hash: H256Le::zero(),
+            chain_work: U256::one(),
+        };
+    }: _(RawOrigin::Root, checkpoint)
+    verify {
+        assert_eq!(BtcRelay::<T>::checkpoint(0), Some(checkpoint));
+    }
+
+    remove_checkpoint {
+        let checkpoint = Checkpoint {
+            block_height: 0,
+            block_hash: H256Le::zero(),
+            chain_work: U256::one(),
+        };
+        BtcRelay::<T>::_set_checkpoint(checkpoint).unwrap();
+    }: _(RawOrigin::Root, 0)
+    verify {
+        assert_eq!(BtcRelay::<T>::checkpoint(0), None);
+    }
+
+    set_verification_profile {
+        let verification_profile = VerificationProfile {
+            difficulty_check: false,
+            ..Default::default()
+        };
+    }: _(RawOrigin::Root, verification_profile)
+    verify {
+        assert_eq!(BtcRelay::<T>::verification_profile(), verification_profile);
+    }
+
 }
 
 #[cfg(test)]
@@ -192,9 +311,14 @@ mod tests {
         ExtBuilder::build().execute_with(|| {
             assert_ok!(test_benchmark_initialize::<Test>());
             assert_ok!(test_benchmark_store_block_header::<Test>());
+            assert_ok!(test_benchmark_swap_main_blockchain::<Test>());
             assert_ok!(test_benchmark_verify_and_validate_transaction::<Test>());
             assert_ok!(test_benchmark_verify_transaction_inclusion::<Test>());
             assert_ok!(test_benchmark_validate_transaction::<Test>());
+            assert_ok!(test_benchmark_set_checkpoint::<Test>());
+            assert_ok!(test_benchmark_remove_checkpoint::<Test>());
+            assert_ok!(test_benchmark_set_verification_profile::<Test>());
+            assert_ok!(test_benchmark_invalidate_block::<Test>());
         });
     }
 }
@@ -0,0 +1,235 @@
+//! Minimal bitcoind JSON-RPC client used by the off-chain worker to fetch block headers.
+
+use bitcoin::types::{H256Le, RawBlockHeader};
+use sp_runtime::offchain::{http, Duration};
+use sp_std::{prelude::*, str, vec};
+
+/// Timeout of a single request to bitcoind in milliseconds
+const REQUEST_TIMEOUT_MS: u64 = 5_000;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The request could not be sent or timed out
+    Http,
+    /// bitcoind returned an error or a malformed result
+    InvalidResponse,
+}
+
+/// Client for the JSON-RPC interface of a bitcoind node
+pub struct Client<'a> {
+    url: &'a str,
+    authorization: Option<Vec<u8>>,
+}
+
+impl<'a> Client<'a> {
+    /// Creates a new client
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - address of the JSON-RPC interface, e.g. `http://127.0.0.1:8332`
+    /// * `credentials` - optional `user:password` used for basic authentication
+    pub fn new(url: &'a str, credentials: Option<&[u8]>) -> Self {
+        Client {
+            url,
+            authorization: credentials
+                .map(|credentials| [&b"Basic "[..], &base64_encode(credentials)[..]].concat()),
+        }
+    }
+
+    /// Returns the height of the best block known to bitcoind
+    pub fn get_block_count(&self) -> Result<u32, Error> {
+        let result = self.call("getblockcount", b"[]")?;
+        parse_u32(&result)
+    }
+
+    /// Returns the hash of the best chain block at the given height
+    ///
+    /// # Arguments
+    ///
+    /// * `height` - block height
+    pub fn get_block_hash(&self, height: u32) -> Result<H256Le, Error> {
+        let params = [&b"["[..], &encode_u32(height)[..], &b"]"[..]].concat();
+        let result = self.call("getblockhash", &params)?;
+        let bytes = hex::decode(parse_string(&result)?).map_err(|_| Error::InvalidResponse)?;
+        if bytes.len() != 32 {
+            return Err(Error::InvalidResponse);
+        }
+        Ok(H256Le::from_bytes_be(&bytes))
+    }
+
+    /// Returns the raw header of the block with the given hash
+    ///
+    /// # Arguments
+    ///
+    /// * `block_hash` - block hash
+    pub fn get_block_header(&self, block_hash: H256Le) -> Result<RawBlockHeader, Error> {
+        let params = [
+            &b"[\""[..],
+            block_hash.to_hex_be().as_bytes(),
+            &b"\",false]"[..],
+        ]
+        .concat();
+        let result = self.call("getblockheader", &params)?;
+        let raw_block_header =
+            RawBlockHeader::from_hex(parse_string(&result)?).map_err(|_| Error::InvalidResponse)?;
+        if raw_block_header.hash() != block_hash {
+            return Err(Error::InvalidResponse);
+        }
+        Ok(raw_block_header)
+    }
+
+    /// Calls a JSON-RPC method and returns the raw `result` value
+    fn call(&self, method: &str, params: &[u8]) -> Result<Vec<u8>, Error> {
+        let body = [
+            &br#"{"jsonrpc":"1.0","id":"btc-relay","method":""#[..],
+            method.as_bytes(),
+            &br#"","params":"#[..],
+            params,
+            &b"}"[..],
+        ]
+        .concat();
+
+        let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(REQUEST_TIMEOUT_MS));
+        let mut request = http::Request::post(self.url, vec![body])
+            .add_header("Content-Type", "application/json");
+        if let Some(authorization) = &self.authorization {
+            // base64 is always valid utf8
+            let authorization = str::from_utf8(authorization).map_err(|_| Error::Http)?;
+            request = request.add_header("Authorization", authorization);
+        }
+
+        let pending = request.deadline(deadline).send().map_err(|_| Error::Http)?;
+        let response = pending
+            .try_wait(deadline)
+            .map_err(|_| Error::Http)?
+            .map_err(|_| Error::Http)?;
+        if response.code != 200 {
+            return Err(Error::InvalidResponse);
+        }
+
+        let body = response.body().collect::<Vec<u8>>();
+        extract_result(&body).map(|result| result.to_vec())
+    }
+}
+
+/// Extracts the raw `result` value from a JSON-RPC response. Only strings
+/// without escape sequences and unsigned integers are supported.
+fn extract_result(body: &[u8]) -> Result<&[u8], Error> {
+    const KEY: &[u8] = b"\"result\":";
+    let start = body
+        .windows(KEY.len())
+        .position(|window| window == KEY)
+        .ok_or(Error::InvalidResponse)?
+        + KEY.len();
+    let value = &body[start..];
+    let value = &value[value
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .ok_or(Error::InvalidResponse)?..];
+
+    let len = if value[0] == b'"' {
+        value[1..]
+            .iter()
+            .position(|&c| c == b'"')
+            .ok_or(Error::InvalidResponse)?
+            + 2
+    } else {
+        value
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .unwrap_or(value.len())
+    };
+    Ok(&value[..len])
+}
+
+fn parse_string(value: &[u8]) -> Result<&[u8], Error> {
+    match value {
+        [b'"', string @ .., b'"'] => Ok(string),
+        _ => Err(Error::InvalidResponse),
+    }
+}
+
+fn parse_u32(value: &[u8]) -> Result<u32, Error> {
+    str::from_utf8(value)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .ok_or(Error::InvalidResponse)
+}
+
+fn encode_u32(value: u32) -> Vec<u8> {
+    let mut digits = Vec::new();
+    let mut value = value;
+    loop {
+        digits.push(b'0' + (value % 10) as u8);
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+fn base64_encode(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity((input.len() + 2) / 3 * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let indices = [
+            bytes[0] >> 2,
+            (bytes[0] & 0x03) << 4 | bytes[1] >> 4,
+            (bytes[1] & 0x0f) << 2 | bytes[2] >> 6,
+            bytes[2] & 0x3f,
+        ];
+        for (i, index) in indices.iter().enumerate() {
+            if i <= chunk.len() {
+                output.push(BASE64_ALPHABET[*index as usize]);
+            } else {
+                output.push(b'=');
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_result_succeeds() {
+        assert_eq!(
+            extract_result(br#"{"result":"00ff","error":null,"id":"btc-relay"}"#),
+            Ok(&b"\"00ff\""[..])
+        );
+        assert_eq!(
+            extract_result(br#"{"result": 654321,"error":null,"id":"btc-relay"}"#),
+            Ok(&b"654321"[..])
+        );
+        assert_eq!(
+            extract_result(br#"{"result":null,"error":{"code":-8}}"#).and_then(parse_string),
+            Err(Error::InvalidResponse)
+        );
+        assert_eq!(extract_result(b"{}"), Err(Error::InvalidResponse));
+    }
+
+    #[test]
+    fn encode_u32_succeeds() {
+        assert_eq!(encode_u32(0), b"0".to_vec());
+        assert_eq!(encode_u32(654321), b"654321".to_vec());
+    }
+
+    #[test]
+    fn base64_encode_succeeds() {
+        assert_eq!(base64_encode(b"user:pass"), b"dXNlcjpwYXNz".to_vec());
+        assert_eq!(base64_encode(b"user:pw"), b"dXNlcjpwdw==".to_vec());
+        assert_eq!(base64_encode(b"user:pwd1"), b"dXNlcjpwd2Qx".to_vec());
+        assert_eq!(base64_encode(b"user:pw1"), b"dXNlcjpwdzE=".to_vec());
+    }
+}
@@ -55,3 +55,12 @@ pub(crate) mod sla {
         <sla::Module<T>>::event_update_relayer_sla(relayer_id, event)
     }
 }
+
+#[cfg_attr(test, mockable)]
+pub(crate) mod fee {
+    use frame_support::dispatch::DispatchResult;
+
+    pub fn reward_header_submission<T: fee::Config>(relayer: &T::AccountId) -> DispatchResult {
+        <fee::Module<T>>::reward_header_submission(relayer)
+    }
+}
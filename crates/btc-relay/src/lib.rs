@@ -5,6 +5,8 @@
 #![cfg_attr(test, feature(proc_macro_hygiene))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod bitcoind;
+
 mod ext;
 
 mod types;
@@ -27,17 +29,35 @@ extern crate mocktopus;
 use mocktopus::macros::mockable;
 
 use frame_support::debug;
+use frame_support::traits::Get;
 use frame_support::transactional;
+use frame_support::weights::{Pays, PostDispatchInfo, Weight};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::{DispatchError, DispatchResult},
-    ensure, IterableStorageMap,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    storage::{
+        migration::{remove_storage_prefix, take_storage_value},
+        unhashed, with_transaction,
+    },
+    IterableStorageDoubleMap, IterableStorageMap,
+};
+use frame_system::{
+    ensure_root, ensure_signed,
+    offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
 };
-use frame_system::ensure_signed;
 use primitive_types::U256;
-use sp_core::H160;
+use sp_core::{crypto::KeyTypeId, H160};
+use sp_runtime::offchain::{
+    storage::StorageValueRef,
+    storage_lock::{StorageLock, Time},
+    StorageKind,
+};
+use sp_runtime::traits::Saturating;
+use sp_runtime::TransactionOutcome;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::prelude::*;
+use sp_std::str;
 
 // Crates
 pub use bitcoin;
@@ -48,26 +68,139 @@ pub use bitcoin::Address as BtcAddress;
 use bitcoin::Error as BitcoinError;
 pub use bitcoin::PublicKey as BtcPublicKey;
 use security::types::ErrorCode;
-pub use types::RichBlockHeader;
+use types::{v0, ChainWorkMigrationCursor, Version};
+pub use types::{
+    BitcoinNetwork, BlockHeaderResult, Checkpoint, NetworkParameters, RichBlockHeader,
+    TransactionConsumer, VerificationProfile,
+};
 pub use weights::WeightInfo;
 
+/// Key type of the node-local keys used by the off-chain worker to sign
+/// block header submissions.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"btcr");
+
+/// Crypto of the keys used by the off-chain worker.
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    /// Identifier of the relayer key of the off-chain worker
+    pub struct RelayerId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for RelayerId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
 /// ## Configuration and Constants
 /// The pallet's configuration trait.
 /// For further reference, see the [specification](https://interlay.gitlab.io/polkabtc-spec/btcrelay-spec/spec/data-model.html).
-pub trait Config: frame_system::Config + security::Config + sla::Config {
+///
+/// The pallet is instantiable, each instance relays a different Bitcoin-like network.
+pub trait Config<I: Instance = DefaultInstance>:
+    frame_system::Config + security::Config + sla::Config + fee::Config
+{
     /// The overarching event type.
-    type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
+    type Event: From<Event<Self, I>> + Into<<Self as frame_system::Config>::Event>;
+
+    /// Consensus parameters of the relayed network.
+    type Network: Get<NetworkParameters>;
 
     /// Weight information for the extrinsics in this module.
     type WeightInfo: WeightInfo;
+
+    /// Handler for executed requests whose Bitcoin transaction was reorged out.
+    type OnReorg: OnReorg;
+
+    /// Off-chain worker of the instance, `()` disables it.
+    type OffchainWorker: OffchainWorker<Self::BlockNumber>;
+}
+
+/// Configuration of the off-chain worker relaying block headers from a local
+/// bitcoind node, only needed by runtimes that use `BitcoindRelayer`.
+pub trait OffchainConfig<I: Instance = DefaultInstance>:
+    Config<I> + CreateSignedTransaction<Call<Self, I>>
+{
+    /// Key used by the off-chain worker to sign block header submissions.
+    type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+}
+
+/// Off-chain worker run by the module after each block import.
+pub trait OffchainWorker<BlockNumber> {
+    /// Called with the number of the imported block.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_number` - current parachain block number
+    fn offchain_worker(block_number: BlockNumber);
+}
+
+impl<BlockNumber> OffchainWorker<BlockNumber> for () {
+    fn offchain_worker(_block_number: BlockNumber) {}
+}
+
+/// Off-chain worker relaying block headers from a local bitcoind node.
+pub struct BitcoindRelayer<T, I = DefaultInstance>(sp_std::marker::PhantomData<(T, I)>);
+
+impl<T: OffchainConfig<I>, I: Instance> OffchainWorker<T::BlockNumber> for BitcoindRelayer<T, I> {
+    fn offchain_worker(block_number: T::BlockNumber) {
+        if let Err(err) = Module::<T, I>::relay_block_headers(block_number) {
+            debug::warn!("BTC-Relay off-chain worker failed: {:?}", err);
+        }
+    }
+}
+
+/// Handler for executed requests whose Bitcoin transaction was included in a
+/// block that has been removed from the main chain by a reorg.
+pub trait OnReorg {
+    /// Called for every request that consumed a transaction of a reorged out block.
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - the request that consumed the transaction
+    /// * `block_hash` - the hash of the reorged out block
+    fn on_reorg(consumer: TransactionConsumer, block_hash: H256Le);
+}
+
+#[cfg_attr(test, mockable)]
+impl OnReorg for () {
+    fn on_reorg(_consumer: TransactionConsumer, _block_hash: H256Le) {}
+}
+
+macro_rules! impl_on_reorg_for_tuple {
+    ($($handler:ident),+) => {
+        impl<$($handler: OnReorg),+> OnReorg for ($($handler,)+) {
+            fn on_reorg(consumer: TransactionConsumer, block_hash: H256Le) {
+                $($handler::on_reorg(consumer, block_hash);)+
+            }
+        }
+    };
 }
 
+impl_on_reorg_for_tuple!(A);
+impl_on_reorg_for_tuple!(A, B);
+impl_on_reorg_for_tuple!(A, B, C);
+impl_on_reorg_for_tuple!(A, B, C, D);
+
 /// Difficulty Adjustment Interval
 pub const DIFFICULTY_ADJUSTMENT_INTERVAL: u32 = 2016;
 
 /// Target Timespan: 2 weeks (1209600 seconds)
 pub const TARGET_TIMESPAN: u32 = 1_209_600;
 
+/// Target Spacing: 10 minutes (600 seconds)
+pub const TARGET_SPACING: u32 = 600;
+
+/// Number of previous blocks used to compute the median time past
+pub const MEDIAN_TIME_SPAN: u32 = 11;
+
 // Used in Bitcoin's retarget algorithm
 pub const TARGET_TIMESPAN_DIVISOR: u32 = 4;
 
@@ -89,23 +222,65 @@ pub const UNROUNDED_MAX_TARGET: U256 = U256([
     0x0000_0000_ffff_ffffu64,
 ]);
 
+/// Regtest Maximum Target
+/// 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF
+pub const REGTEST_MAX_TARGET: U256 = U256([
+    <u64>::max_value(),
+    <u64>::max_value(),
+    <u64>::max_value(),
+    0x7fff_ffff_ffff_ffffu64,
+]);
+
+/// Signet Maximum Target
+/// 0x00000377AE000000000000000000000000000000000000000000000000000000
+pub const SIGNET_MAX_TARGET: U256 = U256([0, 0, 0, 0x0000_0377_ae00_0000u64]);
+
 /// Main chain id
 pub const MAIN_CHAIN_ID: u32 = 0;
 
+/// Maximum number of stored positions visited per block by the chainwork migration
+pub const CHAIN_WORK_MIGRATION_STEPS: u32 = 512;
+
+/// Maximum number of forks checked for pruning per block
+pub const PRUNING_MAX_SCANNED_FORKS: u32 = 16;
+
+/// Offchain local storage key of the bitcoind JSON-RPC url, prefixed by the instance
+pub const OFFCHAIN_BITCOIND_URL: &[u8] = b"bitcoind-url";
+
+/// Offchain local storage key of the bitcoind `user:password` credentials, prefixed by the instance
+pub const OFFCHAIN_BITCOIND_CREDENTIALS: &[u8] = b"bitcoind-credentials";
+
+/// Offchain local storage key of the best block and parachain height of the last submission
+const OFFCHAIN_LAST_SUBMISSION: &[u8] = b"last-submission";
+
+/// Offchain local storage key of the lock held while the off-chain worker submits block headers
+const OFFCHAIN_LOCK: &[u8] = b"lock";
+
+/// Maximum number of block headers submitted by the off-chain worker at once
+pub const OFFCHAIN_MAX_HEADERS: u32 = 16;
+
+/// Number of parachain blocks the off-chain worker waits for its last
+/// submission to be included before submitting again
+pub const OFFCHAIN_RESUBMISSION_DELAY: u32 = 5;
+
+/// Maximum number of blocks below the best block the off-chain worker searches
+/// for the common ancestor with bitcoind's main chain
+pub const OFFCHAIN_MAX_REORG_DEPTH: u32 = 144;
+
 /// Number of outputs expected in the accepted transaction format
 /// See: <https://interlay.gitlab.io/polkabtc-spec/btcrelay-spec/intro/accepted-format.html>
 pub const ACCEPTED_NO_TRANSACTION_OUTPUTS: u32 = 2;
 
 // This pallet's storage items.
 decl_storage! {
-    trait Store for Module<T: Config> as BTCRelay {
+    trait Store for Module<T: Config<I>, I: Instance = DefaultInstance> as BTCRelay {
         /// ## Storage
         /// Store Bitcoin block headers
         BlockHeaders: map hasher(blake2_128_concat) H256Le => RichBlockHeader<T::AccountId>;
 
-        /// Priority queue of BlockChain elements, ordered by the maximum height (descending).
-        /// The first index into this mapping (0) is considered to be the longest chain. The value
-        /// of the entry is the index into `ChainsIndex` to retrieve the `BlockChain`.
+        /// Priority queue of BlockChain elements, ordered by the accumulated chainwork (descending).
+        /// The first index into this mapping (0) is considered to be the chain with the most work.
+        /// The value of the entry is the index into `ChainsIndex` to retrieve the `BlockChain`.
         Chains: map hasher(blake2_128_concat) u32 => Option<u32>;
 
         /// Auxiliary mapping of chains ids to `BlockChain` entries. The first index into this
@@ -127,26 +302,77 @@ decl_storage! {
         /// Registers the parachain height upon storing a block
         ParachainHeight: map hasher(blake2_128_concat) H256Le => T::BlockNumber;
 
+        /// Target of the last block, up to and including the given block, that was not mined
+        /// under the minimum difficulty rule. Only tracked on networks allowing such blocks.
+        LastRegularTargets: map hasher(blake2_128_concat) H256Le => Option<U256>;
+
+        /// Transaction outputs, identified by txid and output index, consumed by executed requests
+        UsedTransactions: double_map hasher(blake2_128_concat) H256Le, hasher(blake2_128_concat) u32 => Option<TransactionConsumer>;
+
+        /// Transactions that fulfilled executed requests, so that further payments for a request can be detected
+        FulfillingTransactions: map hasher(blake2_128_concat) TransactionConsumer => Option<H256Le>;
+
+        /// Block headers invalidated by governance and their removed descendants, which can not be
+        /// stored again
+        InvalidatedBlocks: map hasher(blake2_128_concat) H256Le => bool;
+
+        /// Requests that consumed a transaction included in the block, removed once the block is pruned
+        /// or the requests are stable
+        BlockConsumers get(fn block_consumers): map hasher(blake2_128_concat) H256Le => Vec<TransactionConsumer>;
+
+        /// Requests, with the block including their transaction, that are stable once the main chain
+        /// reaches the height
+        StableBlockConsumers: map hasher(twox_64_concat) u32 => Vec<(H256Le, TransactionConsumer)>;
+
         /// Global security parameter k for stable Bitcoin transactions
         StableBitcoinConfirmations get(fn bitcoin_confirmations) config(): u32;
 
         /// Global security parameter k for stable Parachain transactions
         StableParachainConfirmations get(fn parachain_confirmations) config(): T::BlockNumber;
 
-        /// Whether the module should perform difficulty checks.
-        DisableDifficultyCheck get(fn disable_difficulty_check) config(): bool;
+        /// Trusted blocks by height, any header or fork conflicting with these is rejected.
+        /// If non-empty, BTC-Relay can only be initialized from one of them.
+        Checkpoints get(fn checkpoint): map hasher(twox_64_concat) u32 => Option<Checkpoint>;
+
+        /// Number of main chain blocks below the best block whose headers are kept,
+        /// deeper headers are pruned. Pruning is disabled if set to 0.
+        PruningDepth get(fn pruning_depth) config(): u32;
+
+        /// Number of blocks a fork may fall behind the best block before it is
+        /// removed. Removal of stale forks is disabled if set to 0.
+        MaxForkDepth get(fn max_fork_depth) config(): u32;
 
-        /// Whether the module should perform inclusion checks.
-        DisableInclusionCheck get(fn disable_inclusion_check) config(): bool;
+        /// Maximum number of block headers removed per parachain block.
+        MaxPrunedHeadersPerBlock get(fn max_pruned_headers_per_block) config(): u32;
 
-        /// Whether the module should perform OP_RETURN checks.
-        DisableOpReturnCheck get(fn disable_op_return_check) config(): bool;
+        /// All main chain block headers below this height have been pruned.
+        PrunedHeight get(fn pruned_height): u32;
 
-        /// Whether to disable relayer authorization.
-        DisableRelayerAuth get(fn disable_relayer_auth) config(): bool;
+        /// Id of the next fork checked for pruning.
+        ForkPruningCursor get(fn fork_pruning_cursor): u32;
+
+        /// Checks performed by the module, all checks are enabled by default.
+        ActiveVerificationProfile get(fn verification_profile) config(verification_profile): VerificationProfile;
 
         /// Accounts that are able to submit block headers.
         AuthorizedRelayers: map hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Progress of the multi-block chainwork migration to `Version::V1`,
+        /// BTC-Relay can not be used until it has completed.
+        ChainWorkMigration get(fn chain_work_migration): Option<ChainWorkMigrationCursor>;
+
+        /// Build storage at V3 (requires default 0).
+        StorageVersion get(fn storage_version) build(|_| Version::V3): Version = Version::V0;
+    }
+    add_extra_genesis {
+        config(checkpoints): Vec<Checkpoint>;
+        // don't allow a mainnet relay without inclusion checks
+        build(|config| {
+            Module::<T, I>::ensure_verification_profile_allowed(&config.verification_profile).unwrap();
+            for checkpoint in &config.checkpoints {
+                <Checkpoints<I>>::insert(checkpoint.block_height, checkpoint);
+            }
+        })
     }
 }
 
@@ -158,20 +384,71 @@ macro_rules! extract_op_return {
                     data
                 } else
             )*
-            { return Err(Error::<T>::NotOpReturn.into()); }
+            { return Err(Error::<T, I>::NotOpReturn.into()); }
         }
     };
 }
 
 decl_module! {
-    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+    pub struct Module<T: Config<I>, I: Instance = DefaultInstance> for enum Call where origin: T::Origin {
         // Initialize errors
-        type Error = Error<T>;
+        type Error = Error<T, I>;
+
+        /// Consensus and address parameters of the relayed network.
+        const Network: NetworkParameters = T::Network::get();
 
         // Initializing events
         fn deposit_event() = default;
 
-        /// One time function to initialize the BTC-Relay with the first block
+        /// Upgrade the runtime depending on the current `StorageVersion`.
+        /// The chainwork of stored block headers is migrated over multiple blocks
+        /// in `on_initialize`, during which BTC-Relay can not be used.
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+
+            if Self::storage_version() == Version::V0 {
+                weight = weight.saturating_add(Self::start_chain_work_migration());
+                <StorageVersion<I>>::put(Version::V1);
+                weight = weight.saturating_add(T::DbWeight::get().writes(1));
+            }
+
+            if Self::storage_version() == Version::V1 {
+                // the network is configured in the runtime now
+                remove_storage_prefix(I::PREFIX.as_bytes(), b"Network", &[]);
+                <StorageVersion<I>>::put(Version::V2);
+                weight = weight.saturating_add(T::DbWeight::get().writes(2));
+            }
+
+            if Self::storage_version() == Version::V2 {
+                Self::migrate_verification_profile_v2_to_v3();
+                <StorageVersion<I>>::put(Version::V3);
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 6));
+            }
+
+            weight
+        }
+
+        fn on_initialize(_n: T::BlockNumber) -> Weight {
+            if let Some(migration) = Self::chain_work_migration() {
+                return Self::migrate_chain_work_step(migration)
+                    .saturating_add(T::DbWeight::get().reads(1));
+            }
+            Self::prune_block_headers().saturating_add(T::DbWeight::get().reads(1))
+        }
+
+        /// Relays block headers from a local bitcoind node, if configured.
+        fn offchain_worker(block_number: T::BlockNumber) {
+            T::OffchainWorker::offchain_worker(block_number);
+        }
+
+        /// One time function to initialize the BTC-Relay with the first block.
+        /// If checkpoints are configured, the block must be one of them and
+        /// its trusted chainwork is used.
+        ///
+        /// There is no initialization from a succinct proof of work: Bitcoin headers
+        /// commit neither to an MMR of their ancestors (FlyClient) nor to superblock
+        /// interlinks (NiPoPoW), so sampled headers can not be proven to belong to
+        /// one chain without relaying all headers in between.
         ///
         /// # Arguments
         ///
@@ -183,6 +460,8 @@ decl_module! {
         /// 	- One storage read to check that parachain is not shutdown. O(1)
         /// 	- One storage read to check if relayer authorization is disabled. O(1)
         /// 	- One storage read to check if relayer is authorized. O(1)
+        /// 	- One storage read to retrieve the checkpoint at the block height. O(1)
+        /// 	- One storage read to check if any checkpoint is configured. O(1)
         /// - Storage Writes:
         ///     - One storage write to store block hash. O(1)
         ///     - One storage write to store block header. O(1)
@@ -194,7 +473,7 @@ decl_module! {
         ///
         /// Total Complexity: O(1)
         /// # </weight>
-        #[weight = <T as Config>::WeightInfo::initialize()]
+        #[weight = <T as Config<I>>::WeightInfo::initialize()]
         #[transactional]
         fn initialize(
             origin,
@@ -211,64 +490,178 @@ decl_module! {
         /// # Arguments
         ///
         /// * `raw_block_header` - 80 byte raw Bitcoin block header.
+        /// * `max_reorg_depth` - maximum number of main chain blocks the header may replace,
+        ///                       see `get_reorg_depth_bound`.
         ///
         /// # <weight>
-        /// Key: C (len of chains), P (len of positions)
+        /// Key: C (len of chains), P (len of positions), F (number of main chain blocks replaced by a reorg),
+        /// K (number of checkpoints)
         /// - Storage Reads:
         /// 	- One storage read to check that parachain is not shutdown. O(1)
         /// 	- One storage read to check if relayer authorization is disabled. O(1)
         /// 	- One storage read to check if relayer is authorized. O(1)
         /// 	- One storage read to check if block header is stored. O(1)
+        /// 	- Two storage reads to check if the block header or its parent was invalidated. O(1)
         /// 	- One storage read to retrieve parent block hash. O(1)
         /// 	- One storage read to check if difficulty check is disabled. O(1)
         /// 	- One storage read to retrieve last re-target. O(1)
+        /// 	- One storage read to retrieve the last regular target on networks that allow
+        /// 	  minimum difficulty blocks. O(1)
+        /// 	- One storage read to retrieve the checkpoint at the block height. O(1)
+        /// 	- If the header extends a fork, one storage read per checkpoint and one per
+        /// 	  checkpoint above the fork start to check the main chain. O(K)
         /// 	- One storage read to retrieve all Chains. O(C)
         /// - Storage Writes:
         ///     - One storage write to store block hash. O(1)
         ///     - One storage write to store block header. O(1)
+        ///     - One storage write to store the last regular target on networks that allow
+        ///       minimum difficulty blocks. O(1)
         /// 	- One storage mutate to extend main chain. O(1)
         ///     - One storage write to store best block hash. O(1)
         ///     - One storage write to store best block height. O(1)
+        ///     - One storage take and one storage mutate to clear the requests that became
+        ///       stable. O(1)
         /// - Notable Computation:
         /// 	- O(P) sort to reorg chains.
+        /// 	- O(F) block header updates if the main chain is swapped, bounded by
+        /// 	  `max_reorg_depth`.
         /// - External Module Operations:
         /// 	- Updates relayer sla score.
         /// - Events:
         /// 	- One event for block stored (fork or extension).
         ///
-        /// Total Complexity: O(C + P)
+        /// Total Complexity: O(C + P + F + K)
+        ///
+        /// The weight of a reorg is refunded if the main chain was not swapped.
         /// # </weight>
-        #[weight = <T as Config>::WeightInfo::store_block_header()]
+        ///
+        /// The first submission of a new main chain block header by an authorized
+        /// relayer is free of transaction fees and rewarded by the fee module.
+        #[weight = <T as Config<I>>::WeightInfo::store_block_header()
+            .saturating_add(<T as Config<I>>::WeightInfo::swap_main_blockchain(*max_reorg_depth))]
         #[transactional]
         fn store_block_header(
-            origin, raw_block_header: RawBlockHeader
-        ) -> DispatchResult {
+            origin, raw_block_header: RawBlockHeader, max_reorg_depth: u32
+        ) -> DispatchResultWithPostInfo {
             let relayer = ensure_signed(origin)?;
-            Self::_store_block_header(relayer, raw_block_header)
+            let block_header_hash = raw_block_header.hash();
+            let reorg_depth =
+                Self::_store_block_header(relayer.clone(), raw_block_header, max_reorg_depth)?;
+            Ok(PostDispatchInfo {
+                actual_weight: Some(Self::store_block_header_weight(reorg_depth)),
+                pays_fee: Self::reward_main_chain_header(&relayer, block_header_hash),
+            })
         }
 
-        /// Stores multiple new block headers
+        /// Stores multiple new block headers. The longest valid prefix of the batch
+        /// is stored, headers that are already stored are skipped. The outcome for
+        /// each processed header is reported in a `StoreBlockHeaders` event.
+        ///
+        /// At most one reorg is processed per batch: once the main chain has been
+        /// swapped, the batch stops at the first header that does not extend the
+        /// main chain.
         ///
         /// # Arguments
         ///
         /// * `raw_block_headers` - vector of Bitcoin block headers.
+        /// * `max_reorg_depth` - maximum number of main chain blocks the reorg may replace.
         ///
         /// # <weight>
-        /// - As in `store_block_header`, multiplied by the number of concatenated headers.
+        /// - As in `store_block_header` for every header, plus the weight of a
+        ///   single reorg of at most `max_reorg_depth` blocks.
+        /// - Skipped and unprocessed headers as well as an unused reorg are refunded.
         /// # </weight>
-        #[weight = <T as Config>::WeightInfo::store_block_header().saturating_mul(raw_block_headers.len() as u64)]
+        ///
+        /// The batch is free of transaction fees if at least one header was stored
+        /// and every stored header is a new main chain block header submitted by an
+        /// authorized relayer. Skipped duplicates do not make the batch pay.
+        #[weight = <T as Config<I>>::WeightInfo::store_block_header()
+            .saturating_mul(raw_block_headers.len() as u64)
+            .saturating_add(<T as Config<I>>::WeightInfo::swap_main_blockchain(*max_reorg_depth))]
         #[transactional]
         fn store_block_headers(
-            origin, raw_block_headers: Vec<RawBlockHeader>
-        ) -> DispatchResult {
+            origin, raw_block_headers: Vec<RawBlockHeader>, max_reorg_depth: u32
+        ) -> DispatchResultWithPostInfo {
             let relayer = ensure_signed(origin)?;
-            // TODO: can we optimize this?
-            for raw_block_header in raw_block_headers {
-                Self::_store_block_header(relayer.clone(), raw_block_header)?;
-            }
+            Self::_store_block_headers(relayer, raw_block_headers, max_reorg_depth)
+        }
+
+        /// Adds or replaces the checkpoint at the given height (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `checkpoint` - trusted block height, hash and chainwork
+        #[weight = <T as Config<I>>::WeightInfo::set_checkpoint()]
+        #[transactional]
+        fn set_checkpoint(origin, checkpoint: Checkpoint) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::_set_checkpoint(checkpoint)?;
+            Self::deposit_event(<Event<T, I>>::SetCheckpoint(checkpoint.block_height, checkpoint.block_hash));
+            Ok(())
+        }
+
+        /// Removes the checkpoint at the given height (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `block_height` - height of the checkpoint to remove
+        #[weight = <T as Config<I>>::WeightInfo::remove_checkpoint()]
+        #[transactional]
+        fn remove_checkpoint(origin, block_height: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            <Checkpoints<I>>::remove(block_height);
+            Self::deposit_event(<Event<T, I>>::RemoveCheckpoint(block_height));
+            Ok(())
+        }
+
+        /// Sets the checks performed by the module (only executable by the Root account).
+        /// Inclusion checks can not be disabled when relaying Bitcoin mainnet.
+        ///
+        /// # Arguments
+        ///
+        /// * `verification_profile` - checks to perform
+        #[weight = <T as Config<I>>::WeightInfo::set_verification_profile()]
+        #[transactional]
+        fn set_verification_profile(origin, verification_profile: VerificationProfile) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::ensure_verification_profile_allowed(&verification_profile)?;
+            <ActiveVerificationProfile<I>>::put(verification_profile);
+            Self::deposit_event(<Event<T, I>>::SetVerificationProfile(verification_profile));
             Ok(())
         }
 
+        /// Marks a block header and all of its descendants invalid and removes them
+        /// from storage. If the main chain is affected, the valid fork with the most
+        /// accumulated work becomes the new main chain (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `block_hash` - hash of the invalid block header
+        /// * `max_headers` - maximum number of headers at or above the invalid block,
+        ///                   see `get_invalidation_bound`
+        /// * `max_chains` - maximum number of chains
+        ///
+        /// # <weight>
+        /// Key: F (number of headers at or above the invalid block), C (number of chains)
+        /// - O(C) chains are checked for descendants of the invalid block and re-sorted.
+        /// - O(F) headers are removed and recorded as invalid.
+        /// - Headers that are not descendants of the invalid block are refunded.
+        /// # </weight>
+        #[weight = <T as Config<I>>::WeightInfo::invalidate_block(*max_headers, *max_chains)]
+        #[transactional]
+        fn invalidate_block(
+            origin, block_hash: H256Le, max_headers: u32, max_chains: u32
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let (headers, chains) = Self::get_invalidation_bound(&block_hash);
+            ensure!(
+                headers <= max_headers && chains <= max_chains,
+                Error::<T, I>::InvalidationBoundExceeded
+            );
+            let (removed_headers, chains) = Self::_invalidate_block(block_hash)?;
+            Ok(Some(<T as Config<I>>::WeightInfo::invalidate_block(removed_headers, chains)).into())
+        }
+
         /// Verifies the inclusion of `tx_id` and validates the given raw Bitcoin transaction, according to the
         /// supported transaction format (see <https://interlay.gitlab.io/polkabtc-spec/btcrelay-spec/intro/accepted-format.html>)
         ///
@@ -282,7 +675,7 @@ decl_module! {
         /// * `payment_value` - value of BTC sent in the 1st / payment UTXO of the transaction
         /// * `recipient_btc_address` - 20 byte Bitcoin address of recipient of the BTC in the 1st  / payment UTXO
         /// * `op_return_id` - 32 byte hash identifier expected in OP_RETURN (replay protection)
-        #[weight = <T as Config>::WeightInfo::verify_and_validate_transaction()]
+        #[weight = <T as Config<I>>::WeightInfo::verify_and_validate_transaction()]
         #[transactional]
         fn verify_and_validate_transaction(
             origin,
@@ -300,7 +693,7 @@ decl_module! {
 
             // Check that the passed raw_tx indeed matches the tx_id used for
             // transaction inclusion verification
-            ensure!(tx_id == transaction.tx_id(), Error::<T>::InvalidTxid);
+            ensure!(tx_id == transaction.tx_id(), Error::<T, I>::InvalidTxid);
 
             // Verify that the transaction is indeed included in the main chain
             // Check for Parachain RUNNING state is performed here
@@ -332,7 +725,7 @@ decl_module! {
         /// 	- One storage read to check stable bitcoin confirmations. O(1)
         /// 	- One storage read to check stable parachain confirmations. O(1)
         /// # </weight>
-        #[weight = <T as Config>::WeightInfo::verify_transaction_inclusion()]
+        #[weight = <T as Config<I>>::WeightInfo::verify_transaction_inclusion()]
         #[transactional]
         fn verify_transaction_inclusion(
             origin,
@@ -355,7 +748,7 @@ decl_module! {
         /// * `payment_value` - value of BTC sent to the recipient
         /// * `recipient_btc_address` - expected Bitcoin address of recipient (p2sh, p2pkh, p2wpkh)
         /// * `op_return_id` - 32 byte hash identifier expected in OP_RETURN (replay protection)
-        #[weight = <T as Config>::WeightInfo::validate_transaction()]
+        #[weight = <T as Config<I>>::WeightInfo::validate_transaction()]
         #[transactional]
         fn validate_transaction(
             origin,
@@ -372,7 +765,7 @@ decl_module! {
 }
 
 #[cfg_attr(test, mockable)]
-impl<T: Config> Module<T> {
+impl<T: Config<I>, I: Instance> Module<T, I> {
     /// Ensure the given `relayer` is authorized or
     /// return `Ok(())` if this check is disabled.
     ///
@@ -381,18 +774,54 @@ impl<T: Config> Module<T> {
     /// * `relayer` - block submitter
     fn ensure_relayer_authorized(relayer: T::AccountId) -> DispatchResult {
         ensure!(
-            Self::disable_relayer_auth() || <AuthorizedRelayers<T>>::contains_key(relayer),
-            Error::<T>::RelayerNotAuthorized
+            Self::disable_relayer_auth() || <AuthorizedRelayers<T, I>>::contains_key(relayer),
+            Error::<T, I>::RelayerNotAuthorized
+        );
+        Ok(())
+    }
+
+    /// Ensure the given verification profile may be used on the relayed network.
+    ///
+    /// # Arguments
+    ///
+    /// * `verification_profile` - checks to perform
+    fn ensure_verification_profile_allowed(
+        verification_profile: &VerificationProfile,
+    ) -> DispatchResult {
+        ensure!(
+            verification_profile.inclusion_check || !Self::is_mainnet(),
+            Error::<T, I>::InclusionCheckRequired
         );
         Ok(())
     }
 
+    /// Whether the relayed network is a production network
+    fn is_mainnet() -> bool {
+        T::Network::get().mainnet
+    }
+
+    fn disable_difficulty_check() -> bool {
+        !Self::verification_profile().difficulty_check
+    }
+
+    fn disable_inclusion_check() -> bool {
+        !Self::verification_profile().inclusion_check
+    }
+
+    fn disable_op_return_check() -> bool {
+        !Self::verification_profile().op_return_check
+    }
+
+    fn disable_relayer_auth() -> bool {
+        !Self::verification_profile().relayer_auth
+    }
+
     pub fn register_authorized_relayer(relayer: T::AccountId) {
-        <AuthorizedRelayers<T>>::insert(relayer, true);
+        <AuthorizedRelayers<T, I>>::insert(relayer, true);
     }
 
     pub fn deregister_authorized_relayer(relayer: T::AccountId) {
-        <AuthorizedRelayers<T>>::remove(relayer);
+        <AuthorizedRelayers<T, I>>::remove(relayer);
     }
 
     pub fn _initialize(
@@ -401,18 +830,40 @@ impl<T: Config> Module<T> {
         block_height: u32,
     ) -> DispatchResult {
         // Check if BTC-Relay was already initialized
-        ensure!(!Self::best_block_exists(), Error::<T>::AlreadyInitialized);
+        ensure!(
+            !Self::best_block_exists(),
+            Error::<T, I>::AlreadyInitialized
+        );
 
         // check if the relayer is registered
         Self::ensure_relayer_authorized(relayer.clone())?;
 
         // Parse the block header bytes to extract the required info
         let basic_block_header =
-            parse_block_header(&raw_block_header).map_err(|err| Error::<T>::from(err))?;
+            parse_block_header(&raw_block_header).map_err(|err| Error::<T, I>::from(err))?;
         let block_header_hash = raw_block_header.hash();
 
+        // start from the trusted chainwork of a checkpoint, if configured,
+        // otherwise the chainwork is accounted relative to the initial block
+        let chain_work = match Self::get_checkpoint(block_height) {
+            Some(checkpoint) => {
+                ensure!(
+                    checkpoint.block_hash == block_header_hash,
+                    Error::<T, I>::CheckpointConflict
+                );
+                checkpoint.chain_work
+            }
+            None => {
+                ensure!(
+                    <Checkpoints<I>>::iter().next().is_none(),
+                    Error::<T, I>::NoCheckpoint
+                );
+                basic_block_header.work()
+            }
+        };
+
         // construct the BlockChain struct
-        let blockchain = Self::initialize_blockchain(block_height, block_header_hash);
+        let blockchain = Self::initialize_blockchain(block_height, block_header_hash, chain_work);
         // Create rich block header
         let block_header = RichBlockHeader::<T::AccountId> {
             block_hash: block_header_hash,
@@ -420,6 +871,7 @@ impl<T: Config> Module<T> {
             block_height: block_height,
             chain_ref: blockchain.chain_id,
             account_id: relayer,
+            chain_work,
         };
 
         // Store a new BlockHeader struct in BlockHeaders
@@ -436,17 +888,24 @@ impl<T: Config> Module<T> {
         Self::set_best_block_height(block_height);
 
         // Emit a Initialized Event
-        Self::deposit_event(Event::Initialized(block_height, block_header_hash));
+        Self::deposit_event(<Event<T, I>>::Initialized(block_height, block_header_hash));
 
         Ok(())
     }
 
+    /// Stores a new block header.
+    ///
+    /// Returns the number of main chain blocks replaced if the header triggered
+    /// a reorg, zero otherwise. Fails if the reorg would replace more than
+    /// `max_reorg_depth` blocks.
     pub fn _store_block_header(
         relayer: T::AccountId,
         raw_block_header: RawBlockHeader,
-    ) -> DispatchResult {
+        max_reorg_depth: u32,
+    ) -> Result<u32, DispatchError> {
         // Make sure Parachain is not shutdown
         ext::security::ensure_parachain_status_not_shutdown::<T>()?;
+        Self::ensure_chain_work_migrated()?;
 
         // check if the relayer is registered
         Self::ensure_relayer_authorized(relayer.clone())?;
@@ -469,6 +928,12 @@ impl<T: Config> Module<T> {
         // Set the height of the block header
         let current_block_height = prev_block_height + 1;
 
+        // accumulate the work of the new block on top of its parent
+        let chain_work = prev_header
+            .chain_work
+            .checked_add(basic_block_header.work())
+            .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+
         // Update the blockchain
         // check if we create a new blockchain or extend the existing one
         debug::print!("Prev max height: {:?}\n", prev_blockchain.max_height);
@@ -476,12 +941,31 @@ impl<T: Config> Module<T> {
         let is_fork = prev_blockchain.max_height != prev_block_height;
         debug::print!("Fork detected: {:?}\n", is_fork);
 
+        // the height of the first block that is not shared with the main chain
+        let fork_start_height = if is_fork {
+            Some(current_block_height)
+        } else if prev_blockchain.chain_id != MAIN_CHAIN_ID {
+            Some(prev_blockchain.start_height)
+        } else {
+            None
+        };
+        Self::ensure_no_checkpoint_conflict(
+            &block_header_hash,
+            current_block_height,
+            fork_start_height,
+        )?;
+
         let blockchain = if is_fork {
             // create new blockchain element
-            Self::create_blockchain(current_block_height, block_header_hash)
+            Self::create_blockchain(current_block_height, block_header_hash, chain_work)
         } else {
             // extend the current chain
-            Self::extend_blockchain(current_block_height, &block_header_hash, prev_blockchain)?
+            Self::extend_blockchain(
+                current_block_height,
+                &block_header_hash,
+                chain_work,
+                prev_blockchain,
+            )?
         };
 
         // Create rich block header
@@ -491,12 +975,14 @@ impl<T: Config> Module<T> {
             block_height: current_block_height,
             chain_ref: blockchain.chain_id,
             account_id: relayer.clone(),
+            chain_work,
         };
 
         // Store a new BlockHeader struct in BlockHeaders
         Self::set_block_header_from_hash(block_header_hash, &block_header);
 
         // Storing the blockchain depends if we extend or create a new chain
+        let mut reorg_depth = 0;
         if is_fork {
             // create a new chain
             // Store a pointer to BlockChain in ChainsIndex
@@ -506,14 +992,15 @@ impl<T: Config> Module<T> {
         } else {
             // extended the chain
             // Update the pointer to BlockChain in ChainsIndex
-            <ChainsIndex>::mutate(blockchain.chain_id, |_b| &blockchain);
+            <ChainsIndex<I>>::mutate(blockchain.chain_id, |_b| &blockchain);
 
             // check if ordering of Chains needs updating
-            Self::check_and_do_reorg(&blockchain)?;
+            reorg_depth = Self::check_and_do_reorg(&blockchain, max_reorg_depth)?;
 
             if blockchain.chain_id == MAIN_CHAIN_ID {
                 Self::set_best_block(block_header_hash);
-                Self::set_best_block_height(current_block_height)
+                Self::set_best_block_height(current_block_height);
+                Self::clear_stable_block_consumers(prev_block_height, current_block_height);
             }
         };
 
@@ -524,20 +1011,182 @@ impl<T: Config> Module<T> {
 
         if current_best_block == block_header_hash {
             // extends the main chain
-            Self::deposit_event(Event::StoreMainChainHeader(
+            Self::deposit_event(<Event<T, I>>::StoreMainChainHeader(
                 current_block_height,
                 block_header_hash,
             ));
         } else {
             // created a new fork or updated an existing one
-            Self::deposit_event(Event::StoreForkHeader(
+            Self::deposit_event(<Event<T, I>>::StoreForkHeader(
                 blockchain.chain_id,
                 current_block_height,
                 block_header_hash,
             ));
         };
 
-        Ok(())
+        Ok(reorg_depth)
+    }
+
+    /// Stores the longest valid prefix of a batch of block headers, skipping
+    /// headers that are already stored. Each header is stored atomically.
+    ///
+    /// Fails if no header of the batch could be stored or skipped.
+    pub fn _store_block_headers(
+        relayer: T::AccountId,
+        raw_block_headers: Vec<RawBlockHeader>,
+        max_reorg_depth: u32,
+    ) -> DispatchResultWithPostInfo {
+        let mut results = Vec::with_capacity(raw_block_headers.len());
+        let mut actual_weight: Weight = 0;
+        let mut reorged = false;
+        let mut pays_fee = Pays::No;
+        let mut stored = false;
+
+        for raw_block_header in raw_block_headers {
+            let block_header_hash = raw_block_header.hash();
+
+            // headers submitted by racing relayers are skipped
+            if Self::block_header_exists(block_header_hash) {
+                actual_weight = actual_weight.saturating_add(T::DbWeight::get().reads(1));
+                results.push((block_header_hash, BlockHeaderResult::Duplicate));
+                continue;
+            }
+
+            // the declared weight only covers a single reorg
+            if reorged && !Self::extends_best_block(&raw_block_header) {
+                break;
+            }
+
+            let result = with_transaction(|| {
+                match Self::_store_block_header(relayer.clone(), raw_block_header, max_reorg_depth)
+                {
+                    Ok(reorg_depth) => TransactionOutcome::Commit(Ok(reorg_depth)),
+                    Err(err) => TransactionOutcome::Rollback(Err(err)),
+                }
+            });
+            match result {
+                Ok(reorg_depth) => {
+                    actual_weight =
+                        actual_weight.saturating_add(Self::store_block_header_weight(reorg_depth));
+                    reorged = reorged || reorg_depth > 0;
+                    results.push((block_header_hash, BlockHeaderResult::Stored));
+                    stored = true;
+                    if Self::reward_main_chain_header(&relayer, block_header_hash) == Pays::Yes {
+                        pays_fee = Pays::Yes;
+                    }
+                }
+                Err(err) => {
+                    if results.is_empty() {
+                        return Err(err.into());
+                    }
+                    actual_weight = actual_weight
+                        .saturating_add(<T as Config<I>>::WeightInfo::store_block_header());
+                    results.push((block_header_hash, BlockHeaderResult::Rejected(err)));
+                    pays_fee = Pays::Yes;
+                    break;
+                }
+            }
+        }
+
+        Self::deposit_event(<Event<T, I>>::StoreBlockHeaders(results));
+
+        // a batch of duplicates only did the relayer's own work
+        if !stored {
+            pays_fee = Pays::Yes;
+        }
+
+        Ok(PostDispatchInfo {
+            actual_weight: Some(actual_weight),
+            pays_fee,
+        })
+    }
+
+    /// Rewards an authorized relayer for a stored block header that extends the
+    /// main chain. Returns whether the submission still pays transaction fees.
+    /// A failing reward does not fail the submission, it is logged instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `relayer` - account of the relayer
+    /// * `block_header_hash` - hash of the stored block header
+    fn reward_main_chain_header(relayer: &T::AccountId, block_header_hash: H256Le) -> Pays {
+        if Self::get_best_block() != block_header_hash
+            || !<AuthorizedRelayers<T, I>>::contains_key(relayer)
+        {
+            return Pays::Yes;
+        }
+        if let Err(err) = ext::fee::reward_header_submission::<T>(relayer) {
+            debug::warn!("Failed to reward header submission: {:?}", err);
+        }
+        Pays::No
+    }
+
+    /// Weight of storing a block header, including the reorg it triggered
+    fn store_block_header_weight(reorg_depth: u32) -> Weight {
+        let weight = <T as Config<I>>::WeightInfo::store_block_header();
+        if reorg_depth > 0 {
+            weight.saturating_add(<T as Config<I>>::WeightInfo::swap_main_blockchain(
+                reorg_depth,
+            ))
+        } else {
+            weight
+        }
+    }
+
+    /// Upper bound of the number of main chain blocks replaced if the block
+    /// header triggers a reorg, derived from the position of its parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw_block_header` - 80 byte raw Bitcoin block header
+    pub fn get_reorg_depth_bound(raw_block_header: &RawBlockHeader) -> u32 {
+        let prev_header = match parse_block_header(raw_block_header)
+            .map_err(|err| Error::<T, I>::from(err).into())
+            .and_then(|block_header| Self::get_block_header_from_hash(block_header.hash_prev_block))
+        {
+            Ok(prev_header) => prev_header,
+            Err(_) => return 0,
+        };
+        // the first block that is not shared with the main chain
+        let fork_start_height = if prev_header.chain_ref == MAIN_CHAIN_ID {
+            prev_header.block_height.saturating_add(1)
+        } else {
+            match Self::get_block_chain_from_id(prev_header.chain_ref) {
+                Ok(fork) => fork.start_height,
+                Err(_) => return 0,
+            }
+        };
+        Self::get_best_block_height()
+            .saturating_add(1)
+            .saturating_sub(fork_start_height)
+    }
+
+    /// Upper bound of the headers removed if the block is invalidated, i.e. the
+    /// headers of all chains at or above its height, and the number of chains
+    ///
+    /// # Arguments
+    ///
+    /// * `block_hash` - hash of the block header
+    pub fn get_invalidation_bound(block_hash: &H256Le) -> (u32, u32) {
+        let block_height = match Self::get_block_header_from_hash(*block_hash) {
+            Ok(block_header) => block_header.block_height,
+            Err(_) => return (0, 0),
+        };
+        <ChainsIndex<I>>::iter().fold((0u32, 0u32), |(headers, chains), (_, chain)| {
+            let chain_headers = (chain.max_height.saturating_add(1))
+                .saturating_sub(chain.start_height.max(block_height));
+            (
+                headers.saturating_add(chain_headers),
+                chains.saturating_add(1),
+            )
+        })
+    }
+
+    /// Checks if the parent of the block header is the best block
+    fn extends_best_block(raw_block_header: &RawBlockHeader) -> bool {
+        parse_block_header(raw_block_header).map_or(false, |block_header| {
+            block_header.hash_prev_block == Self::get_best_block()
+        })
     }
 
     pub fn _verify_transaction_inclusion(
@@ -548,6 +1197,7 @@ impl<T: Config> Module<T> {
         if Self::disable_inclusion_check() {
             return Ok(());
         }
+        Self::ensure_chain_work_migrated()?;
 
         let best_block_height = Self::get_best_block_height();
         Self::ensure_no_ongoing_fork(best_block_height)?;
@@ -558,12 +1208,19 @@ impl<T: Config> Module<T> {
             merkle_proof
                 .block_header
                 .hash()
-                .map_err(|err| Error::<T>::from(err))?,
-        )?;
+                .map_err(|err| Error::<T, I>::from(err))?,
+        )
+        .map_err(|err| {
+            if Self::is_block_header_pruned(&merkle_proof.block_header) {
+                Error::<T, I>::BlockPruned.into()
+            } else {
+                err
+            }
+        })?;
 
         ensure!(
             rich_header.chain_ref == MAIN_CHAIN_ID,
-            Error::<T>::InvalidChainID
+            Error::<T, I>::InvalidChainID
         );
 
         let block_height = rich_header.block_height;
@@ -581,13 +1238,13 @@ impl<T: Config> Module<T> {
         // fail if the transaction hash is invalid
         ensure!(
             proof_result.transaction_hash == tx_id,
-            Error::<T>::InvalidTxid
+            Error::<T, I>::InvalidTxid
         );
 
         // fail if the merkle root is invalid
         ensure!(
             proof_result.extracted_root == rich_header.block_header.merkle_root,
-            Error::<T>::InvalidMerkleProof
+            Error::<T, I>::InvalidMerkleProof
         );
         Ok(())
     }
@@ -600,10 +1257,10 @@ impl<T: Config> Module<T> {
     /// * `transaction` - Bitcoin transaction
     pub fn extract_outputs(
         transaction: Transaction,
-    ) -> Result<(Vec<(i64, BtcAddress)>, Vec<(i64, Vec<u8>)>), Error<T>> {
+    ) -> Result<(Vec<(i64, BtcAddress)>, Vec<(i64, Vec<u8>)>), Error<T, I>> {
         ensure!(
             transaction.outputs.len() <= ACCEPTED_MAX_TRANSACTION_OUTPUTS as usize,
-            Error::<T>::MalformedTransaction
+            Error::<T, I>::MalformedTransaction
         );
 
         let mut payments = Vec::new();
@@ -619,8 +1276,8 @@ impl<T: Config> Module<T> {
         Ok((payments, op_returns))
     }
 
-    /// Extract the payment value from the first output with an address
-    /// that matches the `recipient_btc_address`.
+    /// Extract the index and payment value of the first output with an
+    /// address that matches the `recipient_btc_address`.
     ///
     /// # Arguments
     ///
@@ -629,12 +1286,12 @@ impl<T: Config> Module<T> {
     fn extract_payment_value(
         transaction: Transaction,
         recipient_btc_address: BtcAddress,
-    ) -> Result<i64, DispatchError> {
+    ) -> Result<(u32, i64), DispatchError> {
         ensure!(
             // We would typically expect two outputs here (payment, refund) but
             // the input amount may be exact so we would only require one
             transaction.outputs.len() >= ACCEPTED_MIN_TRANSACTION_OUTPUTS as usize,
-            Error::<T>::MalformedTransaction
+            Error::<T, I>::MalformedTransaction
         );
 
         // Check if payment is first output
@@ -645,7 +1302,7 @@ impl<T: Config> Module<T> {
         {
             Some(Ok(extr_recipient_btc_address)) => {
                 if recipient_btc_address == extr_recipient_btc_address {
-                    return Ok(transaction.outputs[0].value);
+                    return Ok((0, transaction.outputs[0].value));
                 }
             }
             _ => (),
@@ -659,7 +1316,7 @@ impl<T: Config> Module<T> {
         {
             Some(Ok(extr_recipient_btc_address)) => {
                 if recipient_btc_address == extr_recipient_btc_address {
-                    return Ok(transaction.outputs[1].value);
+                    return Ok((1, transaction.outputs[1].value));
                 }
             }
             _ => (),
@@ -673,17 +1330,17 @@ impl<T: Config> Module<T> {
         {
             Some(Ok(extr_recipient_btc_address)) => {
                 if recipient_btc_address == extr_recipient_btc_address {
-                    return Ok(transaction.outputs[2].value);
+                    return Ok((2, transaction.outputs[2].value));
                 }
             }
             _ => (),
         };
 
         // Payment UTXO sends to incorrect address
-        Err(Error::<T>::WrongRecipient.into())
+        Err(Error::<T, I>::WrongRecipient.into())
     }
 
-    /// Extract the payment value and `OP_RETURN` payload from the first
+    /// Extract the index, payment value and `OP_RETURN` payload of the first
     /// output with an address that matches the `recipient_btc_address`.
     ///
     /// # Arguments
@@ -693,12 +1350,12 @@ impl<T: Config> Module<T> {
     fn extract_payment_value_and_op_return(
         transaction: Transaction,
         recipient_btc_address: BtcAddress,
-    ) -> Result<(i64, Vec<u8>), DispatchError> {
+    ) -> Result<(u32, i64, Vec<u8>), DispatchError> {
         ensure!(
             // We would typically expect three outputs (payment, op_return, refund) but
             // exceptionally the input amount may be exact so we would only require two
             transaction.outputs.len() >= ACCEPTED_MIN_TRANSACTION_OUTPUTS_WITH_OP_RETURN as usize,
-            Error::<T>::MalformedTransaction
+            Error::<T, I>::MalformedTransaction
         );
 
         // Check if payment is first output
@@ -706,6 +1363,7 @@ impl<T: Config> Module<T> {
             Ok(extr_recipient_btc_address) => {
                 if recipient_btc_address == extr_recipient_btc_address {
                     return Ok((
+                        0,
                         transaction.outputs[0].value,
                         extract_op_return!(transaction.outputs.get(1), transaction.outputs.get(2)),
                     ));
@@ -719,6 +1377,7 @@ impl<T: Config> Module<T> {
             Ok(extr_recipient_btc_address) => {
                 if recipient_btc_address == extr_recipient_btc_address {
                     return Ok((
+                        1,
                         transaction.outputs[1].value,
                         extract_op_return!(transaction.outputs.get(0), transaction.outputs.get(2)),
                     ));
@@ -736,6 +1395,7 @@ impl<T: Config> Module<T> {
             Some(Ok(extr_recipient_btc_address)) => {
                 if recipient_btc_address == extr_recipient_btc_address {
                     return Ok((
+                        2,
                         transaction.outputs[2].value,
                         extract_op_return!(transaction.outputs.get(0), transaction.outputs.get(1)),
                     ));
@@ -745,7 +1405,7 @@ impl<T: Config> Module<T> {
         };
 
         // Payment UTXO sends to incorrect address
-        Err(Error::<T>::WrongRecipient.into())
+        Err(Error::<T, I>::WrongRecipient.into())
     }
 
     pub fn is_op_return_disabled() -> bool {
@@ -766,80 +1426,174 @@ impl<T: Config> Module<T> {
             .clone()
             .inputs
             .get(0)
-            .ok_or(Error::<T>::MalformedTransaction)?
+            .ok_or(Error::<T, I>::MalformedTransaction)?
             .extract_address()
-            .map_err(|_| Error::<T>::MalformedTransaction)?;
+            .map_err(|_| Error::<T, I>::MalformedTransaction)?;
+
+        let (_, extr_payment_value) =
+            Self::extract_payment_output(transaction, recipient_btc_address, op_return_id)?;
+
+        // Check if payment UTXO transfers sufficient value
+        ensure!(
+            extr_payment_value >= payment_value,
+            Error::<T, I>::InsufficientValue
+        );
 
-        let extr_payment_value = if Self::is_op_return_disabled() {
-            Self::extract_payment_value(transaction, recipient_btc_address)?
+        Ok((input_address, extr_payment_value))
+    }
+
+    /// Extract the index and value of the payment output, checking the `OP_RETURN`
+    /// payload if required.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - Bitcoin transaction
+    /// * `recipient_btc_address` - expected payment recipient
+    /// * `op_return_id` - expected `OP_RETURN` payload, if any
+    fn extract_payment_output(
+        transaction: Transaction,
+        recipient_btc_address: BtcAddress,
+        op_return_id: Option<Vec<u8>>,
+    ) -> Result<(u32, i64), DispatchError> {
+        if Self::is_op_return_disabled() {
+            Self::extract_payment_value(transaction, recipient_btc_address)
         } else {
             if let Some(op_return_id) = op_return_id {
                 // NOTE: op_return UTXO should not contain any value
-                let (extr_payment_value, extr_op_return) =
+                let (output_index, extr_payment_value, extr_op_return) =
                     Self::extract_payment_value_and_op_return(transaction, recipient_btc_address)?;
 
                 // Check if data UTXO has correct OP_RETURN value
-                ensure!(extr_op_return == op_return_id, Error::<T>::InvalidOpReturn);
+                ensure!(
+                    extr_op_return == op_return_id,
+                    Error::<T, I>::InvalidOpReturn
+                );
 
-                extr_payment_value
+                Ok((output_index, extr_payment_value))
             } else {
                 // using the on-chain key derivation scheme we only expect a simple
                 // payment to the vault's new deposit address
-                Self::extract_payment_value(transaction, recipient_btc_address)?
+                Self::extract_payment_value(transaction, recipient_btc_address)
             }
-        };
-
-        // Check if payment UTXO transfers sufficient value
-        ensure!(
-            extr_payment_value >= payment_value,
-            Error::<T>::InsufficientValue
-        );
-
-        Ok((input_address, extr_payment_value))
+        }
     }
 
-    // ********************************
-    // START: Storage getter functions
-    // ********************************
-
-    /// Get chain id from position (sorted by max block height)
-    fn get_chain_id_from_position(position: u32) -> Result<u32, DispatchError> {
-        <Chains>::get(position).ok_or(Error::<T>::InvalidChainID.into())
-    }
+    /// Registers the payment output of a transaction, as matched by `_validate_transaction`,
+    /// as consumed by the given request. Fails if the output was consumed before.
+    /// The request is notified through `OnReorg` if the block including the
+    /// transaction is reorged out.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx_id` - The hash of the transaction
+    /// * `raw_merkle_proof` - The raw merkle proof of the transaction inclusion
+    /// * `raw_tx` - raw Bitcoin transaction
+    /// * `recipient_btc_address` - Bitcoin address of the consumed output
+    /// * `op_return_id` - expected `OP_RETURN` payload, if any
+    /// * `consumer` - the request consuming the output
+    pub fn _use_transaction(
+        tx_id: H256Le,
+        raw_merkle_proof: Vec<u8>,
+        raw_tx: Vec<u8>,
+        recipient_btc_address: BtcAddress,
+        op_return_id: Option<Vec<u8>>,
+        consumer: TransactionConsumer,
+    ) -> DispatchResult {
+        let transaction = Self::parse_transaction(&raw_tx)?;
+        ensure!(tx_id == transaction.tx_id(), Error::<T, I>::InvalidTxid);
+
+        let (output_index, _) =
+            Self::extract_payment_output(transaction, recipient_btc_address, op_return_id)?;
+
+        ensure!(
+            !<UsedTransactions<I>>::contains_key(tx_id, output_index),
+            Error::<T, I>::TransactionAlreadyUsed
+        );
+        <UsedTransactions<I>>::insert(tx_id, output_index, consumer);
+        <FulfillingTransactions<I>>::insert(consumer, tx_id);
+
+        // the proof is not verified if inclusion checks are disabled
+        if !Self::disable_inclusion_check() {
+            let merkle_proof = Self::parse_merkle_proof(&raw_merkle_proof)?;
+            let block_hash = merkle_proof
+                .block_header
+                .hash()
+                .map_err(|err| Error::<T, I>::from(err))?;
+            <BlockConsumers<I>>::append(block_hash, consumer);
+            // the request is no longer notified once it has stable confirmations
+            let stable_height = Self::get_best_block_height()
+                .saturating_add(Self::get_stable_transaction_confirmations());
+            <StableBlockConsumers<I>>::append(stable_height, (block_hash, consumer));
+        }
+
+        Ok(())
+    }
+
+    /// Get the transaction that fulfilled the request, if it was executed
+    ///
+    /// # Arguments
+    ///
+    /// * `consumer` - The request
+    pub fn get_fulfilling_transaction(consumer: TransactionConsumer) -> Option<H256Le> {
+        <FulfillingTransactions<I>>::get(consumer)
+    }
+
+    /// Get the requests that consumed outputs of a transaction, by output index
+    ///
+    /// # Arguments
+    ///
+    /// * `tx_id` - The hash of the transaction
+    pub fn get_transaction_consumers(tx_id: H256Le) -> Vec<(u32, TransactionConsumer)> {
+        <UsedTransactions<I>>::iter_prefix(tx_id).collect()
+    }
+
+    // ********************************
+    // START: Storage getter functions
+    // ********************************
+
+    /// Get chain id from position (sorted by accumulated chainwork)
+    fn get_chain_id_from_position(position: u32) -> Result<u32, DispatchError> {
+        <Chains<I>>::get(position).ok_or(Error::<T, I>::InvalidChainID.into())
+    }
 
     /// Get the position of the fork in Chains
     fn get_chain_position_from_chain_id(chain_id: u32) -> Result<u32, DispatchError> {
-        for (k, v) in <Chains>::iter() {
+        for (k, v) in <Chains<I>>::iter() {
             if v == chain_id {
                 return Ok(k);
             }
         }
-        Err(Error::<T>::ForkIdNotFound.into())
+        Err(Error::<T, I>::ForkIdNotFound.into())
     }
 
     /// Get a blockchain from the id
     fn get_block_chain_from_id(chain_id: u32) -> Result<BlockChain, DispatchError> {
-        <ChainsIndex>::get(chain_id).ok_or(Error::<T>::InvalidChainID.into())
+        <ChainsIndex<I>>::get(chain_id).ok_or(Error::<T, I>::InvalidChainID.into())
     }
 
     /// Get the current best block hash
     pub fn get_best_block() -> H256Le {
-        <BestBlock>::get()
+        <BestBlock<I>>::get()
     }
 
     /// Check if a best block hash is set
     fn best_block_exists() -> bool {
-        <BestBlock>::exists()
+        <BestBlock<I>>::exists()
     }
 
     /// get the best block height
     pub fn get_best_block_height() -> u32 {
-        <BestBlockHeight>::get()
+        <BestBlockHeight<I>>::get()
+    }
+
+    /// Get the parameters of the relayed network
+    pub fn network_parameters() -> NetworkParameters {
+        T::Network::get()
     }
 
     /// Get the current chain counter
     fn get_chain_counter() -> u32 {
-        <ChainCounter>::get()
+        <ChainCounter<I>>::get()
     }
 
     /// Get a block hash from a blockchain
@@ -850,24 +1604,119 @@ impl<T: Config> Module<T> {
     /// * `block_height`: the height if the block header
     fn get_block_hash(chain_id: u32, block_height: u32) -> Result<H256Le, DispatchError> {
         if !Self::block_exists(chain_id, block_height) {
-            return Err(Error::<T>::MissingBlockHeight.into());
+            if chain_id == MAIN_CHAIN_ID && block_height < Self::pruned_height() {
+                return Err(Error::<T, I>::BlockPruned.into());
+            }
+            return Err(Error::<T, I>::MissingBlockHeight.into());
+        }
+        Ok(<ChainsHashes<I>>::get(chain_id, block_height))
+    }
+
+    /// Get the checkpoint at the given height, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `block_height`: the height of the checkpoint
+    fn get_checkpoint(block_height: u32) -> Option<Checkpoint> {
+        <Checkpoints<I>>::get(block_height)
+    }
+
+    /// Adds or replaces the checkpoint at its height. Fails if the main
+    /// chain already contains a different block at that height.
+    ///
+    /// # Arguments
+    ///
+    /// * `checkpoint`: the new checkpoint
+    fn _set_checkpoint(checkpoint: Checkpoint) -> DispatchResult {
+        if let Ok(block_hash) = Self::get_block_hash(MAIN_CHAIN_ID, checkpoint.block_height) {
+            ensure!(
+                block_hash == checkpoint.block_hash,
+                Error::<T, I>::CheckpointConflict
+            );
+        }
+        <Checkpoints<I>>::insert(checkpoint.block_height, checkpoint);
+        Ok(())
+    }
+
+    /// Ensures a new block header does not conflict with any checkpoint. A header
+    /// conflicts if a checkpoint at its height has a different hash, or if it is
+    /// part of a fork starting at or below a checkpoint in the main chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_hash`: the hash of the new block header
+    /// * `block_height`: the height of the new block header
+    /// * `fork_start_height`: first height not shared with the main chain, if on a fork
+    fn ensure_no_checkpoint_conflict(
+        block_hash: &H256Le,
+        block_height: u32,
+        fork_start_height: Option<u32>,
+    ) -> DispatchResult {
+        if let Some(checkpoint) = Self::get_checkpoint(block_height) {
+            ensure!(
+                checkpoint.block_hash == *block_hash,
+                Error::<T, I>::CheckpointConflict
+            );
+        }
+        if let Some(fork_start_height) = fork_start_height {
+            Self::ensure_no_checkpoint_in_main_chain_from(fork_start_height)?;
+        }
+        Ok(())
+    }
+
+    /// Ensures the main chain contains no checkpoint at or above the given height
+    ///
+    /// # Arguments
+    ///
+    /// * `block_height`: the lowest height that may be replaced in the main chain
+    fn ensure_no_checkpoint_in_main_chain_from(block_height: u32) -> DispatchResult {
+        for checkpoint in <Checkpoints<I>>::iter_values() {
+            ensure!(
+                checkpoint.block_height < block_height
+                    || Self::get_block_hash(MAIN_CHAIN_ID, checkpoint.block_height)
+                        != Ok(checkpoint.block_hash),
+                Error::<T, I>::CheckpointConflict
+            );
         }
-        Ok(<ChainsHashes>::get(chain_id, block_height))
+        Ok(())
     }
 
     /// Get a block header from its hash
-    fn get_block_header_from_hash(
+    pub fn get_block_header_from_hash(
         block_hash: H256Le,
     ) -> Result<RichBlockHeader<T::AccountId>, DispatchError> {
-        if <BlockHeaders<T>>::contains_key(block_hash) {
-            return Ok(<BlockHeaders<T>>::get(block_hash));
+        if <BlockHeaders<T, I>>::contains_key(block_hash) {
+            return Ok(<BlockHeaders<T, I>>::get(block_hash));
         }
-        Err(Error::<T>::BlockNotFound.into())
+        Err(Error::<T, I>::BlockNotFound.into())
+    }
+
+    /// Get a main chain block header from its height
+    ///
+    /// # Arguments
+    ///
+    /// * `block_height`: the height of the block header
+    pub fn get_block_header_at_height(
+        block_height: u32,
+    ) -> Result<RichBlockHeader<T::AccountId>, DispatchError> {
+        let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+        Self::get_block_header_from_height(&main_chain, block_height)
+    }
+
+    /// Get all blockchains ordered by their position in `Chains`,
+    /// i.e. the main chain followed by the forks
+    pub fn get_chains() -> Vec<BlockChain> {
+        let mut chains = <Chains<I>>::iter().collect::<Vec<(u32, u32)>>();
+        chains.sort_by_key(|(position, _)| *position);
+        chains
+            .into_iter()
+            .filter_map(|(_, chain_id)| <ChainsIndex<I>>::get(chain_id))
+            .collect()
     }
 
     /// Check if a block header exists
     pub fn block_header_exists(block_hash: H256Le) -> bool {
-        <BlockHeaders<T>>::contains_key(block_hash)
+        <BlockHeaders<T, I>>::contains_key(block_hash)
     }
 
     /// Get a block header from
@@ -882,25 +1731,25 @@ impl<T: Config> Module<T> {
     /// Storage setter functions
     /// Set a new chain with position and id
     fn set_chain_from_position_and_id(position: u32, id: u32) {
-        <Chains>::insert(position, id);
+        <Chains<I>>::insert(position, id);
     }
 
     /// Swap chain elements
     fn swap_chain(pos_1: u32, pos_2: u32) {
         // swaps the values of two keys
-        <Chains>::swap(pos_1, pos_2)
+        <Chains<I>>::swap(pos_1, pos_2)
     }
 
     /// Remove a chain id from chains
     fn remove_blockchain_from_chain(position: u32) -> Result<(), DispatchError> {
         // swap the element with the last element in the mapping
         // collect the unsorted chains iterable as a vector and sort it by index
-        let mut chains = <Chains>::iter().collect::<Vec<(u32, u32)>>();
+        let mut chains = <Chains<I>>::iter().collect::<Vec<(u32, u32)>>();
         chains.sort_by_key(|k| k.0);
 
         // get the last position as stored in the list
         let last_pos = match chains.len() {
-            0 => return Err(Error::<T>::ForkIdNotFound.into()),
+            0 => return Err(Error::<T, I>::ForkIdNotFound.into()),
             // chains stores (position, index)
             n => chains[n - 1].0,
         };
@@ -908,81 +1757,114 @@ impl<T: Config> Module<T> {
         // don't remove main chain id
         if last_pos > 0 {
             // remove the old head (now the value at the initial position)
-            <Chains>::remove(last_pos);
+            <Chains<I>>::remove(last_pos);
         }
         Ok(())
     }
 
     /// Set a new blockchain in ChainsIndex
     fn set_block_chain_from_id(id: u32, chain: &BlockChain) {
-        <ChainsIndex>::insert(id, &chain);
+        <ChainsIndex<I>>::insert(id, &chain);
     }
 
     /// Update a blockchain in ChainsIndex
     fn mutate_block_chain_from_id(id: u32, chain: BlockChain) {
-        <ChainsIndex>::mutate(id, |b| *b = Some(chain));
+        <ChainsIndex<I>>::mutate(id, |b| *b = Some(chain));
     }
 
     /// Remove a blockchain element from ChainsIndex
     fn remove_blockchain_from_chain_index(id: u32) {
-        <ChainsIndex>::remove(id);
+        <ChainsIndex<I>>::remove(id);
     }
 
     /// Set a new block header
     fn set_block_header_from_hash(hash: H256Le, header: &RichBlockHeader<T::AccountId>) {
-        <BlockHeaders<T>>::insert(hash, header);
+        <BlockHeaders<T, I>>::insert(hash, header);
         // register the current height to track stable parachain confirmations
         Self::set_parachain_height_from_hash(hash);
+        Self::set_last_regular_target(hash, header);
+    }
+
+    /// Tracks the target of the last block not mined under the minimum difficulty
+    /// rule, such that it can be looked up without walking back the chain
+    ///
+    /// # Arguments
+    ///
+    /// * `hash`: the hash of the block header
+    /// * `header`: the block header
+    fn set_last_regular_target(hash: H256Le, header: &RichBlockHeader<T::AccountId>) {
+        let network = Self::network_parameters();
+        if !network.allows_min_difficulty_blocks {
+            return;
+        }
+        let target = if header.block_height % network.difficulty_adjustment_interval != 0
+            && header.block_header.target == Self::round_target(network.max_target)
+        {
+            <LastRegularTargets<I>>::get(header.block_header.hash_prev_block)
+                .unwrap_or(header.block_header.target)
+        } else {
+            header.block_header.target
+        };
+        <LastRegularTargets<I>>::insert(hash, target);
     }
 
     /// Store the height of the parachain when storing a Bitcoin header
     fn set_parachain_height_from_hash(hash: H256Le) {
         let height = <frame_system::Module<T>>::block_number();
-        <ParachainHeight<T>>::insert(hash, height);
+        <ParachainHeight<T, I>>::insert(hash, height);
     }
 
     /// update the chain_ref of a block header
     fn mutate_block_header_from_chain_id(hash: &H256Le, chain_ref: u32) {
-        <BlockHeaders<T>>::mutate(&hash, |header| header.chain_ref = chain_ref);
+        <BlockHeaders<T, I>>::mutate(&hash, |header| header.chain_ref = chain_ref);
     }
 
     /// Set a new best block
     fn set_best_block(hash: H256Le) {
-        <BestBlock>::put(hash);
+        <BestBlock<I>>::put(hash);
     }
 
     /// Set a new best block height
     fn set_best_block_height(height: u32) {
-        <BestBlockHeight>::put(height);
+        <BestBlockHeight<I>>::put(height);
     }
 
     /// Set a new chain counter
     fn increment_chain_counter() -> u32 {
         let new_counter = Self::get_chain_counter() + 1;
-        <ChainCounter>::put(new_counter);
+        <ChainCounter<I>>::put(new_counter);
 
         new_counter
     }
 
     /// Initialize the new main blockchain with a single block
-    fn initialize_blockchain(block_height: u32, block_hash: H256Le) -> BlockChain {
+    fn initialize_blockchain(
+        block_height: u32,
+        block_hash: H256Le,
+        chain_work: U256,
+    ) -> BlockChain {
         let chain_id = MAIN_CHAIN_ID;
 
         // generate an empty blockchain
-        Self::generate_blockchain(chain_id, block_height, block_hash)
+        Self::generate_blockchain(chain_id, block_height, block_hash, chain_work)
     }
 
     /// Create a new blockchain element with a new chain id
-    fn create_blockchain(block_height: u32, block_hash: H256Le) -> BlockChain {
+    fn create_blockchain(block_height: u32, block_hash: H256Le, chain_work: U256) -> BlockChain {
         // get a new chain id
         let chain_id: u32 = Self::increment_chain_counter();
 
         // generate an empty blockchain
-        Self::generate_blockchain(chain_id, block_height, block_hash)
+        Self::generate_blockchain(chain_id, block_height, block_hash, chain_work)
     }
 
     /// Generate the raw blockchain from a chain Id and with a single block
-    fn generate_blockchain(chain_id: u32, block_height: u32, block_hash: H256Le) -> BlockChain {
+    fn generate_blockchain(
+        chain_id: u32,
+        block_height: u32,
+        block_hash: H256Le,
+        chain_work: U256,
+    ) -> BlockChain {
         // initialize an empty chain
 
         Self::insert_block_hash(chain_id, block_height, block_hash);
@@ -993,39 +1875,57 @@ impl<T: Config> Module<T> {
             max_height: block_height,
             no_data: BTreeSet::new(),
             invalid: BTreeSet::new(),
+            chain_work,
         }
     }
 
     fn insert_block_hash(chain_id: u32, block_height: u32, block_hash: H256Le) {
-        <ChainsHashes>::insert(chain_id, block_height, block_hash);
+        <ChainsHashes<I>>::insert(chain_id, block_height, block_hash);
     }
 
     fn remove_block_hash(chain_id: u32, block_height: u32) {
-        <ChainsHashes>::remove(chain_id, block_height);
+        <ChainsHashes<I>>::remove(chain_id, block_height);
+    }
+
+    /// Remove a block header and its references from storage
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_id`: the id of the blockchain containing the block
+    /// * `block_height`: the height of the block
+    /// * `block_hash`: the hash of the block
+    fn remove_block_header(chain_id: u32, block_height: u32, block_hash: H256Le) {
+        Self::remove_block_hash(chain_id, block_height);
+        <BlockHeaders<T, I>>::remove(block_hash);
+        <ParachainHeight<T, I>>::remove(block_hash);
+        <LastRegularTargets<I>>::remove(block_hash);
+        <BlockConsumers<I>>::remove(block_hash);
     }
 
     fn block_exists(chain_id: u32, block_height: u32) -> bool {
-        <ChainsHashes>::contains_key(chain_id, block_height)
+        <ChainsHashes<I>>::contains_key(chain_id, block_height)
     }
 
     fn _blocks_count(chain_id: u32) -> usize {
-        <ChainsHashes>::iter_prefix_values(chain_id).count()
+        <ChainsHashes<I>>::iter_prefix_values(chain_id).count()
     }
 
     /// Add a new block header to an existing blockchain
     fn extend_blockchain(
         block_height: u32,
         block_hash: &H256Le,
+        chain_work: U256,
         prev_blockchain: BlockChain,
     ) -> Result<BlockChain, DispatchError> {
         let mut blockchain = prev_blockchain;
 
         if Self::block_exists(blockchain.chain_id, block_height) {
-            return Err(Error::<T>::DuplicateBlock.into());
+            return Err(Error::<T, I>::DuplicateBlock.into());
         }
         Self::insert_block_hash(blockchain.chain_id, block_height, *block_hash);
 
         blockchain.max_height = block_height;
+        blockchain.chain_work = chain_work;
         Self::set_block_chain_from_id(blockchain.chain_id, &blockchain);
 
         Ok(blockchain)
@@ -1042,17 +1942,17 @@ impl<T: Config> Module<T> {
 
     // Wrapper functions around bitcoin lib for testing purposes
     fn parse_transaction(raw_tx: &[u8]) -> Result<Transaction, DispatchError> {
-        Ok(parse_transaction(&raw_tx).map_err(|err| Error::<T>::from(err))?)
+        Ok(parse_transaction(&raw_tx).map_err(|err| Error::<T, I>::from(err))?)
     }
 
     fn parse_merkle_proof(raw_merkle_proof: &[u8]) -> Result<MerkleProof, DispatchError> {
-        MerkleProof::parse(&raw_merkle_proof).map_err(|err| Error::<T>::from(err).into())
+        MerkleProof::parse(&raw_merkle_proof).map_err(|err| Error::<T, I>::from(err).into())
     }
 
     fn verify_merkle_proof(merkle_proof: &MerkleProof) -> Result<ProofResult, DispatchError> {
         merkle_proof
             .verify_proof()
-            .map_err(|err| Error::<T>::from(err).into())
+            .map_err(|err| Error::<T, I>::from(err).into())
     }
 
     /// Parses and verifies a raw Bitcoin block header.
@@ -1068,14 +1968,21 @@ impl<T: Config> Module<T> {
         raw_block_header: &RawBlockHeader,
     ) -> Result<BlockHeader, DispatchError> {
         let basic_block_header =
-            parse_block_header(&raw_block_header).map_err(|err| Error::<T>::from(err))?;
+            parse_block_header(&raw_block_header).map_err(|err| Error::<T, I>::from(err))?;
 
         let block_header_hash = raw_block_header.hash();
 
         // Check that the block header is not yet stored in BTC-Relay
         ensure!(
             !Self::block_header_exists(block_header_hash),
-            Error::<T>::DuplicateBlock
+            Error::<T, I>::DuplicateBlock
+        );
+
+        // Check that neither the block header nor its parent was invalidated
+        ensure!(
+            !<InvalidatedBlocks<I>>::contains_key(block_header_hash)
+                && !<InvalidatedBlocks<I>>::contains_key(basic_block_header.hash_prev_block),
+            Error::<T, I>::InvalidatedBlock
         );
 
         // Check that the referenced previous block header exists in BTC-Relay
@@ -1084,7 +1991,7 @@ impl<T: Config> Module<T> {
         // Check that the PoW hash satisfies the target set in the block header
         ensure!(
             block_header_hash.as_u256() < basic_block_header.target,
-            Error::<T>::LowDiff
+            Error::<T, I>::LowDiff
         );
 
         // Check that the diff. target is indeed correctly set in the block header, i.e., check for re-target.
@@ -1095,20 +2002,68 @@ impl<T: Config> Module<T> {
         }
 
         let expected_target =
-            if block_height >= 2016 && block_height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0 {
-                Self::compute_new_target(&prev_block_header, block_height)?
-            } else {
-                prev_block_header.block_header.target
-            };
+            Self::get_expected_target(&prev_block_header, &basic_block_header, block_height)?;
 
         ensure!(
             basic_block_header.target == expected_target,
-            Error::<T>::DiffTargetHeader
+            Error::<T, I>::DiffTargetHeader
         );
 
         Ok(basic_block_header)
     }
 
+    /// Returns the target a block at the given height must have, according to
+    /// the configured `NetworkParameters`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prev_block_header`: previous block header
+    /// * `block_header`: block header to check
+    /// * `block_height` : block height of `block_header`
+    fn get_expected_target(
+        prev_block_header: &RichBlockHeader<T::AccountId>,
+        block_header: &BlockHeader,
+        block_height: u32,
+    ) -> Result<U256, DispatchError> {
+        let network = Self::network_parameters();
+
+        if block_height >= network.difficulty_adjustment_interval
+            && block_height % network.difficulty_adjustment_interval == 0
+        {
+            return if network.no_retargeting {
+                Ok(prev_block_header.block_header.target)
+            } else {
+                Self::compute_new_target(prev_block_header, block_height)
+            };
+        }
+
+        if network.allows_min_difficulty_blocks {
+            // a block may be mined at the minimum difficulty if no block
+            // was found for more than twice the target spacing
+            if block_header.timestamp as u64
+                > prev_block_header.block_header.timestamp as u64
+                    + 2 * network.target_spacing as u64
+            {
+                return Ok(Self::round_target(network.max_target));
+            }
+            return Ok(Self::get_last_regular_target(prev_block_header));
+        }
+
+        Ok(prev_block_header.block_header.target)
+    }
+
+    /// Returns the target of the last block, up to and including the given
+    /// block, that was not mined under the minimum difficulty rule. Falls
+    /// back to the target of the block itself if it is not tracked.
+    ///
+    /// # Arguments
+    ///
+    /// * `prev_block_header`: block header to look up the last regular target for
+    fn get_last_regular_target(prev_block_header: &RichBlockHeader<T::AccountId>) -> U256 {
+        <LastRegularTargets<I>>::get(prev_block_header.block_hash)
+            .unwrap_or(prev_block_header.block_header.target)
+    }
+
     /// Computes Bitcoin's PoW retarget algorithm for a given block height
     ///
     /// # Arguments
@@ -1119,31 +2074,48 @@ impl<T: Config> Module<T> {
         prev_block_header: &RichBlockHeader<T::AccountId>,
         block_height: u32,
     ) -> Result<U256, DispatchError> {
+        let network = Self::network_parameters();
         // get time of last retarget
         let last_retarget_time =
             Self::get_last_retarget_time(prev_block_header.chain_ref, block_height)?;
-        // Compute new target
-        let actual_timespan = if ((prev_block_header.block_header.timestamp as u64
-            - last_retarget_time) as u32)
-            < (TARGET_TIMESPAN / TARGET_TIMESPAN_DIVISOR)
-        {
-            TARGET_TIMESPAN / TARGET_TIMESPAN_DIVISOR
-        } else {
-            TARGET_TIMESPAN * TARGET_TIMESPAN_DIVISOR
-        };
+        // Compute new target, limiting the adjustment to a factor of TARGET_TIMESPAN_DIVISOR
+        let actual_timespan = (prev_block_header.block_header.timestamp as u64)
+            .saturating_sub(last_retarget_time)
+            .max((network.target_timespan / TARGET_TIMESPAN_DIVISOR) as u64)
+            .min((network.target_timespan * TARGET_TIMESPAN_DIVISOR) as u64);
 
         let new_target = U256::from(actual_timespan)
             .checked_mul(prev_block_header.block_header.target)
-            .ok_or(Error::<T>::ArithmeticOverflow)?
-            .checked_div(U256::from(TARGET_TIMESPAN))
-            .ok_or(Error::<T>::ArithmeticUnderflow)?;
+            .ok_or(Error::<T, I>::ArithmeticOverflow)?
+            .checked_div(U256::from(network.target_timespan))
+            .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
 
         // ensure target does not exceed max. target
-        Ok(if new_target > UNROUNDED_MAX_TARGET {
-            UNROUNDED_MAX_TARGET
+        let max_target = network.max_target;
+        Ok(Self::round_target(if new_target > max_target {
+            max_target
         } else {
             new_target
-        })
+        }))
+    }
+
+    /// Rounds a target to the precision of its compact representation
+    /// (`nBits`) as stored in the block header.
+    ///
+    /// # Arguments
+    ///
+    /// * `target`: the unrounded target
+    fn round_target(target: U256) -> U256 {
+        let size = (target.bits() + 7) / 8;
+        if size <= 3 {
+            return target;
+        }
+        let mut shift = 8 * (size - 3);
+        // the mantissa is signed, so it may only use 23 bits
+        if (target >> shift).low_u32() & 0x0080_0000 != 0 {
+            shift += 8;
+        }
+        (target >> shift) << shift
     }
 
     /// Returns the timestamp of the last difficulty retarget on the specified BlockChain, given the current block height
@@ -1156,11 +2128,220 @@ impl<T: Config> Module<T> {
         let block_chain = Self::get_block_chain_from_id(chain_ref)?;
         let last_retarget_header = Self::get_block_header_from_height(
             &block_chain,
-            block_height - DIFFICULTY_ADJUSTMENT_INTERVAL,
+            block_height - Self::network_parameters().difficulty_adjustment_interval,
         )?;
         Ok(last_retarget_header.block_header.timestamp as u64)
     }
 
+    /// Removes stale forks and main chain block headers below the pruning window,
+    /// deleting at most `MaxPrunedHeadersPerBlock` headers.
+    ///
+    /// Returns the weight consumed.
+    fn prune_block_headers() -> Weight {
+        let max_headers = Self::max_pruned_headers_per_block();
+        if max_headers == 0 || !Self::best_block_exists() {
+            return T::DbWeight::get().reads(2);
+        }
+        let best_block_height = Self::get_best_block_height();
+        let mut weight = T::DbWeight::get().reads(3);
+
+        let (fork_weight, pruned_fork_headers) =
+            Self::prune_stale_forks(best_block_height, max_headers);
+        weight = weight.saturating_add(fork_weight);
+        let pruned_main_headers = match Self::prune_main_chain(
+            best_block_height,
+            max_headers.saturating_sub(pruned_fork_headers),
+        ) {
+            Ok(pruned) => pruned,
+            Err(err) => {
+                debug::print!("Failed to prune main chain: {:?}\n", err);
+                0
+            }
+        };
+
+        let pruned = pruned_main_headers as Weight;
+        weight.saturating_add(T::DbWeight::get().reads_writes(4 + pruned, 1 + 5 * pruned))
+    }
+
+    /// Removes the headers of forks that fell more than `MaxForkDepth` blocks
+    /// behind the best block, that branch off below the pruned main chain, or
+    /// whose parent was removed. At most `PRUNING_MAX_SCANNED_FORKS` forks are
+    /// checked per call, continuing from the `ForkPruningCursor`.
+    ///
+    /// Returns the weight consumed and the number of removed headers. Forks
+    /// that can not be pruned are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `best_block_height`: height of the best block
+    /// * `max_headers`: maximum number of headers to remove
+    fn prune_stale_forks(best_block_height: u32, max_headers: u32) -> (Weight, u32) {
+        let max_fork_depth = Self::max_fork_depth();
+        let pruned_height = Self::pruned_height();
+        let chain_counter = Self::get_chain_counter();
+        let mut chain_id = Self::fork_pruning_cursor();
+        let mut weight = T::DbWeight::get().reads_writes(4, 1);
+
+        let mut pruned = 0;
+        for _ in 0..PRUNING_MAX_SCANNED_FORKS.min(chain_counter) {
+            if pruned >= max_headers {
+                break;
+            }
+            if chain_id == MAIN_CHAIN_ID || chain_id > chain_counter {
+                chain_id = MAIN_CHAIN_ID + 1;
+            }
+
+            weight = weight.saturating_add(T::DbWeight::get().reads(1));
+            if let Some(fork) = <ChainsIndex<I>>::get(chain_id) {
+                let is_stale = max_fork_depth > 0
+                    && fork.max_height.saturating_add(max_fork_depth) < best_block_height;
+                weight = weight.saturating_add(T::DbWeight::get().reads(3));
+                if is_stale || fork.start_height < pruned_height || Self::is_fork_detached(&fork) {
+                    match Self::prune_fork(fork, max_headers - pruned) {
+                        Ok(removed) => {
+                            weight = weight.saturating_add(
+                                T::DbWeight::get()
+                                    .reads_writes(1 + removed as Weight, 1 + 5 * removed as Weight),
+                            );
+                            pruned += removed;
+                            // continue with the same fork if it was only partially removed
+                            if <ChainsIndex<I>>::contains_key(chain_id) {
+                                break;
+                            }
+                        }
+                        Err(err) => {
+                            debug::warn!("Failed to prune fork {}: {:?}", chain_id, err);
+                        }
+                    }
+                }
+            }
+            chain_id += 1;
+        }
+        <ForkPruningCursor<I>>::put(chain_id);
+
+        if pruned > 0 {
+            // removing headers reduces the chainwork of the forks
+            let chains = Self::sort_chains() as Weight;
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(2 * chains, 2 * chains));
+        }
+        (weight, pruned)
+    }
+
+    /// Whether the parent of the first block of a fork was removed
+    ///
+    /// # Arguments
+    ///
+    /// * `fork`: the fork to check
+    fn is_fork_detached(fork: &BlockChain) -> bool {
+        match Self::get_block_hash(fork.chain_id, fork.start_height)
+            .and_then(Self::get_block_header_from_hash)
+        {
+            Ok(first_header) => {
+                !Self::block_header_exists(first_header.block_header.hash_prev_block)
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// Removes the headers of a chain starting at its tip. A fork is removed
+    /// from `ChainsIndex` once all of its headers are deleted, the caller must
+    /// re-sort `Chains` afterwards. All reads happen before the first write,
+    /// so the fork is left untouched on failure.
+    ///
+    /// Returns the number of removed headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `fork`: the chain to remove headers from
+    /// * `max_headers`: maximum number of headers to remove
+    fn prune_fork(mut fork: BlockChain, max_headers: u32) -> Result<u32, DispatchError> {
+        let fork_length = (fork.max_height + 1).saturating_sub(fork.start_height);
+        let pruned = fork_length.min(max_headers);
+        let lowest_pruned_height = fork.max_height + 1 - pruned;
+        let block_hashes = (lowest_pruned_height..=fork.max_height)
+            .map(|block_height| Self::get_block_hash(fork.chain_id, block_height))
+            .collect::<Result<Vec<_>, _>>()?;
+        // the remaining fork only accounts for the work up to its new tip
+        let remaining_chain_work = if pruned < fork_length {
+            Some(Self::get_block_header_from_height(&fork, lowest_pruned_height - 1)?.chain_work)
+        } else {
+            None
+        };
+
+        for (block_height, block_hash) in (lowest_pruned_height..).zip(block_hashes) {
+            Self::remove_block_header(fork.chain_id, block_height, block_hash);
+            fork.no_data.remove(&block_height);
+            fork.invalid.remove(&block_height);
+        }
+        match remaining_chain_work {
+            Some(chain_work) => {
+                fork.max_height = lowest_pruned_height - 1;
+                fork.chain_work = chain_work;
+                Self::set_block_chain_from_id(fork.chain_id, &fork);
+            }
+            None => Self::remove_blockchain_from_chain_index(fork.chain_id),
+        }
+        Ok(pruned)
+    }
+
+    /// Removes main chain headers deeper than `PruningDepth`, keeping the headers
+    /// required for the next retarget and the median time past.
+    ///
+    /// Returns the number of removed headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `best_block_height`: height of the best block
+    /// * `max_headers`: maximum number of headers to remove
+    fn prune_main_chain(best_block_height: u32, max_headers: u32) -> Result<u32, DispatchError> {
+        let pruning_depth = Self::pruning_depth();
+        if pruning_depth == 0 {
+            return Ok(0);
+        }
+        let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+
+        let difficulty_adjustment_interval =
+            Self::network_parameters().difficulty_adjustment_interval;
+        let last_retarget_height =
+            best_block_height - best_block_height % difficulty_adjustment_interval;
+        let prune_below = best_block_height
+            .saturating_sub(pruning_depth.max(MEDIAN_TIME_SPAN))
+            .min(last_retarget_height);
+
+        let mut block_height = Self::pruned_height().max(main_chain.start_height);
+        let mut pruned = 0;
+        while block_height < prune_below && pruned < max_headers {
+            if let Ok(block_hash) = Self::get_block_hash(MAIN_CHAIN_ID, block_height) {
+                Self::remove_block_header(MAIN_CHAIN_ID, block_height, block_hash);
+                pruned += 1;
+            }
+            block_height += 1;
+        }
+        <PrunedHeight<I>>::put(block_height);
+
+        Ok(pruned)
+    }
+
+    /// Whether an unknown block header lies below `PrunedHeight`. As the height
+    /// of an unknown header is not known, it is considered pruned if it is older
+    /// than the lowest remaining main chain header.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_header`: the unknown block header
+    fn is_block_header_pruned(block_header: &BlockHeader) -> bool {
+        let pruned_height = Self::pruned_height();
+        if pruned_height == 0 {
+            return false;
+        }
+        match Self::get_block_hash(MAIN_CHAIN_ID, pruned_height)
+            .and_then(Self::get_block_header_from_hash)
+        {
+            Ok(lowest_header) => block_header.timestamp < lowest_header.block_header.timestamp,
+            Err(_) => false,
+        }
+    }
+
     /// Swap the main chain with a fork. This method takes the starting height
     /// of the fork and replaces each block in the main chain with the blocks
     /// in the fork. It moves the replaced blocks in the main chain to a new
@@ -1168,6 +2349,8 @@ impl<T: Config> Module<T> {
     /// Last, it replaces the chain_ref of each block header in the new main
     /// chain to the MAIN_CHAIN_ID and each block header in the new fork to the
     /// new chain id.
+    /// Finally, the requests relying on transactions of the replaced blocks
+    /// are notified through `OnReorg`.
     ///
     /// # Arguments
     ///
@@ -1216,9 +2399,11 @@ impl<T: Config> Module<T> {
             max_height: main_chain.max_height,
             no_data,
             invalid,
+            chain_work: main_chain.chain_work,
         };
 
         main_chain.max_height = fork.max_height;
+        main_chain.chain_work = fork.chain_work;
         main_chain.no_data.append(&mut fork.no_data.clone());
         main_chain.invalid.append(&mut fork.invalid.clone());
 
@@ -1246,11 +2431,13 @@ impl<T: Config> Module<T> {
         Self::insert_sorted(&forked_main_chain)?;
 
         // update all the forked block headers
+        let mut reorged_blocks = Vec::new();
         for height in fork.start_height..=forked_main_chain.max_height {
             let block_hash = Self::get_block_hash(main_chain.chain_id, height)?;
             Self::insert_block_hash(forked_main_chain.chain_id, height, block_hash);
             Self::mutate_block_header_from_chain_id(&block_hash, forked_main_chain.chain_id);
             Self::remove_block_hash(MAIN_CHAIN_ID, height);
+            reorged_blocks.push(block_hash);
         }
 
         // update all new main chain block headers
@@ -1259,40 +2446,54 @@ impl<T: Config> Module<T> {
             Self::mutate_block_header_from_chain_id(&block, MAIN_CHAIN_ID);
             Self::insert_block_hash(MAIN_CHAIN_ID, height, block);
         }
-        <ChainsHashes>::remove_prefix(fork.chain_id);
+        <ChainsHashes<I>>::remove_prefix(fork.chain_id);
         if !fork.is_invalid() && !fork.is_no_data() {
             Self::recover_if_needed()?
         }
 
+        // notify the requests relying on transactions of the reorged out blocks
+        for block_hash in reorged_blocks {
+            Self::notify_reorged_block(block_hash);
+        }
+        Self::clear_stable_block_consumers(forked_main_chain.max_height, main_chain.max_height);
+
         Ok(())
     }
 
     /// Checks if a newly inserted fork results in an update to the sorted
-    /// Chains mapping. This happens when the max height of the fork is greater
-    /// than the max height of the previous element in the Chains mapping.
+    /// Chains mapping. This happens when the accumulated chainwork of the fork
+    /// is greater than the chainwork of the previous element in the Chains mapping.
+    /// The main chain is only replaced if the fork has strictly more work and is
+    /// at least `StableBitcoinConfirmations` blocks ahead.
     ///
     /// # Arguments
     ///
     /// * `fork` - the blockchain element that may cause a reorg
-    fn check_and_do_reorg(fork: &BlockChain) -> Result<(), DispatchError> {
+    /// * `max_reorg_depth` - maximum number of main chain blocks the reorg may replace
+    ///
+    /// # Returns
+    ///
+    /// * the number of replaced main chain blocks, or zero if there was no reorg
+    fn check_and_do_reorg(fork: &BlockChain, max_reorg_depth: u32) -> Result<u32, DispatchError> {
         // Check if the ordering needs updating
         // if the fork is the main chain, we don't need to update the ordering
         if fork.chain_id == MAIN_CHAIN_ID {
-            return Ok(());
+            return Ok(0);
         }
 
         // TODO: remove, fix for rm head_index
-        if let None = <Chains>::get(0) {
-            <Chains>::insert(0, 0);
+        if let None = <Chains<I>>::get(0) {
+            <Chains<I>>::insert(0, 0);
         }
 
         // get the position of the fork in Chains
         let fork_position: u32 = Self::get_chain_position_from_chain_id(fork.chain_id)?;
-        // check if the previous element in Chains has a lower block_height
+        // check if the previous element in Chains has less chainwork
         let mut current_position = fork_position;
-        let mut current_height = fork.max_height;
+        let current_work = fork.chain_work;
+        let mut reorg_depth = 0;
 
-        // swap elements as long as previous block height is smaller
+        // swap elements as long as previous chainwork is smaller
         while current_position > 0 {
             // get the previous position
             let prev_position = current_position - 1;
@@ -1306,30 +2507,36 @@ impl<T: Config> Module<T> {
                     continue;
                 };
 
-            // get the previous blockchain height
-            let prev_height = Self::get_block_chain_from_id(prev_blockchain_id)?.max_height;
-            // swap elements if block height is greater
-            if prev_height < current_height {
+            // get the previous blockchain
+            let prev_blockchain = Self::get_block_chain_from_id(prev_blockchain_id)?;
+            let prev_height = prev_blockchain.max_height;
+            // swap elements if chainwork is greater
+            if prev_blockchain.chain_work < current_work {
                 // Check if swap occurs on the main chain element
                 if prev_blockchain_id == MAIN_CHAIN_ID {
-                    // if the previous position is the top element
-                    // and the current height is more than the
+                    // if the previous position is the top element, the fork
+                    // has more work and its height is more than the
                     // STABLE_TRANSACTION_CONFIRMATIONS ahead
                     // we are swapping the main chain
-                    if prev_height + Self::get_stable_transaction_confirmations() < current_height {
+                    if prev_height + Self::get_stable_transaction_confirmations() < fork.max_height
+                    {
+                        reorg_depth = (prev_height + 1).saturating_sub(fork.start_height);
+                        ensure!(
+                            reorg_depth <= max_reorg_depth,
+                            Error::<T, I>::ReorgDepthExceeded
+                        );
+                        let old_chain_tip = <BestBlock<I>>::get();
                         Self::swap_main_blockchain(&fork)?;
 
                         // announce the new main chain
-                        let new_chain_tip = <BestBlock>::get();
-                        let block_height = <BestBlockHeight>::get();
-                        let fork_depth = fork.max_height - fork.start_height;
-                        Self::deposit_event(Event::ChainReorg(
+                        let new_chain_tip = <BestBlock<I>>::get();
+                        Self::deposit_event(<Event<T, I>>::ChainReorg(
+                            old_chain_tip,
                             new_chain_tip,
-                            block_height,
-                            fork_depth,
+                            reorg_depth,
                         ));
                     } else {
-                        Self::deposit_event(Event::ForkAheadOfMainChain(
+                        Self::deposit_event(<Event<T, I>>::ForkAheadOfMainChain(
                             prev_height,     // main chain height
                             fork.max_height, // fork height
                             fork.chain_id,   // fork id
@@ -1342,18 +2549,17 @@ impl<T: Config> Module<T> {
                     Self::swap_chain(prev_position, current_position);
                 }
 
-                // update the current chain to the previous one
+                // the fork moved up by one position, keep comparing its work
                 current_position = prev_position;
-                current_height = prev_height;
             } else {
                 break;
             }
         }
 
-        Ok(())
+        Ok(reorg_depth)
     }
 
-    /// Insert a new fork into the Chains mapping sorted by its max height
+    /// Insert a new fork into the Chains mapping sorted by its accumulated chainwork
     ///
     /// # Arguments
     ///
@@ -1361,7 +2567,7 @@ impl<T: Config> Module<T> {
     fn insert_sorted(blockchain: &BlockChain) -> Result<(), DispatchError> {
         // get a sorted vector over the Chains elements
         // NOTE: LinkedStorageMap iterators are not sorted over the keys
-        let mut chains = <Chains>::iter().collect::<Vec<(u32, u32)>>();
+        let mut chains = <Chains<I>>::iter().collect::<Vec<(u32, u32)>>();
         chains.sort_by_key(|k| k.0);
 
         let max_chain_element = chains.len() as u32;
@@ -1370,16 +2576,16 @@ impl<T: Config> Module<T> {
         let mut position_blockchain = max_chain_element;
 
         // Starting from the second highest element, find where to insert the new fork
-        // the previous element's block height should be higher or equal
-        // the next element's block height should be lower or equal
+        // the previous element's chainwork should be higher or equal
+        // the next element's chainwork should be lower or equal
         // NOTE: we never want to insert a new main chain through this function
         for (curr_position, curr_chain_id) in chains.iter().skip(1) {
-            // get the height of the current chain_id
-            let curr_height = Self::get_block_chain_from_id(*curr_chain_id)?.max_height;
+            // get the chainwork of the current chain_id
+            let curr_work = Self::get_block_chain_from_id(*curr_chain_id)?.chain_work;
 
-            // if the height of the current blockchain is lower than
+            // if the chainwork of the current blockchain is lower than
             // the new blockchain, it should be inserted at that position
-            if curr_height <= blockchain.max_height {
+            if curr_work <= blockchain.chain_work {
                 position_blockchain = *curr_position;
                 break;
             };
@@ -1402,6 +2608,223 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Marks a block header and all of its descendants invalid and removes them
+    /// from storage. The main chain is truncated below the invalid block and the
+    /// valid fork with the most accumulated work branching off the remaining main
+    /// chain is promoted, regardless of `StableBitcoinConfirmations`.
+    /// Returns the number of removed headers and the number of checked chains.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_hash` - hash of the invalid block header
+    pub fn _invalidate_block(block_hash: H256Le) -> Result<(u32, u32), DispatchError> {
+        Self::ensure_chain_work_migrated()?;
+        let block_header = Self::get_block_header_from_hash(block_hash)?;
+        let block_height = block_header.block_height;
+        let old_best_block = Self::get_best_block();
+        let old_best_block_height = Self::get_best_block_height();
+
+        let is_main_chain = block_header.chain_ref == MAIN_CHAIN_ID;
+        if is_main_chain {
+            let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+            ensure!(
+                block_height > main_chain.start_height,
+                Error::<T, I>::InitialBlockInvalidation
+            );
+            Self::ensure_no_checkpoint_in_main_chain_from(block_height)?;
+        }
+
+        // chains are processed by start height such that the parent
+        // of each fork has been checked before the fork itself
+        let mut chains = <ChainsIndex<I>>::iter()
+            .map(|(_, chain)| chain)
+            .collect::<Vec<BlockChain>>();
+        chains.sort_by_key(|chain| chain.start_height);
+        let checked_chains = chains.len() as u32;
+
+        let mut invalid_blocks = Vec::new();
+        let mut removed_headers = 0;
+        for chain in chains {
+            if chain.max_height < block_height {
+                continue;
+            }
+            // a chain only links to another chain through its first header, so
+            // either it contains the invalid block or it starts on an invalid block
+            let first_invalid_height = if chain.start_height <= block_height {
+                Some(block_height).filter(|&height| {
+                    Self::get_block_hash(chain.chain_id, height) == Ok(block_hash)
+                })
+            } else {
+                let hash = Self::get_block_hash(chain.chain_id, chain.start_height)?;
+                let header = Self::get_block_header_from_hash(hash)?;
+                Some(chain.start_height)
+                    .filter(|_| invalid_blocks.contains(&header.block_header.hash_prev_block))
+            };
+
+            if let Some(first_invalid_height) = first_invalid_height {
+                for height in first_invalid_height..=chain.max_height {
+                    let hash = Self::get_block_hash(chain.chain_id, height)?;
+                    if chain.chain_id == MAIN_CHAIN_ID {
+                        Self::notify_reorged_block(hash);
+                    }
+                    invalid_blocks.push(hash);
+                }
+                let invalid_headers = chain.max_height - first_invalid_height + 1;
+                removed_headers += Self::prune_fork(chain, invalid_headers)?;
+            }
+        }
+        // descendants of the invalid block can not be stored again either
+        <InvalidatedBlocks<I>>::insert(block_hash, true);
+        for hash in invalid_blocks {
+            <InvalidatedBlocks<I>>::insert(hash, true);
+        }
+
+        let mut reorg_start_height = block_height;
+        if is_main_chain {
+            Self::sort_chains();
+            if let Some(fork) = Self::get_best_valid_fork()? {
+                reorg_start_height = reorg_start_height.min(fork.start_height);
+                let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+                if fork.start_height > main_chain.max_height {
+                    Self::append_to_main_blockchain(&fork)?;
+                } else {
+                    Self::swap_main_blockchain(&fork)?;
+                }
+            }
+            Self::sort_chains();
+
+            let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+            let best_block = Self::get_block_hash(MAIN_CHAIN_ID, main_chain.max_height)?;
+            Self::set_best_block(best_block);
+            Self::set_best_block_height(main_chain.max_height);
+            if !main_chain.is_invalid() && !main_chain.is_no_data() {
+                Self::recover_if_needed()?;
+            }
+        } else {
+            Self::sort_chains();
+        }
+
+        Self::deposit_event(<Event<T, I>>::InvalidateBlock(block_hash, removed_headers));
+        if is_main_chain {
+            Self::deposit_event(<Event<T, I>>::ChainReorg(
+                old_best_block,
+                Self::get_best_block(),
+                (old_best_block_height + 1).saturating_sub(reorg_start_height),
+            ));
+        }
+
+        Ok((removed_headers, checked_chains))
+    }
+
+    /// Get the valid fork branching off the main chain with the most
+    /// accumulated work, if it has more work than the main chain
+    fn get_best_valid_fork() -> Result<Option<BlockChain>, DispatchError> {
+        let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+        let mut chains = <Chains<I>>::iter().collect::<Vec<(u32, u32)>>();
+        chains.sort_by_key(|k| k.0);
+
+        for (_, chain_id) in chains.into_iter().skip(1) {
+            let fork = Self::get_block_chain_from_id(chain_id)?;
+            if fork.chain_work <= main_chain.chain_work {
+                break;
+            }
+            if fork.is_invalid()
+                || fork.start_height <= main_chain.start_height
+                || fork.start_height > main_chain.max_height + 1
+            {
+                continue;
+            }
+            let first_header = Self::get_block_header_from_height(&fork, fork.start_height)?;
+            if Self::get_block_hash(MAIN_CHAIN_ID, fork.start_height - 1)
+                == Ok(first_header.block_header.hash_prev_block)
+            {
+                return Ok(Some(fork));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Appends a fork branching off the tip of the main chain to the main chain
+    ///
+    /// # Arguments
+    ///
+    /// * `fork` - the fork extending the main chain
+    fn append_to_main_blockchain(fork: &BlockChain) -> DispatchResult {
+        let mut main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+        for height in fork.start_height..=fork.max_height {
+            let block_hash = Self::get_block_hash(fork.chain_id, height)?;
+            Self::mutate_block_header_from_chain_id(&block_hash, MAIN_CHAIN_ID);
+            Self::insert_block_hash(MAIN_CHAIN_ID, height, block_hash);
+        }
+        <ChainsHashes<I>>::remove_prefix(fork.chain_id);
+
+        main_chain.max_height = fork.max_height;
+        main_chain.chain_work = fork.chain_work;
+        main_chain.no_data.append(&mut fork.no_data.clone());
+        main_chain.invalid.append(&mut fork.invalid.clone());
+        Self::set_block_chain_from_id(MAIN_CHAIN_ID, &main_chain);
+
+        let position = Self::get_chain_position_from_chain_id(fork.chain_id)?;
+        Self::remove_blockchain_from_chain(position)?;
+        Self::remove_blockchain_from_chain_index(fork.chain_id);
+        Ok(())
+    }
+
+    /// Sorts the forks in Chains by their accumulated chainwork,
+    /// keeping the main chain at the first position
+    ///
+    /// Returns the number of chains.
+    fn sort_chains() -> u32 {
+        let mut forks = <ChainsIndex<I>>::iter()
+            .map(|(_, chain)| chain)
+            .filter(|chain| chain.chain_id != MAIN_CHAIN_ID)
+            .collect::<Vec<BlockChain>>();
+        forks.sort_by(|a, b| b.chain_work.cmp(&a.chain_work));
+
+        for (position, _) in <Chains<I>>::iter().collect::<Vec<(u32, u32)>>() {
+            <Chains<I>>::remove(position);
+        }
+        Self::set_chain_from_position_and_id(0, MAIN_CHAIN_ID);
+        for (position, fork) in forks.iter().enumerate() {
+            Self::set_chain_from_position_and_id(position as u32 + 1, fork.chain_id);
+        }
+        forks.len() as u32 + 1
+    }
+
+    /// Notifies the requests relying on transactions of a block removed from the main chain.
+    /// The requests stay executed, so their fulfilling transactions are kept to detect
+    /// further payments, also once the transactions are included in the new main chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_hash` - hash of the removed block
+    fn notify_reorged_block(block_hash: H256Le) {
+        for consumer in <BlockConsumers<I>>::get(block_hash) {
+            T::OnReorg::on_reorg(consumer, block_hash);
+        }
+    }
+
+    /// Stops tracking the requests that became stable while the main chain
+    /// advanced, i.e. that were executed `StableBitcoinConfirmations` blocks ago
+    ///
+    /// # Arguments
+    ///
+    /// * `prev_best_height` - height of the main chain before it advanced
+    /// * `best_height` - new height of the main chain
+    fn clear_stable_block_consumers(prev_best_height: u32, best_height: u32) {
+        for height in prev_best_height.saturating_add(1)..=best_height {
+            for (block_hash, consumer) in <StableBlockConsumers<I>>::take(height) {
+                let mut consumers = <BlockConsumers<I>>::get(block_hash);
+                consumers.retain(|c| *c != consumer);
+                if consumers.is_empty() {
+                    <BlockConsumers<I>>::remove(block_hash);
+                } else {
+                    <BlockConsumers<I>>::insert(block_hash, consumers);
+                }
+            }
+        }
+    }
+
     /// Flag an error in a block header. This function is called by the
     /// security pallet.
     ///
@@ -1410,6 +2833,8 @@ impl<T: Config> Module<T> {
     /// * `block_hash` - the hash of the block header with the error
     /// * `error` - the error code for the block header
     pub fn flag_block_error(block_hash: H256Le, error: ErrorCode) -> Result<(), DispatchError> {
+        Self::ensure_chain_work_migrated()?;
+
         // Get the chain id of the block header
         let block_header = Self::get_block_header_from_hash(block_hash)?;
         let chain_id = block_header.chain_ref;
@@ -1422,13 +2847,13 @@ impl<T: Config> Module<T> {
         let newly_flagged = match error {
             ErrorCode::NoDataBTCRelay => blockchain.no_data.insert(block_header.block_height),
             ErrorCode::InvalidBTCRelay => blockchain.invalid.insert(block_header.block_height),
-            _ => return Err(Error::<T>::UnknownErrorcode.into()),
+            _ => return Err(Error::<T, I>::UnknownErrorcode.into()),
         };
 
         // If the block was not already flagged, store the updated blockchain entry
         if newly_flagged {
             Self::mutate_block_chain_from_id(chain_id, blockchain);
-            Self::deposit_event(Event::FlagBlockError(block_hash, chain_id, error));
+            Self::deposit_event(<Event<T, I>>::FlagBlockError(block_hash, chain_id, error));
         }
 
         Ok(())
@@ -1442,6 +2867,8 @@ impl<T: Config> Module<T> {
     /// * `block_hash` - the hash of the block header being cleared
     /// * `error` - the error code for the block header
     pub fn clear_block_error(block_hash: H256Le, error: ErrorCode) -> Result<(), DispatchError> {
+        Self::ensure_chain_work_migrated()?;
+
         // Get the chain id of the block header
         let block_header = Self::get_block_header_from_hash(block_hash)?;
         let chain_id = block_header.chain_ref;
@@ -1454,7 +2881,7 @@ impl<T: Config> Module<T> {
         let block_exists = match error {
             ErrorCode::NoDataBTCRelay => blockchain.no_data.remove(&block_header.block_height),
             ErrorCode::InvalidBTCRelay => blockchain.invalid.remove(&block_header.block_height),
-            _ => return Err(Error::<T>::UnknownErrorcode.into()),
+            _ => return Err(Error::<T, I>::UnknownErrorcode.into()),
         };
 
         if block_exists {
@@ -1465,7 +2892,7 @@ impl<T: Config> Module<T> {
             // Store the updated blockchain entry
             Self::mutate_block_chain_from_id(chain_id, blockchain);
 
-            Self::deposit_event(Event::ClearBlockError(block_hash, chain_id, error));
+            Self::deposit_event(<Event<T, I>>::ClearBlockError(block_hash, chain_id, error));
         }
 
         Ok(())
@@ -1491,7 +2918,7 @@ impl<T: Config> Module<T> {
         if main_chain_height >= tx_block_height + required_confirmations {
             Ok(())
         } else {
-            Err(Error::<T>::BitcoinConfirmations.into())
+            Err(Error::<T, I>::BitcoinConfirmations.into())
         }
     }
 
@@ -1503,11 +2930,11 @@ impl<T: Config> Module<T> {
     /// * `block_hash` - hash of the block to check
     pub fn check_parachain_confirmations(block_hash: H256Le) -> Result<(), DispatchError> {
         let current_height = <frame_system::Module<T>>::block_number();
-        let submitted_height = <ParachainHeight<T>>::get(block_hash);
+        let submitted_height = <ParachainHeight<T, I>>::get(block_hash);
 
         ensure!(
             submitted_height + Self::parachain_confirmations() <= current_height,
-            Error::<T>::ParachainConfirmations
+            Error::<T, I>::ParachainConfirmations
         );
 
         Ok(())
@@ -1527,32 +2954,43 @@ impl<T: Config> Module<T> {
 
         // Ensure main chain has no invalid block
         let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
-        ensure!(!main_chain.is_invalid(), Error::<T>::Invalid);
+        ensure!(!main_chain.is_invalid(), Error::<T, I>::Invalid);
 
         // Check if a NO_DATA block exists at a lower height than block_height
         if main_chain.is_no_data() {
             match main_chain.no_data.iter().next_back() {
-                Some(no_data_height) => ensure!(block_height < *no_data_height, Error::<T>::NoData),
+                Some(no_data_height) => {
+                    ensure!(block_height < *no_data_height, Error::<T, I>::NoData)
+                }
                 None => (),
             }
         }
         Ok(())
     }
 
+    /// Checks that no fork has at least as much work as the main chain without
+    /// its last `StableBitcoinConfirmations` blocks.
     fn ensure_no_ongoing_fork(best_block_height: u32) -> Result<(), DispatchError> {
         // check if there is a next best fork
         match Self::get_chain_id_from_position(1) {
-            // if yes, check that the main chain is at least Self::confirmations() ahead
+            // if yes, check that the main chain has more work than the fork
+            // without its last Self::confirmations() blocks
             Ok(id) => {
-                let next_best_fork_height = Self::get_block_chain_from_id(id)?.max_height;
-
-                debug::print!("Best block height: {}", best_block_height);
-                debug::print!("Next best fork height: {}", next_best_fork_height);
+                let next_best_fork_work = Self::get_block_chain_from_id(id)?.chain_work;
+                let main_chain = Self::get_block_chain_from_id(MAIN_CHAIN_ID)?;
+                let confirmed_height = best_block_height
+                    .saturating_sub(Self::get_stable_transaction_confirmations())
+                    .max(main_chain.start_height)
+                    .max(Self::pruned_height());
+                let confirmed_work =
+                    Self::get_block_header_from_height(&main_chain, confirmed_height)?.chain_work;
+
+                debug::print!("Confirmed main chain work: {}", confirmed_work);
+                debug::print!("Next best fork work: {}", next_best_fork_work);
                 // fail if there is an ongoing fork
                 ensure!(
-                    best_block_height
-                        >= next_best_fork_height + Self::get_stable_transaction_confirmations(),
-                    Error::<T>::OngoingFork
+                    confirmed_work >= next_best_fork_work,
+                    Error::<T, I>::OngoingFork
                 );
             }
             // else, do nothing if there is no fork
@@ -1561,7 +2999,162 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Starts migrating `BlockHeaders` and `ChainsIndex` to track the accumulated
+    /// chainwork. As the work below the initial block is unknown, it is accounted
+    /// relative to the lowest stored block.
+    ///
+    /// Returns the weight consumed.
+    fn start_chain_work_migration() -> Weight {
+        let weight = T::DbWeight::get().reads(4);
+        if !Self::best_block_exists() {
+            return weight;
+        }
+        let main_chain: Option<v0::BlockChain> =
+            unhashed::get(&<ChainsIndex<I>>::hashed_key_for(MAIN_CHAIN_ID));
+        let start_height = match main_chain {
+            Some(main_chain) => main_chain.start_height,
+            None => return weight,
+        };
+        // forks were at most `StableBitcoinConfirmations` ahead of the main chain
+        <ChainWorkMigration<I>>::put(ChainWorkMigrationCursor {
+            block_height: start_height,
+            max_height: Self::get_best_block_height()
+                .saturating_add(Self::get_stable_transaction_confirmations()),
+            chain_id: MAIN_CHAIN_ID,
+            headers_migrated: false,
+        });
+        weight.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Ensures the chainwork migration has completed
+    fn ensure_chain_work_migrated() -> DispatchResult {
+        ensure!(
+            !<ChainWorkMigration<I>>::exists(),
+            Error::<T, I>::MigrationInProgress
+        );
+        Ok(())
+    }
+
+    /// Visits at most `CHAIN_WORK_MIGRATION_STEPS` stored positions of the chainwork
+    /// migration. Block headers are migrated by ascending height, such that the
+    /// parent of each header has been migrated before it. Afterwards, the chains
+    /// are migrated and sorted by their accumulated chainwork.
+    ///
+    /// Returns the weight consumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `migration` - the progress of the migration
+    fn migrate_chain_work_step(mut migration: ChainWorkMigrationCursor) -> Weight {
+        let chain_counter = Self::get_chain_counter();
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+        for _ in 0..CHAIN_WORK_MIGRATION_STEPS {
+            if !migration.headers_migrated {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+                if Self::block_exists(migration.chain_id, migration.block_height) {
+                    let block_hash =
+                        <ChainsHashes<I>>::get(migration.chain_id, migration.block_height);
+                    Self::migrate_block_header_chain_work(block_hash);
+                    weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 1));
+                }
+
+                if migration.chain_id < chain_counter {
+                    migration.chain_id += 1;
+                } else if migration.block_height < migration.max_height {
+                    migration.chain_id = MAIN_CHAIN_ID;
+                    migration.block_height += 1;
+                } else {
+                    migration.chain_id = MAIN_CHAIN_ID;
+                    migration.headers_migrated = true;
+                }
+            } else {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+                let key = <ChainsIndex<I>>::hashed_key_for(migration.chain_id);
+                if let Some(chain) = unhashed::get::<v0::BlockChain>(&key) {
+                    let tip = <ChainsHashes<I>>::get(chain.chain_id, chain.max_height);
+                    Self::set_block_chain_from_id(
+                        chain.chain_id,
+                        &BlockChain {
+                            chain_id: chain.chain_id,
+                            start_height: chain.start_height,
+                            max_height: chain.max_height,
+                            no_data: chain.no_data,
+                            invalid: chain.invalid,
+                            chain_work: <BlockHeaders<T, I>>::get(tip).chain_work,
+                        },
+                    );
+                    weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
+                }
+
+                if migration.chain_id < chain_counter {
+                    migration.chain_id += 1;
+                } else {
+                    // the priority queue was ordered by height
+                    let chains = Self::sort_chains() as Weight;
+                    <ChainWorkMigration<I>>::kill();
+                    return weight
+                        .saturating_add(T::DbWeight::get().reads_writes(2 * chains, 2 * chains));
+                }
+            }
+        }
+
+        <ChainWorkMigration<I>>::put(migration);
+        weight
+    }
+
+    /// Migrates a block header to track the accumulated chainwork, which requires
+    /// its parent to be migrated already.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_hash` - the hash of the block header
+    fn migrate_block_header_chain_work(block_hash: H256Le) {
+        let key = <BlockHeaders<T, I>>::hashed_key_for(block_hash);
+        let header = match unhashed::get::<v0::RichBlockHeader<T::AccountId>>(&key) {
+            Some(header) => header,
+            None => return,
+        };
+        let prev_chain_work = if Self::block_header_exists(header.block_header.hash_prev_block) {
+            <BlockHeaders<T, I>>::get(header.block_header.hash_prev_block).chain_work
+        } else {
+            U256::zero()
+        };
+        <BlockHeaders<T, I>>::insert(
+            block_hash,
+            RichBlockHeader {
+                block_hash: header.block_hash,
+                block_header: header.block_header,
+                block_height: header.block_height,
+                chain_ref: header.chain_ref,
+                account_id: header.account_id,
+                chain_work: prev_chain_work.saturating_add(header.block_header.work()),
+            },
+        );
+    }
+
+    /// Replaces the `Disable*` flags by the `VerificationProfile`. Inclusion checks
+    /// are always enabled when relaying Bitcoin mainnet.
+    fn migrate_verification_profile_v2_to_v3() {
+        let is_disabled = |item: &[u8]| {
+            take_storage_value::<bool>(I::PREFIX.as_bytes(), item, &[]).unwrap_or_default()
+        };
+        let verification_profile = VerificationProfile {
+            difficulty_check: !is_disabled(b"DisableDifficultyCheck"),
+            inclusion_check: !is_disabled(b"DisableInclusionCheck") || Self::is_mainnet(),
+            op_return_check: !is_disabled(b"DisableOpReturnCheck"),
+            relayer_auth: !is_disabled(b"DisableRelayerAuth"),
+        };
+        <ActiveVerificationProfile<I>>::put(verification_profile);
+    }
+
+    /// Recovers the parachain from BTC-Relay failures. Errors are only reported
+    /// for the default instance, which is used to execute requests, so other
+    /// instances never recover the parachain.
     fn recover_if_needed() -> Result<(), DispatchError> {
+        if I::PREFIX != DefaultInstance::PREFIX {
+            return Ok(());
+        }
         if ext::security::is_parachain_error_invalid_btcrelay::<T>()
             || ext::security::is_parachain_error_no_data_btcrelay::<T>()
         {
@@ -1572,22 +3165,164 @@ impl<T: Config> Module<T> {
     }
 }
 
+/// Errors of the off-chain worker
+#[derive(Debug, PartialEq, Eq)]
+enum OffchainError {
+    /// Request to bitcoind failed
+    Bitcoind(bitcoind::Error),
+    /// The configured bitcoind url is not valid utf8
+    InvalidUrl,
+    /// BTC-Relay has not been initialized
+    NotInitialized,
+    /// No local key to sign the submission
+    NoLocalKey,
+    /// None of the stored block headers is on the bitcoind main chain
+    NoCommonAncestor,
+    /// The signed transaction could not be submitted
+    SubmitTransaction,
+}
+
+impl From<bitcoind::Error> for OffchainError {
+    fn from(err: bitcoind::Error) -> Self {
+        Self::Bitcoind(err)
+    }
+}
+
+/// The off-chain worker is enabled by setting the bitcoind url (e.g. `http://127.0.0.1:8332`)
+/// and optionally the `user:password` credentials in the persistent offchain local storage,
+/// under `OFFCHAIN_BITCOIND_URL` and `OFFCHAIN_BITCOIND_CREDENTIALS` prefixed by the instance
+/// (e.g. `BTCRelay::bitcoind-url`). Headers are signed with a `KEY_TYPE` key from the node
+/// keystore, whose account must be an authorized relayer.
+impl<T: OffchainConfig<I>, I: Instance> Module<T, I> {
+    /// Submits the block headers of bitcoind's main chain that are not yet stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_number` - current parachain block number
+    fn relay_block_headers(block_number: T::BlockNumber) -> Result<(), OffchainError> {
+        let url = match Self::offchain_local_storage_get(OFFCHAIN_BITCOIND_URL) {
+            Some(url) => url,
+            None => return Ok(()),
+        };
+        let url = str::from_utf8(&url).map_err(|_| OffchainError::InvalidUrl)?;
+        let credentials = Self::offchain_local_storage_get(OFFCHAIN_BITCOIND_CREDENTIALS);
+        ensure!(Self::best_block_exists(), OffchainError::NotInitialized);
+
+        // workers of concurrent block imports must not submit the same headers
+        let lock_key = Self::offchain_storage_key(OFFCHAIN_LOCK);
+        let mut lock = StorageLock::<Time>::new(&lock_key);
+        let _guard = match lock.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Ok(()),
+        };
+
+        // wait for the last submission to be included
+        let best_block = Self::get_best_block();
+        let last_submission =
+            StorageValueRef::persistent(&Self::offchain_storage_key(OFFCHAIN_LAST_SUBMISSION));
+        if let Some(Some((block_hash, submitted_at))) =
+            last_submission.get::<(H256Le, T::BlockNumber)>()
+        {
+            if block_hash == best_block
+                && block_number < submitted_at.saturating_add(OFFCHAIN_RESUBMISSION_DELAY.into())
+            {
+                return Ok(());
+            }
+        }
+
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        ensure!(signer.can_sign(), OffchainError::NoLocalKey);
+
+        let client = bitcoind::Client::new(url, credentials.as_deref());
+        let raw_block_headers = Self::fetch_block_headers(&client)?;
+        if raw_block_headers.is_empty() {
+            return Ok(());
+        }
+
+        // the headers branch off at most `OFFCHAIN_MAX_REORG_DEPTH` blocks below the best block
+        match signer.send_signed_transaction(|_| {
+            Call::store_block_headers(raw_block_headers.clone(), OFFCHAIN_MAX_REORG_DEPTH)
+        }) {
+            Some((_, Ok(()))) => {
+                last_submission.set(&(best_block, block_number));
+                Ok(())
+            }
+            _ => Err(OffchainError::SubmitTransaction),
+        }
+    }
+
+    /// Fetches the block headers of bitcoind's main chain following the highest
+    /// stored block header, searching at most `OFFCHAIN_MAX_REORG_DEPTH` blocks below the best block.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - bitcoind client
+    fn fetch_block_headers(
+        client: &bitcoind::Client,
+    ) -> Result<Vec<RawBlockHeader>, OffchainError> {
+        let block_count = client.get_block_count()?;
+        let mut height = Self::get_best_block_height().min(block_count);
+        let lowest_height = height.saturating_sub(OFFCHAIN_MAX_REORG_DEPTH);
+        while !Self::block_header_exists(client.get_block_hash(height)?) {
+            if height <= lowest_height {
+                return Err(OffchainError::NoCommonAncestor);
+            }
+            height -= 1;
+        }
+
+        let max_height = block_count.min(height.saturating_add(OFFCHAIN_MAX_HEADERS));
+        (height.saturating_add(1)..=max_height)
+            .map(|height| {
+                let block_hash = client.get_block_hash(height)?;
+                Ok(client.get_block_header(block_hash)?)
+            })
+            .collect()
+    }
+
+    /// Reads a raw value of this instance from the persistent offchain local storage
+    fn offchain_local_storage_get(key: &[u8]) -> Option<Vec<u8>> {
+        sp_io::offchain::local_storage_get(
+            StorageKind::PERSISTENT,
+            &Self::offchain_storage_key(key),
+        )
+    }
+
+    /// Prefixes the offchain local storage key with the instance
+    fn offchain_storage_key(key: &[u8]) -> Vec<u8> {
+        [I::PREFIX.as_bytes(), b"::", key].concat()
+    }
+}
+
 decl_event! {
-    pub enum Event {
+    pub enum Event<T, I: Instance = DefaultInstance>
+    where
+        Phantom = sp_std::marker::PhantomData<T>,
+    {
         Initialized(u32, H256Le),
         StoreMainChainHeader(u32, H256Le),
         StoreForkHeader(u32, u32, H256Le),
-        ChainReorg(H256Le, u32, u32),
+        StoreBlockHeaders(Vec<(H256Le, BlockHeaderResult)>),
+        /// Main chain replaced by a fork [old_tip, new_tip, fork_depth]
+        ChainReorg(H256Le, H256Le, u32),
         ForkAheadOfMainChain(u32, u32, u32),
         VerifyTransaction(H256Le, u32, u32),
         ValidateTransaction(H256Le, u32, H160, H256Le),
         FlagBlockError(H256Le, u32, ErrorCode),
         ClearBlockError(H256Le, u32, ErrorCode),
+        SetCheckpoint(u32, H256Le),
+        RemoveCheckpoint(u32),
+        /// Checks performed by the module updated [verification_profile]
+        SetVerificationProfile(VerificationProfile),
+        /// Block header and its descendants invalidated [block_hash, removed_headers]
+        InvalidateBlock(H256Le, u32),
+        /// Never emitted, binds the events to the runtime of the instance
+        #[doc(hidden)]
+        _Phantom(Phantom),
     }
 }
 
 decl_error! {
-    pub enum Error for Module<T: Config> {
+    pub enum Error for Module<T: Config<I>, I: Instance> {
         /// Already initialized
         AlreadyInitialized,
         /// Missing the block at this height
@@ -1684,10 +3419,30 @@ decl_error! {
         ArithmeticUnderflow,
         /// Relayer is not registered
         RelayerNotAuthorized,
+        /// Block header conflicts with a checkpoint
+        CheckpointConflict,
+        /// Initial block header is not a checkpoint
+        NoCheckpoint,
+        /// Block header was pruned from storage
+        BlockPruned,
+        /// Transaction output was already used to execute a request
+        TransactionAlreadyUsed,
+        /// Block header was invalidated
+        InvalidatedBlock,
+        /// The initial block of the main chain can not be invalidated
+        InitialBlockInvalidation,
+        /// Inclusion checks can not be disabled when relaying Bitcoin mainnet
+        InclusionCheckRequired,
+        /// Block headers are being migrated, BTC-Relay can not be used until the migration has completed
+        MigrationInProgress,
+        /// Reorg replaces more main chain blocks than declared
+        ReorgDepthExceeded,
+        /// Invalidation removes more block headers or checks more chains than declared
+        InvalidationBoundExceeded,
     }
 }
 
-impl<T: Config> From<BitcoinError> for Error<T> {
+impl<T: Config<I>, I: Instance> From<BitcoinError> for Error<T, I> {
     fn from(err: BitcoinError) -> Self {
         match err {
             BitcoinError::MalformedMerkleProof => Self::MalformedMerkleProof,
@@ -1,12 +1,12 @@
 use crate as btc_relay;
-use crate::{Config, Error};
+use crate::{Config, Error, VerificationProfile};
 use frame_support::{parameter_types, traits::StorageMapShim};
 use mocktopus::mocking::clear_mocks;
 use sp_arithmetic::{FixedI128, FixedU128};
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    testing::{Header, TestSignature, TestXt, UintAuthorityId},
+    traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
 };
 
 pub const BITCOIN_CONFIRMATIONS: u32 = 6;
@@ -33,11 +33,13 @@ frame_support::construct_runtime!(
         Treasury: treasury::{Module, Call, Storage, Event<T>},
 
         // Operational
-        BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event},
+        BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event<T>},
+        BTCRelayTestnet: btc_relay::<Instance1>::{Module, Call, Config<T>, Storage, Event<T>},
         Security: security::{Module, Call, Storage, Event},
         VaultRegistry: vault_registry::{Module, Call, Config<T>, Storage, Event<T>},
         ExchangeRateOracle: exchange_rate_oracle::{Module, Call, Config<T>, Storage, Event<T>},
         Sla: sla::{Module, Call, Config<T>, Storage, Event<T>},
+        Fee: fee::{Module, Call, Config<T>, Storage, Event<T>},
     }
 );
 
@@ -112,9 +114,66 @@ impl pallet_balances::Config<pallet_balances::Instance2> for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub BitcoinMainnet: btc_relay::NetworkParameters = btc_relay::BitcoinNetwork::Mainnet.parameters();
+    pub BitcoinTestnet: btc_relay::NetworkParameters = btc_relay::BitcoinNetwork::Testnet.parameters();
+}
+
 impl Config for Test {
     type Event = TestEvent;
     type WeightInfo = ();
+    type Network = BitcoinMainnet;
+    type OnReorg = ();
+    type OffchainWorker = btc_relay::BitcoindRelayer<Test>;
+}
+
+impl btc_relay::OffchainConfig for Test {
+    type AuthorityId = TestAuthId;
+}
+
+impl Config<btc_relay::Instance1> for Test {
+    type Event = TestEvent;
+    type WeightInfo = ();
+    type Network = BitcoinTestnet;
+    type OnReorg = ();
+    type OffchainWorker = ();
+}
+
+pub type Extrinsic = TestXt<Call, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    Call: From<C>,
+{
+    type OverarchingCall = Call;
+    type Extrinsic = Extrinsic;
+}
+
+impl<C> frame_system::offchain::CreateSignedTransaction<C> for Test
+where
+    Call: From<C>,
+{
+    fn create_transaction<A: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+        call: Call,
+        _public: UintAuthorityId,
+        _account: AccountId,
+        nonce: u64,
+    ) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+        Some((call, (nonce, ())))
+    }
+}
+
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+    type RuntimeAppPublic = UintAuthorityId;
+    type GenericPublic = UintAuthorityId;
+    type GenericSignature = TestSignature;
 }
 
 impl sla::Config for Test {
@@ -122,6 +181,13 @@ impl sla::Config for Test {
     type SignedFixedPoint = FixedI128;
 }
 
+impl fee::Config for Test {
+    type Event = TestEvent;
+    type UnsignedFixedPoint = FixedU128;
+    type WeightInfo = ();
+    type RelayerRewards = ();
+}
+
 impl treasury::Config for Test {
     type Event = TestEvent;
     type PolkaBTC = pallet_balances::Module<Test, pallet_balances::Instance2>;
@@ -156,12 +222,19 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 100;
+}
+
 impl security::Config for Test {
     type Event = TestEvent;
+    type MaxStatusHistory = MaxStatusHistory;
+    type RandomnessSource = pallet_randomness_collective_flip::Module<Test>;
 }
 
 pub type TestEvent = Event;
 pub type TestError = Error<Test>;
+pub type TestnetError = Error<Test, btc_relay::Instance1>;
 pub type SecurityError = security::Error<Test>;
 
 pub struct ExtBuilder;
@@ -175,10 +248,29 @@ impl ExtBuilder {
         btc_relay::GenesisConfig::<Test> {
             bitcoin_confirmations: BITCOIN_CONFIRMATIONS,
             parachain_confirmations: PARACHAIN_CONFIRMATIONS,
-            disable_difficulty_check: false,
-            disable_inclusion_check: false,
-            disable_op_return_check: false,
-            disable_relayer_auth: true,
+            checkpoints: vec![],
+            pruning_depth: 0,
+            max_fork_depth: 0,
+            max_pruned_headers_per_block: 0,
+            verification_profile: VerificationProfile {
+                relayer_auth: false,
+                ..Default::default()
+            },
+        }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
+        btc_relay::GenesisConfig::<Test, btc_relay::Instance1> {
+            bitcoin_confirmations: BITCOIN_CONFIRMATIONS,
+            parachain_confirmations: PARACHAIN_CONFIRMATIONS,
+            checkpoints: vec![],
+            pruning_depth: 0,
+            max_fork_depth: 0,
+            max_pruned_headers_per_block: 0,
+            verification_profile: VerificationProfile {
+                relayer_auth: false,
+                ..Default::default()
+            },
         }
         .assimilate_storage(&mut storage)
         .unwrap();
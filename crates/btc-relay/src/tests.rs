@@ -4,19 +4,27 @@ use primitive_types::U256;
 use crate::ext;
 use crate::mock::*;
 use crate::types::*;
-use crate::BtcAddress;
-use crate::Event;
+use crate::{BitcoinNetwork, BtcAddress, WeightInfo};
+
+type Event = crate::Event<Test>;
 
 use bitcoin::formatter::TryFormattable;
 use bitcoin::merkle::*;
 use bitcoin::parser::*;
 use bitcoin::types::*;
-use frame_support::{assert_err, assert_ok};
+use codec::Decode;
+use frame_support::{assert_err, assert_ok, weights::Pays};
 use mocktopus::mocking::*;
 use security::{ErrorCode, StatusCode};
+use sp_core::offchain::{
+    testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+    OffchainExt, StorageKind, TransactionPoolExt,
+};
+use sp_runtime::testing::UintAuthorityId;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::convert::TryInto;
 use sp_std::str::FromStr;
+use std::{cell::RefCell, rc::Rc};
 
 /// # Getters and setters
 ///
@@ -55,6 +63,7 @@ fn get_block_header_from_hash_succeeds() {
             block_height: block_height,
             chain_ref: chain_ref,
             account_id: Default::default(),
+            chain_work: Default::default(),
         };
 
         BTCRelay::set_block_header_from_hash(rich_header.block_hash, &rich_header);
@@ -163,6 +172,7 @@ fn store_block_header_on_mainchain_succeeds() {
             block_height: block_height,
             chain_ref: chain_ref,
             account_id: Default::default(),
+            chain_work: Default::default(),
         };
         BTCRelay::get_block_header_from_hash
             .mock_safe(move |_| MockResult::Return(Ok(rich_header)));
@@ -175,7 +185,8 @@ fn store_block_header_on_mainchain_succeeds() {
         let block_header_hash = block_header.hash();
         assert_ok!(BTCRelay::store_block_header(
             Origin::signed(3),
-            block_header
+            block_header,
+            u32::MAX
         ));
 
         let store_main_event = TestEvent::btc_relay(Event::StoreMainChainHeader(
@@ -208,6 +219,7 @@ fn store_block_header_on_fork_succeeds() {
             block_height: block_height - 1,
             chain_ref: chain_ref,
             account_id: Default::default(),
+            chain_work: Default::default(),
         };
         BTCRelay::get_block_header_from_hash
             .mock_safe(move |_| MockResult::Return(Ok(rich_header)));
@@ -220,7 +232,8 @@ fn store_block_header_on_fork_succeeds() {
         let block_header_hash = block_header.hash();
         assert_ok!(BTCRelay::store_block_header(
             Origin::signed(3),
-            block_header
+            block_header,
+            u32::MAX
         ));
 
         let store_fork_event = TestEvent::btc_relay(Event::StoreForkHeader(
@@ -241,7 +254,7 @@ fn store_block_header_parachain_shutdown_fails() {
             .mock_safe(|| MockResult::Return(Err(SecurityError::ParachainShutdown.into())));
 
         assert_err!(
-            BTCRelay::store_block_header(Origin::signed(3), block_header),
+            BTCRelay::store_block_header(Origin::signed(3), block_header, u32::MAX),
             SecurityError::ParachainShutdown,
         );
     })
@@ -257,7 +270,7 @@ fn check_and_do_reorg_is_main_chain_succeeds() {
         let blockchain =
             get_empty_block_chain_from_chain_id_and_height(chain_ref, start_height, block_height);
 
-        assert_ok!(BTCRelay::check_and_do_reorg(&blockchain));
+        assert_ok!(BTCRelay::check_and_do_reorg(&blockchain, u32::MAX));
     })
 }
 
@@ -272,7 +285,7 @@ fn check_and_do_reorg_fork_id_not_found() {
             get_empty_block_chain_from_chain_id_and_height(chain_ref, start_height, block_height);
 
         assert_err!(
-            BTCRelay::check_and_do_reorg(&blockchain),
+            BTCRelay::check_and_do_reorg(&blockchain, u32::MAX),
             TestError::ForkIdNotFound
         );
     })
@@ -325,7 +338,7 @@ fn check_and_do_reorg_swap_fork_position() {
 
         assert_eq!(current_position, fork_position);
 
-        assert_ok!(BTCRelay::check_and_do_reorg(&fork));
+        assert_ok!(BTCRelay::check_and_do_reorg(&fork, u32::MAX));
         // assert that positions have been swapped
         let new_position = BTCRelay::get_chain_position_from_chain_id(fork_chain_ref).unwrap();
         assert_eq!(new_position, swap_position);
@@ -339,53 +352,112 @@ fn check_and_do_reorg_swap_fork_position() {
 #[test]
 fn check_and_do_reorg_new_fork_is_main_chain() {
     run_test(|| {
-        // insert the main chain in Chains and ChainsIndex
-        let main_chain_ref: u32 = 0;
+        use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageValue;
+        use crate::ChainCounter;
+
+        // store the main chain and the fork with their block headers
         let main_start_height: u32 = 4;
         let main_block_height: u32 = 110;
-        let main_position: u32 = 0;
-        let main = get_empty_block_chain_from_chain_id_and_height(
-            main_chain_ref,
-            main_start_height,
-            main_block_height,
-        );
-        BTCRelay::set_chain_from_position_and_id(main_position, main_chain_ref);
-        BTCRelay::set_block_chain_from_id(main_chain_ref, &main);
-
-        // insert the fork chain in Chains and ChainsIndex
+        let main = store_blockchain_and_random_headers(0, main_start_height, main_block_height, 0);
         let fork_chain_ref: u32 = 4;
         let fork_block_height: u32 = 117;
-        let fork_position: u32 = 1;
-        let fork = get_empty_block_chain_from_chain_id_and_height(
+        let fork = store_blockchain_and_random_headers(
             fork_chain_ref,
             main_start_height,
             fork_block_height,
+            1,
         );
-        BTCRelay::set_chain_from_position_and_id(fork_position, fork_chain_ref);
-        BTCRelay::set_block_chain_from_id(fork_chain_ref, &fork);
+        <ChainCounter>::put(fork_chain_ref);
 
         // set the best block
-        let best_block_hash = H256Le::zero();
-        BTCRelay::set_best_block(best_block_hash);
-        BTCRelay::set_best_block_height(fork_block_height);
+        let old_tip = BTCRelay::get_block_hash(main.chain_id, main_block_height).unwrap();
+        let new_tip = BTCRelay::get_block_hash(fork_chain_ref, fork_block_height).unwrap();
+        BTCRelay::set_best_block(old_tip);
+        BTCRelay::set_best_block_height(main_block_height);
 
-        // check that fork is at its initial position
-        let current_position = BTCRelay::get_chain_position_from_chain_id(fork_chain_ref).unwrap();
-
-        assert_eq!(current_position, fork_position);
-
-        BTCRelay::swap_main_blockchain.mock_safe(|_| MockResult::Return(Ok(())));
+        assert_eq!(
+            BTCRelay::check_and_do_reorg(&fork, u32::MAX),
+            Ok(main_block_height + 1 - main_start_height)
+        );
 
-        assert_ok!(BTCRelay::check_and_do_reorg(&fork));
         // assert that the new main chain is set
         let reorg_event = TestEvent::btc_relay(Event::ChainReorg(
-            best_block_hash,
-            fork_block_height,
-            fork.max_height - fork.start_height,
+            old_tip,
+            new_tip,
+            main_block_height + 1 - main_start_height,
         ));
         assert!(System::events().iter().any(|a| a.event == reorg_event));
+        assert_eq!(BTCRelay::get_best_block(), new_tip);
+        assert_eq!(BTCRelay::get_best_block_height(), fork_block_height);
+        assert_eq!(
+            BTCRelay::get_block_hash(crate::MAIN_CHAIN_ID, fork_block_height),
+            Ok(new_tip)
+        );
+        // the replaced main chain is kept as a fork
+        let forked_main_chain = BTCRelay::get_block_chain_from_id(fork_chain_ref + 1).unwrap();
+        assert_eq!(forked_main_chain.max_height, main_block_height);
+        assert_eq!(
+            BTCRelay::get_block_hash(forked_main_chain.chain_id, main_block_height),
+            Ok(old_tip)
+        );
+    })
+}
+
+#[test]
+fn check_and_do_reorg_deeper_than_max_reorg_depth_fails() {
+    run_test(|| {
+        use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageValue;
+        use crate::ChainCounter;
+
+        let main_start_height: u32 = 4;
+        let main_block_height: u32 = 110;
+        let main = store_blockchain_and_random_headers(0, main_start_height, main_block_height, 0);
+        let fork_chain_ref: u32 = 4;
+        let fork = store_blockchain_and_random_headers(fork_chain_ref, main_start_height, 117, 1);
+        <ChainCounter>::put(fork_chain_ref);
+
+        let old_tip = BTCRelay::get_block_hash(main.chain_id, main_block_height).unwrap();
+        BTCRelay::set_best_block(old_tip);
+        BTCRelay::set_best_block_height(main_block_height);
+
+        assert_err!(
+            BTCRelay::check_and_do_reorg(&fork, main_block_height - main_start_height),
+            TestError::ReorgDepthExceeded
+        );
+        assert_eq!(BTCRelay::get_best_block(), old_tip);
+    })
+}
+
+#[test]
+fn check_and_do_reorg_compares_main_chain_against_fork_work() {
+    run_test(|| {
+        let main = get_empty_block_chain_from_chain_id_and_height(0, 4, 110);
+        BTCRelay::set_chain_from_position_and_id(0, 0);
+        BTCRelay::set_block_chain_from_id(0, &main);
+
+        // a fork with less work than the main chain ranks above the new fork
+        let weaker_fork = get_empty_block_chain_from_chain_id_and_height(3, 50, 100);
+        BTCRelay::set_chain_from_position_and_id(1, 3);
+        BTCRelay::set_block_chain_from_id(3, &weaker_fork);
+
+        let fork = get_empty_block_chain_from_chain_id_and_height(4, 50, 117);
+        BTCRelay::set_chain_from_position_and_id(2, 4);
+        BTCRelay::set_block_chain_from_id(4, &fork);
+
+        BTCRelay::set_best_block(H256Le::zero());
+        BTCRelay::set_best_block_height(110);
+
+        BTCRelay::swap_main_blockchain.mock_safe(|_| MockResult::Return(Ok(())));
+
+        assert_ok!(BTCRelay::check_and_do_reorg(&fork, u32::MAX));
+        // the fork passed the weaker fork and then replaced the main chain
+        assert_eq!(BTCRelay::get_chain_position_from_chain_id(4), Ok(1));
+        assert!(System::events()
+            .iter()
+            .any(|a| matches!(a.event, TestEvent::btc_relay(Event::ChainReorg(_, _, _)))));
     })
 }
+
 #[test]
 fn check_and_do_reorg_new_fork_below_stable_transaction_confirmations() {
     run_test(|| {
@@ -426,7 +498,7 @@ fn check_and_do_reorg_new_fork_below_stable_transaction_confirmations() {
 
         BTCRelay::swap_main_blockchain.mock_safe(|_| MockResult::Return(Ok(())));
 
-        assert_ok!(BTCRelay::check_and_do_reorg(&fork));
+        assert_ok!(BTCRelay::check_and_do_reorg(&fork, u32::MAX));
         // assert that the fork has not overtaken the main chain
         let ahead_event = TestEvent::btc_relay(Event::ForkAheadOfMainChain(
             main_block_height,
@@ -437,6 +509,72 @@ fn check_and_do_reorg_new_fork_below_stable_transaction_confirmations() {
     })
 }
 
+#[test]
+fn check_and_do_reorg_higher_fork_with_less_work_does_not_reorg() {
+    run_test(|| {
+        // insert the main chain in Chains and ChainsIndex
+        let main_chain_ref: u32 = 0;
+        let main_start_height: u32 = 4;
+        let main_block_height: u32 = 110;
+        let mut main = get_empty_block_chain_from_chain_id_and_height(
+            main_chain_ref,
+            main_start_height,
+            main_block_height,
+        );
+        main.chain_work = 1000.into();
+        BTCRelay::set_chain_from_position_and_id(0, main_chain_ref);
+        BTCRelay::set_block_chain_from_id(main_chain_ref, &main);
+
+        // the fork is longer than the main chain but has less work
+        let fork_chain_ref: u32 = 4;
+        let fork_block_height: u32 = 117;
+        let mut fork = get_empty_block_chain_from_chain_id_and_height(
+            fork_chain_ref,
+            main_start_height,
+            fork_block_height,
+        );
+        fork.chain_work = 500.into();
+        BTCRelay::set_chain_from_position_and_id(1, fork_chain_ref);
+        BTCRelay::set_block_chain_from_id(fork_chain_ref, &fork);
+
+        BTCRelay::swap_main_blockchain.mock_safe(|_| panic!("Should not reorg"));
+
+        assert_ok!(BTCRelay::check_and_do_reorg(&fork, u32::MAX));
+        assert_eq!(
+            BTCRelay::get_chain_position_from_chain_id(fork_chain_ref),
+            Ok(1)
+        );
+        assert!(!System::events().iter().any(|a| matches!(
+            a.event,
+            TestEvent::btc_relay(Event::ForkAheadOfMainChain(..))
+        )));
+    })
+}
+
+#[test]
+fn check_and_do_reorg_fork_with_equal_work_does_not_reorg() {
+    run_test(|| {
+        let main_chain_ref: u32 = 0;
+        let main_start_height: u32 = 4;
+        let mut main =
+            get_empty_block_chain_from_chain_id_and_height(main_chain_ref, main_start_height, 110);
+        main.chain_work = 1000.into();
+        BTCRelay::set_chain_from_position_and_id(0, main_chain_ref);
+        BTCRelay::set_block_chain_from_id(main_chain_ref, &main);
+
+        let fork_chain_ref: u32 = 4;
+        let mut fork =
+            get_empty_block_chain_from_chain_id_and_height(fork_chain_ref, main_start_height, 117);
+        fork.chain_work = 1000.into();
+        BTCRelay::set_chain_from_position_and_id(1, fork_chain_ref);
+        BTCRelay::set_block_chain_from_id(fork_chain_ref, &fork);
+
+        BTCRelay::swap_main_blockchain.mock_safe(|_| panic!("Should not reorg"));
+
+        assert_ok!(BTCRelay::check_and_do_reorg(&fork, u32::MAX));
+    })
+}
+
 /// insert_sorted
 #[test]
 fn insert_sorted_succeeds() {
@@ -493,6 +631,30 @@ fn insert_sorted_succeeds() {
     })
 }
 
+#[test]
+fn insert_sorted_orders_by_chain_work() {
+    run_test(|| {
+        let main = get_empty_block_chain_from_chain_id_and_height(0, 60, 110);
+        BTCRelay::set_block_chain_from_id(0, &main);
+        assert_ok!(BTCRelay::insert_sorted(&main));
+
+        // long fork with little work
+        let mut long_fork = get_empty_block_chain_from_chain_id_and_height(3, 70, 100);
+        long_fork.chain_work = 10.into();
+        BTCRelay::set_block_chain_from_id(3, &long_fork);
+        assert_ok!(BTCRelay::insert_sorted(&long_fork));
+
+        // short fork with more work
+        let mut heavy_fork = get_empty_block_chain_from_chain_id_and_height(4, 70, 80);
+        heavy_fork.chain_work = 20.into();
+        BTCRelay::set_block_chain_from_id(4, &heavy_fork);
+        assert_ok!(BTCRelay::insert_sorted(&heavy_fork));
+
+        assert_eq!(BTCRelay::get_chain_position_from_chain_id(4), Ok(1));
+        assert_eq!(BTCRelay::get_chain_position_from_chain_id(3), Ok(2));
+    })
+}
+
 /// swap_main_blockchain
 #[test]
 fn swap_main_blockchain_succeeds() {
@@ -629,6 +791,7 @@ fn test_verify_block_header_correct_retarget_increase_succeeds() {
             chain_ref,
             block_height,
             Default::default(),
+            Default::default(),
         )
         .unwrap();
 
@@ -661,6 +824,7 @@ fn test_verify_block_header_correct_retarget_decrease_succeeds() {
             chain_ref,
             block_height,
             Default::default(),
+            Default::default(),
         )
         .unwrap();
 
@@ -692,6 +856,7 @@ fn test_verify_block_header_missing_retarget_succeeds() {
             chain_ref,
             block_height,
             Default::default(),
+            Default::default(),
         )
         .unwrap();
 
@@ -725,6 +890,7 @@ fn test_compute_new_target() {
         chain_ref,
         block_height,
         Default::default(),
+        Default::default(),
     )
     .unwrap();
 
@@ -738,6 +904,180 @@ fn test_compute_new_target() {
     assert_eq!(new_target, curr_block_header.target);
 }
 
+#[test]
+fn test_compute_new_target_decrease() {
+    run_test(|| {
+        let chain_ref: u32 = 0;
+        let block_height: u32 = 560448;
+        let retarget_headers = sample_retarget_interval_decrease();
+
+        let last_retarget_time = parse_block_header(&retarget_headers[0]).unwrap().timestamp as u64;
+        let prev_block_header = RichBlockHeader::<AccountId>::new(
+            retarget_headers[1],
+            chain_ref,
+            block_height - 1,
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+
+        let curr_block_header = parse_block_header(&retarget_headers[2]).unwrap();
+
+        BTCRelay::get_last_retarget_time
+            .mock_safe(move |_, _| MockResult::Return(Ok(last_retarget_time)));
+
+        let new_target = BTCRelay::compute_new_target(&prev_block_header, block_height).unwrap();
+
+        assert_eq!(new_target, curr_block_header.target);
+    })
+}
+
+#[test]
+fn test_round_target() {
+    run_test(|| {
+        // mainnet max target rounds to the genesis target (0x1d00ffff)
+        assert_eq!(
+            BTCRelay::round_target(crate::UNROUNDED_MAX_TARGET),
+            sample_parsed_genesis_header(0, 0).block_header.target
+        );
+        // small targets are not rounded
+        assert_eq!(
+            BTCRelay::round_target(U256::from(0x7fffff)),
+            U256::from(0x7fffff)
+        );
+        // the mantissa may not have its sign bit set
+        assert_eq!(
+            BTCRelay::round_target(U256::from(0x80ffffffu64)),
+            U256::from(0x80000000u64)
+        );
+    })
+}
+
+#[test]
+fn test_get_expected_target_mainnet_ignores_timestamp() {
+    run_test(|| {
+        set_bitcoin_network(BitcoinNetwork::Mainnet);
+        let prev_block_header = sample_parsed_first_block(0, 100);
+        let mut block_header = prev_block_header.block_header;
+        block_header.timestamp += 2 * crate::TARGET_SPACING + 1;
+
+        assert_eq!(
+            BTCRelay::get_expected_target(&prev_block_header, &block_header, 101),
+            Ok(prev_block_header.block_header.target)
+        );
+    })
+}
+
+#[test]
+fn test_get_expected_target_testnet_min_difficulty_succeeds() {
+    run_test(|| {
+        set_bitcoin_network(BitcoinNetwork::Testnet);
+        let min_difficulty_target = BTCRelay::round_target(crate::UNROUNDED_MAX_TARGET);
+
+        let mut prev_block_header = sample_parsed_first_block(0, 100);
+        prev_block_header.block_header.target = 1000.into();
+        let mut block_header = prev_block_header.block_header;
+
+        // no block for more than 20 minutes
+        block_header.timestamp += 2 * crate::TARGET_SPACING + 1;
+        assert_eq!(
+            BTCRelay::get_expected_target(&prev_block_header, &block_header, 101),
+            Ok(min_difficulty_target)
+        );
+
+        // within 20 minutes the regular target applies
+        block_header.timestamp -= 1;
+        assert_eq!(
+            BTCRelay::get_expected_target(&prev_block_header, &block_header, 101),
+            Ok(1000.into())
+        );
+    })
+}
+
+#[test]
+fn test_get_expected_target_testnet_walks_back_min_difficulty_blocks() {
+    run_test(|| {
+        set_bitcoin_network(BitcoinNetwork::Testnet);
+        let min_difficulty_target = BTCRelay::round_target(crate::UNROUNDED_MAX_TARGET);
+
+        // block 100 has a regular target, blocks 101 and 102 were mined at minimum difficulty
+        let mut headers: Vec<RichBlockHeader<AccountId>> = Vec::new();
+        for height in 100..103 {
+            let mut header = sample_parsed_first_block(0, height);
+            header.block_hash = H256Le::from_bytes_le(&[height as u8; 32]);
+            header.block_header.target = if height == 100 {
+                1000.into()
+            } else {
+                min_difficulty_target
+            };
+            if let Some(prev) = headers.last() {
+                header.block_header.hash_prev_block = prev.block_hash;
+            }
+            BTCRelay::set_block_header_from_hash(header.block_hash, &header);
+            headers.push(header);
+        }
+
+        // the last regular target is looked up without walking back the chain
+        BTCRelay::get_block_header_from_hash
+            .mock_safe(|_| panic!("Should not read previous block headers"));
+
+        let prev_block_header = headers[2];
+        let block_header = prev_block_header.block_header;
+        assert_eq!(
+            BTCRelay::get_expected_target(&prev_block_header, &block_header, 103),
+            Ok(1000.into())
+        );
+    })
+}
+
+#[test]
+fn test_get_expected_target_regtest_no_retarget() {
+    run_test(|| {
+        set_bitcoin_network(BitcoinNetwork::Regtest);
+        BTCRelay::compute_new_target.mock_safe(|_, _| panic!("Should not retarget"));
+
+        let prev_block_header = sample_parsed_first_block(0, 2015);
+        let block_header = prev_block_header.block_header;
+
+        assert_eq!(
+            BTCRelay::get_expected_target(&prev_block_header, &block_header, 2016),
+            Ok(prev_block_header.block_header.target)
+        );
+    })
+}
+
+#[test]
+fn test_get_expected_target_custom_network_retargets_at_its_interval() {
+    run_test(|| {
+        // a Litecoin-like network
+        let network = NetworkParameters {
+            mainnet: true,
+            difficulty_adjustment_interval: 100,
+            target_timespan: 302_400,
+            target_spacing: 150,
+            max_target: crate::UNROUNDED_MAX_TARGET,
+            allows_min_difficulty_blocks: false,
+            no_retargeting: false,
+            p2pkh_prefix: 0x30,
+            p2sh_prefix: 0x32,
+            bech32_hrp: b"ltc".to_vec(),
+        };
+        BTCRelay::network_parameters.mock_safe(move || MockResult::Return(network.clone()));
+        BTCRelay::compute_new_target.mock_safe(|_, _| MockResult::Return(Ok(1000.into())));
+
+        let prev_block_header = sample_parsed_first_block(0, 99);
+        let block_header = prev_block_header.block_header;
+        assert_eq!(
+            BTCRelay::get_expected_target(&prev_block_header, &block_header, 100),
+            Ok(1000.into())
+        );
+        assert_eq!(
+            BTCRelay::get_expected_target(&prev_block_header, &block_header, 101),
+            Ok(prev_block_header.block_header.target)
+        );
+    })
+}
+
 #[test]
 fn test_verify_block_header_duplicate_fails() {
     run_test(|| {
@@ -1117,6 +1457,7 @@ fn test_flag_block_error_succeeds() {
             block_height: block_height,
             chain_ref: chain_ref,
             account_id: Default::default(),
+            chain_work: Default::default(),
         };
 
         BTCRelay::set_block_header_from_hash(rich_header.block_hash, &rich_header);
@@ -1163,6 +1504,7 @@ fn test_flag_block_error_fails() {
             block_height: block_height,
             chain_ref: chain_ref,
             account_id: Default::default(),
+            chain_work: Default::default(),
         };
 
         BTCRelay::set_block_header_from_hash(rich_header.block_hash, &rich_header);
@@ -1196,6 +1538,7 @@ fn test_clear_block_error_succeeds() {
             block_height: block_height,
             chain_ref: chain_ref,
             account_id: Default::default(),
+            chain_work: Default::default(),
         };
 
         BTCRelay::set_block_header_from_hash(rich_header.block_hash, &rich_header);
@@ -1258,6 +1601,7 @@ fn test_clear_block_error_fails() {
             block_height: block_height,
             chain_ref: chain_ref,
             account_id: Default::default(),
+            chain_work: Default::default(),
         };
 
         BTCRelay::set_block_header_from_hash(rich_header.block_hash, &rich_header);
@@ -1575,12 +1919,53 @@ fn test_verify_transaction_inclusion_invalid_merkle_root_fails() {
     });
 }
 
+/// Mocks a main chain up to height 100 with a work of one per block and a
+/// fork with the given height and work
+fn mock_main_chain_and_fork(fork_height: u32, fork_work: u32) {
+    BTCRelay::get_chain_id_from_position.mock_safe(|_| MockResult::Return(Ok(1)));
+    BTCRelay::get_block_chain_from_id.mock_safe(move |chain_id| {
+        let (max_height, chain_work) = if chain_id == crate::MAIN_CHAIN_ID {
+            (100, 100)
+        } else {
+            (fork_height, fork_work)
+        };
+        MockResult::Return(Ok(BlockChain {
+            chain_id,
+            max_height,
+            chain_work: chain_work.into(),
+            ..Default::default()
+        }))
+    });
+    BTCRelay::get_best_block_height.mock_safe(|| MockResult::Return(100));
+    BTCRelay::get_block_header_from_height.mock_safe(|_, block_height| {
+        let mut block_header = sample_rich_tx_block_header(crate::MAIN_CHAIN_ID, block_height);
+        block_header.chain_work = block_height.into();
+        MockResult::Return(Ok(block_header))
+    });
+}
+
+#[test]
+fn ensure_no_ongoing_fork_compares_chain_work() {
+    run_test(|| {
+        let confirmed_height = 100 - BTCRelay::get_stable_transaction_confirmations();
+
+        // a shorter fork with more work than the confirmed main chain is ongoing
+        mock_main_chain_and_fork(50, confirmed_height + 1);
+        assert_err!(
+            BTCRelay::ensure_no_ongoing_fork(100),
+            TestError::OngoingFork
+        );
+
+        // a longer fork with less work is not
+        mock_main_chain_and_fork(150, confirmed_height);
+        assert_ok!(BTCRelay::ensure_no_ongoing_fork(100));
+    });
+}
+
 #[test]
 fn test_verify_transaction_inclusion_fails_with_ongoing_fork() {
     run_test(|| {
-        BTCRelay::get_chain_id_from_position.mock_safe(|_| MockResult::Return(Ok(1)));
-        BTCRelay::get_block_chain_from_id
-            .mock_safe(|_| MockResult::Return(Ok(BlockChain::default())));
+        mock_main_chain_and_fork(100, 100);
 
         let tx_id = sample_valid_proof_result().transaction_hash;
         let raw_merkle_proof = vec![0u8; 100];
@@ -1771,42 +2156,1755 @@ fn store_generated_block_headers() {
                 .unwrap();
             assert_ok!(BTCRelay::store_block_header(
                 Origin::signed(3),
-                get_header(&last_block)
+                get_header(&last_block),
+                u32::MAX
             ));
         }
         let main_chain: BlockChain =
             BTCRelay::get_block_chain_from_id(crate::MAIN_CHAIN_ID).unwrap();
         assert_eq!(main_chain.start_height, 0);
         assert_eq!(main_chain.max_height, 19);
+        assert_eq!(main_chain.chain_work, last_block.header.work() * 20);
+
+        let best_header = BTCRelay::get_block_header_from_hash(BTCRelay::get_best_block()).unwrap();
+        assert_eq!(best_header.chain_work, main_chain.chain_work);
     })
 }
 
+fn generated_block_header(miner: &BtcAddress, prev: Option<&Block>, height: u32) -> Block {
+    let mut builder = BlockBuilder::new();
+    builder.with_coinbase(miner, 50, height);
+    if let Some(prev) = prev {
+        builder.with_previous_hash(prev.header.hash().unwrap());
+    }
+    builder.mine(U256::from(2).pow(254.into())).unwrap()
+}
+
+fn raw_generated_block_header(block: &Block) -> RawBlockHeader {
+    RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap()
+}
+
+fn checkpoint_of(block: &Block, block_height: u32, chain_work: U256) -> Checkpoint {
+    Checkpoint {
+        block_height,
+        block_hash: block.header.hash().unwrap(),
+        chain_work,
+    }
+}
+
+/// Stores a generated main chain at heights `0..=max_height`
+fn store_generated_main_chain(miner: &BtcAddress, max_height: u32) -> Vec<Block> {
+    let mut blocks = vec![generated_block_header(miner, None, 0)];
+    assert_ok!(BTCRelay::initialize(
+        Origin::signed(3),
+        raw_generated_block_header(&blocks[0]),
+        0
+    ));
+    for height in 1..=max_height {
+        let block = generated_block_header(miner, blocks.last(), height);
+        assert_ok!(BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&block),
+            u32::MAX
+        ));
+        blocks.push(block);
+    }
+    blocks
+}
+
+fn sample_miner() -> BtcAddress {
+    BtcAddress::P2PKH(H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap())
+}
+
+fn sample_fork_miner() -> BtcAddress {
+    BtcAddress::P2PKH(H160::from_str(&"5587090c3288b46df8cc928c6910a8c1bbea508f").unwrap())
+}
+
 #[test]
-fn test_extract_value_fails_with_wrong_recipient() {
+fn initialize_from_checkpoint_uses_trusted_chain_work() {
     run_test(|| {
-        let recipient_btc_address_0 = BtcAddress::P2SH(H160([0; 20]));
-        let recipient_btc_address_1 = BtcAddress::P2SH(H160([1; 20]));
+        let block = generated_block_header(&sample_miner(), None, 100);
+        let checkpoint = checkpoint_of(&block, 100, 12345.into());
+        assert_ok!(BTCRelay::set_checkpoint(Origin::root(), checkpoint));
 
-        let transaction = TransactionBuilder::new()
-            .with_version(2)
-            .add_output(TransactionOutput::payment(32, &recipient_btc_address_0))
-            .build();
+        assert_ok!(BTCRelay::initialize(
+            Origin::signed(3),
+            raw_generated_block_header(&block),
+            100
+        ));
+
+        let main_chain = BTCRelay::get_block_chain_from_id(crate::MAIN_CHAIN_ID).unwrap();
+        assert_eq!(main_chain.chain_work, checkpoint.chain_work);
+        let best_header = BTCRelay::get_block_header_from_hash(BTCRelay::get_best_block()).unwrap();
+        assert_eq!(best_header.chain_work, checkpoint.chain_work);
+    })
+}
+
+#[test]
+fn initialize_without_checkpoint_fails() {
+    run_test(|| {
+        let block = generated_block_header(&sample_miner(), None, 100);
+        assert_ok!(BTCRelay::set_checkpoint(
+            Origin::root(),
+            checkpoint_of(&block, 100, 12345.into())
+        ));
 
         assert_err!(
-            BTCRelay::extract_payment_value(transaction, recipient_btc_address_1),
-            TestError::WrongRecipient
+            BTCRelay::initialize(Origin::signed(3), raw_generated_block_header(&block), 101),
+            TestError::NoCheckpoint
         );
     })
 }
 
 #[test]
-fn test_extract_value_succeeds() {
+fn initialize_conflicting_checkpoint_fails() {
     run_test(|| {
-        let recipient_btc_address = BtcAddress::P2SH(H160([0; 20]));
-        let recipient_value = 64;
+        let block = generated_block_header(&sample_miner(), None, 100);
+        let other_block = generated_block_header(&sample_fork_miner(), None, 100);
+        assert_ok!(BTCRelay::set_checkpoint(
+            Origin::root(),
+            checkpoint_of(&block, 100, 12345.into())
+        ));
 
-        let transaction = TransactionBuilder::new()
-            .with_version(2)
+        assert_err!(
+            BTCRelay::initialize(
+                Origin::signed(3),
+                raw_generated_block_header(&other_block),
+                100
+            ),
+            TestError::CheckpointConflict
+        );
+    })
+}
+
+#[test]
+fn set_checkpoint_succeeds() {
+    use frame_support::dispatch::DispatchError;
+
+    run_test(|| {
+        let block = generated_block_header(&sample_miner(), None, 100);
+        let checkpoint = checkpoint_of(&block, 100, 12345.into());
+
+        assert_err!(
+            BTCRelay::set_checkpoint(Origin::signed(3), checkpoint),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(BTCRelay::set_checkpoint(Origin::root(), checkpoint));
+        assert_eq!(BTCRelay::checkpoint(100), Some(checkpoint));
+        let set_event = TestEvent::btc_relay(Event::SetCheckpoint(100, checkpoint.block_hash));
+        assert!(System::events().iter().any(|a| a.event == set_event));
+
+        // a checkpoint at the same height is replaced
+        let other_checkpoint = Checkpoint {
+            chain_work: 1.into(),
+            ..checkpoint
+        };
+        assert_ok!(BTCRelay::set_checkpoint(Origin::root(), other_checkpoint));
+        assert_eq!(BTCRelay::checkpoint(100), Some(other_checkpoint));
+
+        assert_ok!(BTCRelay::remove_checkpoint(Origin::root(), 100));
+        assert_eq!(BTCRelay::checkpoint(100), None);
+        let remove_event = TestEvent::btc_relay(Event::RemoveCheckpoint(100));
+        assert!(System::events().iter().any(|a| a.event == remove_event));
+    })
+}
+
+#[test]
+fn set_verification_profile_succeeds() {
+    use frame_support::dispatch::DispatchError;
+
+    run_test(|| {
+        let verification_profile = VerificationProfile {
+            difficulty_check: false,
+            op_return_check: false,
+            ..Default::default()
+        };
+        assert_err!(
+            BTCRelay::set_verification_profile(Origin::signed(3), verification_profile),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(BTCRelay::set_verification_profile(
+            Origin::root(),
+            verification_profile
+        ));
+        assert_eq!(BTCRelay::verification_profile(), verification_profile);
+        assert!(BTCRelay::is_op_return_disabled());
+        let set_event = TestEvent::btc_relay(Event::SetVerificationProfile(verification_profile));
+        assert!(System::events().iter().any(|a| a.event == set_event));
+    })
+}
+
+#[test]
+fn set_verification_profile_fails_without_inclusion_check_on_mainnet() {
+    run_test(|| {
+        let verification_profile = VerificationProfile {
+            inclusion_check: false,
+            ..Default::default()
+        };
+        assert_err!(
+            BTCRelay::set_verification_profile(Origin::root(), verification_profile),
+            TestError::InclusionCheckRequired
+        );
+        assert_eq!(
+            BTCRelay::verification_profile(),
+            VerificationProfile::default()
+        );
+
+        // inclusion checks may be disabled on other networks
+        BTCRelay::is_mainnet.mock_safe(|| MockResult::Return(false));
+        assert_ok!(BTCRelay::set_verification_profile(
+            Origin::root(),
+            verification_profile
+        ));
+        assert_eq!(BTCRelay::verification_profile(), verification_profile);
+    })
+}
+
+#[test]
+fn is_mainnet_checks_the_network_parameters() {
+    run_test(|| {
+        assert!(BTCRelay::is_mainnet());
+        assert!(!BTCRelayTestnet::is_mainnet());
+    })
+}
+
+#[test]
+#[should_panic(expected = "InclusionCheckRequired")]
+fn genesis_without_inclusion_check_fails_on_mainnet() {
+    crate::GenesisConfig::<Test> {
+        verification_profile: VerificationProfile {
+            inclusion_check: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .build_storage()
+    .unwrap();
+}
+
+#[test]
+fn set_checkpoint_conflicting_main_chain_fails() {
+    run_test(|| {
+        let miner = sample_miner();
+        let blocks = store_generated_main_chain(&miner, 3);
+        let other_block = generated_block_header(&sample_fork_miner(), Some(&blocks[1]), 2);
+
+        assert_err!(
+            BTCRelay::set_checkpoint(Origin::root(), checkpoint_of(&other_block, 2, 0.into())),
+            TestError::CheckpointConflict
+        );
+        assert_ok!(BTCRelay::set_checkpoint(
+            Origin::root(),
+            checkpoint_of(&blocks[2], 2, 0.into())
+        ));
+    })
+}
+
+#[test]
+fn store_block_header_conflicting_checkpoint_fails() {
+    run_test(|| {
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 2);
+
+        // checkpoint above the current tip
+        let checkpoint_block = generated_block_header(&miner, blocks.last(), 3);
+        assert_ok!(BTCRelay::set_checkpoint(
+            Origin::root(),
+            checkpoint_of(&checkpoint_block, 3, 0.into())
+        ));
+
+        let other_block = generated_block_header(&fork_miner, blocks.last(), 3);
+        assert_err!(
+            BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&other_block),
+                u32::MAX
+            ),
+            TestError::CheckpointConflict
+        );
+        assert_ok!(BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&checkpoint_block),
+            u32::MAX
+        ));
+    })
+}
+
+#[test]
+fn check_and_do_reorg_fork_below_checkpoint_fails() {
+    run_test(|| {
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 5);
+        assert_ok!(BTCRelay::set_checkpoint(
+            Origin::root(),
+            checkpoint_of(&blocks[3], 3, 0.into())
+        ));
+
+        // a new fork branching off below the checkpoint is rejected
+        let fork_block = generated_block_header(&fork_miner, Some(&blocks[2]), 3);
+        assert_err!(
+            BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&fork_block),
+                u32::MAX
+            ),
+            TestError::CheckpointConflict
+        );
+
+        // an existing fork branching off below the checkpoint can no longer be extended
+        let fork_block = generated_block_header(&fork_miner, Some(&blocks[1]), 2);
+        assert_ok!(BTCRelay::remove_checkpoint(Origin::root(), 3));
+        assert_ok!(BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&fork_block),
+            u32::MAX
+        ));
+        assert_ok!(BTCRelay::set_checkpoint(
+            Origin::root(),
+            checkpoint_of(&blocks[3], 3, 0.into())
+        ));
+        let fork_block = generated_block_header(&fork_miner, Some(&fork_block), 3);
+        assert_err!(
+            BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&fork_block),
+                u32::MAX
+            ),
+            TestError::CheckpointConflict
+        );
+    })
+}
+
+#[test]
+fn check_and_do_reorg_fork_above_checkpoint_succeeds() {
+    run_test(|| {
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 5);
+        assert_ok!(BTCRelay::set_checkpoint(
+            Origin::root(),
+            checkpoint_of(&blocks[3], 3, 0.into())
+        ));
+
+        // fork branching off the checkpoint, long enough to become the main chain
+        let mut fork_block = blocks[3].clone();
+        for height in 4..=(5 + BITCOIN_CONFIRMATIONS + 1) {
+            fork_block = generated_block_header(&fork_miner, Some(&fork_block), height);
+            assert_ok!(BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&fork_block),
+                u32::MAX
+            ));
+        }
+
+        assert_eq!(
+            BTCRelay::get_best_block(),
+            fork_block.header.hash().unwrap()
+        );
+        assert_eq!(
+            BTCRelay::get_best_block_height(),
+            5 + BITCOIN_CONFIRMATIONS + 1
+        );
+        assert_eq!(
+            BTCRelay::get_block_hash(crate::MAIN_CHAIN_ID, 3),
+            Ok(blocks[3].header.hash().unwrap())
+        );
+    })
+}
+
+#[test]
+fn store_block_header_refunds_reorg_weight() {
+    run_test(|| {
+        let miner = sample_miner();
+        let blocks = store_generated_main_chain(&miner, 1);
+
+        let block = generated_block_header(&miner, blocks.last(), 2);
+        let post_info = BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&block),
+            u32::MAX,
+        )
+        .unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<() as WeightInfo>::store_block_header())
+        );
+    })
+}
+
+#[test]
+fn store_block_headers_stores_valid_prefix() {
+    run_test(|| {
+        let miner = sample_miner();
+        let blocks = store_generated_main_chain(&miner, 1);
+
+        let block_2 = generated_block_header(&miner, blocks.last(), 2);
+        let block_3 = generated_block_header(&miner, Some(&block_2), 3);
+        let orphan_block = generated_block_header(&sample_fork_miner(), None, 10);
+        let block_4 = generated_block_header(&miner, Some(&block_3), 4);
+
+        assert_ok!(BTCRelay::store_block_headers(
+            Origin::signed(3),
+            vec![&block_2, &block_3, &orphan_block, &block_4]
+                .into_iter()
+                .map(raw_generated_block_header)
+                .collect(),
+            u32::MAX
+        ));
+
+        assert_eq!(BTCRelay::get_best_block(), block_3.header.hash().unwrap());
+        assert!(!BTCRelay::block_header_exists(
+            block_4.header.hash().unwrap()
+        ));
+
+        let store_event = TestEvent::btc_relay(Event::StoreBlockHeaders(vec![
+            (block_2.header.hash().unwrap(), BlockHeaderResult::Stored),
+            (block_3.header.hash().unwrap(), BlockHeaderResult::Stored),
+            (
+                orphan_block.header.hash().unwrap(),
+                BlockHeaderResult::Rejected(TestError::BlockNotFound.into()),
+            ),
+        ]));
+        assert!(System::events().iter().any(|a| a.event == store_event));
+    })
+}
+
+#[test]
+fn store_block_headers_skips_duplicates() {
+    run_test(|| {
+        let miner = sample_miner();
+        let blocks = store_generated_main_chain(&miner, 1);
+        let block = generated_block_header(&miner, blocks.last(), 2);
+
+        assert_ok!(BTCRelay::store_block_headers(
+            Origin::signed(3),
+            vec![
+                raw_generated_block_header(&blocks[1]),
+                raw_generated_block_header(&block)
+            ],
+            u32::MAX
+        ));
+
+        assert_eq!(BTCRelay::get_best_block(), block.header.hash().unwrap());
+        let store_event = TestEvent::btc_relay(Event::StoreBlockHeaders(vec![
+            (
+                blocks[1].header.hash().unwrap(),
+                BlockHeaderResult::Duplicate,
+            ),
+            (block.header.hash().unwrap(), BlockHeaderResult::Stored),
+        ]));
+        assert!(System::events().iter().any(|a| a.event == store_event));
+    })
+}
+
+#[test]
+fn store_block_headers_without_valid_header_fails() {
+    run_test(|| {
+        store_generated_main_chain(&sample_miner(), 1);
+        let orphan_block = generated_block_header(&sample_fork_miner(), None, 10);
+
+        assert_err!(
+            BTCRelay::store_block_headers(
+                Origin::signed(3),
+                vec![raw_generated_block_header(&orphan_block)],
+                u32::MAX
+            ),
+            TestError::BlockNotFound
+        );
+    })
+}
+
+#[test]
+fn store_block_headers_processes_single_reorg() {
+    run_test(|| {
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 2);
+
+        // fork long enough to replace the two main chain blocks above the genesis
+        let mut fork_blocks = vec![blocks[0].clone()];
+        for height in 1..=(2 + BITCOIN_CONFIRMATIONS + 2) {
+            let block = generated_block_header(&fork_miner, fork_blocks.last(), height);
+            fork_blocks.push(block);
+        }
+        // extends the replaced main chain
+        let block = generated_block_header(&miner, blocks.last(), 3);
+
+        let mut raw_block_headers: Vec<RawBlockHeader> = fork_blocks
+            .iter()
+            .skip(1)
+            .map(raw_generated_block_header)
+            .collect();
+        raw_block_headers.push(raw_generated_block_header(&block));
+        let post_info =
+            BTCRelay::store_block_headers(Origin::signed(3), raw_block_headers, u32::MAX).unwrap();
+
+        assert_eq!(
+            BTCRelay::get_best_block(),
+            fork_blocks.last().unwrap().header.hash().unwrap()
+        );
+        assert!(!BTCRelay::block_header_exists(block.header.hash().unwrap()));
+        assert_eq!(
+            post_info.actual_weight,
+            Some(
+                <() as WeightInfo>::store_block_header()
+                    .saturating_mul(fork_blocks.len() as u64 - 1)
+                    .saturating_add(<() as WeightInfo>::swap_main_blockchain(2))
+            )
+        );
+    })
+}
+
+/// Mocks the fee module and returns the rewarded relayers
+fn mock_header_submission_rewards() -> Rc<RefCell<Vec<AccountId>>> {
+    let rewarded = Rc::new(RefCell::new(Vec::new()));
+    let recorded = rewarded.clone();
+    ext::fee::reward_header_submission::<Test>.mock_safe(move |relayer| {
+        recorded.borrow_mut().push(*relayer);
+        MockResult::Return(Ok(()))
+    });
+    rewarded
+}
+
+#[test]
+fn store_block_header_by_authorized_relayer_is_free_and_rewarded() {
+    run_test(|| {
+        let rewarded = mock_header_submission_rewards();
+        let miner = sample_miner();
+        let blocks = store_generated_main_chain(&miner, 1);
+        BTCRelay::register_authorized_relayer(3);
+
+        let block = generated_block_header(&miner, blocks.last(), 2);
+        let post_info = BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&block),
+            u32::MAX,
+        )
+        .unwrap();
+
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(*rewarded.borrow(), vec![3]);
+    })
+}
+
+#[test]
+fn store_block_header_duplicate_pays_fee() {
+    run_test(|| {
+        let rewarded = mock_header_submission_rewards();
+        let blocks = store_generated_main_chain(&sample_miner(), 1);
+        BTCRelay::register_authorized_relayer(3);
+
+        let err = BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&blocks[1]),
+            u32::MAX,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.error, TestError::DuplicateBlock.into());
+        assert_eq!(err.post_info.pays_fee, Pays::Yes);
+        assert!(rewarded.borrow().is_empty());
+    })
+}
+
+#[test]
+fn store_block_header_fork_pays_fee() {
+    run_test(|| {
+        let rewarded = mock_header_submission_rewards();
+        let blocks = store_generated_main_chain(&sample_miner(), 1);
+        BTCRelay::register_authorized_relayer(3);
+
+        let block = generated_block_header(&sample_fork_miner(), Some(&blocks[0]), 1);
+        let post_info = BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&block),
+            u32::MAX,
+        )
+        .unwrap();
+
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+        assert!(rewarded.borrow().is_empty());
+    })
+}
+
+#[test]
+fn store_block_header_by_unauthorized_relayer_pays_fee() {
+    run_test(|| {
+        let rewarded = mock_header_submission_rewards();
+        let miner = sample_miner();
+        let blocks = store_generated_main_chain(&miner, 1);
+
+        // relayer authorization is disabled in the mock
+        let block = generated_block_header(&miner, blocks.last(), 2);
+        let post_info = BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&block),
+            u32::MAX,
+        )
+        .unwrap();
+
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+        assert!(rewarded.borrow().is_empty());
+    })
+}
+
+#[test]
+fn store_block_header_reward_failure_does_not_fail_submission() {
+    run_test(|| {
+        ext::fee::reward_header_submission::<Test>
+            .mock_safe(|_| MockResult::Return(Err(TestError::DuplicateBlock.into())));
+        let miner = sample_miner();
+        let blocks = store_generated_main_chain(&miner, 1);
+        BTCRelay::register_authorized_relayer(3);
+
+        let block = generated_block_header(&miner, blocks.last(), 2);
+        let post_info = BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&block),
+            u32::MAX,
+        )
+        .unwrap();
+
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(BTCRelay::get_best_block(), block.header.hash().unwrap());
+    })
+}
+
+#[test]
+fn store_block_headers_skipped_duplicates_do_not_pay_fee() {
+    run_test(|| {
+        let rewarded = mock_header_submission_rewards();
+        let miner = sample_miner();
+        let mut blocks = store_generated_main_chain(&miner, 1);
+        BTCRelay::register_authorized_relayer(3);
+        for height in 2..=3 {
+            let block = generated_block_header(&miner, blocks.last(), height);
+            blocks.push(block);
+        }
+
+        let post_info = BTCRelay::store_block_headers(
+            Origin::signed(3),
+            vec![raw_generated_block_header(&blocks[2])],
+            u32::MAX,
+        )
+        .unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+
+        let post_info = BTCRelay::store_block_headers(
+            Origin::signed(3),
+            blocks[2..].iter().map(raw_generated_block_header).collect(),
+            u32::MAX,
+        )
+        .unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(*rewarded.borrow(), vec![3, 3]);
+
+        // a batch of duplicates only pays
+        let post_info = BTCRelay::store_block_headers(
+            Origin::signed(3),
+            blocks[2..].iter().map(raw_generated_block_header).collect(),
+            u32::MAX,
+        )
+        .unwrap();
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+        assert_eq!(*rewarded.borrow(), vec![3, 3]);
+    })
+}
+
+const BITCOIND_URL: &str = "http://127.0.0.1:8332";
+
+fn new_offchain_test_ext(
+    offchain: TestOffchainExt,
+    pool: TestTransactionPoolExt,
+) -> sp_io::TestExternalities {
+    clear_mocks();
+    UintAuthorityId::set_all_keys(vec![3]);
+    let mut ext = ExtBuilder::build();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+fn set_bitcoind_config() {
+    sp_io::offchain::local_storage_set(
+        StorageKind::PERSISTENT,
+        b"BTCRelay::bitcoind-url",
+        BITCOIND_URL.as_bytes(),
+    );
+    sp_io::offchain::local_storage_set(
+        StorageKind::PERSISTENT,
+        b"BTCRelay::bitcoind-credentials",
+        b"user:pass",
+    );
+}
+
+fn bitcoind_request(method: &str, params: &str, result: &str) -> PendingRequest {
+    PendingRequest {
+        method: "POST".into(),
+        uri: BITCOIND_URL.into(),
+        headers: vec![
+            ("Content-Type".into(), "application/json".into()),
+            ("Authorization".into(), "Basic dXNlcjpwYXNz".into()),
+        ],
+        body: format!(
+            r#"{{"jsonrpc":"1.0","id":"btc-relay","method":"{}","params":{}}}"#,
+            method, params
+        )
+        .into_bytes(),
+        response: Some(
+            format!(r#"{{"result":{},"error":null,"id":"btc-relay"}}"#, result).into_bytes(),
+        ),
+        sent: true,
+        ..Default::default()
+    }
+}
+
+fn get_block_count_request(block_count: u32) -> PendingRequest {
+    bitcoind_request("getblockcount", "[]", &block_count.to_string())
+}
+
+fn get_block_hash_request(height: u32, block: &Block) -> PendingRequest {
+    bitcoind_request(
+        "getblockhash",
+        &format!("[{}]", height),
+        &format!("\"{}\"", block.header.hash().unwrap().to_hex_be()),
+    )
+}
+
+fn get_block_header_request(block: &Block) -> PendingRequest {
+    bitcoind_request(
+        "getblockheader",
+        &format!("[\"{}\",false]", block.header.hash().unwrap().to_hex_be()),
+        &format!(
+            "\"{}\"",
+            hex::encode(raw_generated_block_header(block).as_bytes())
+        ),
+    )
+}
+
+fn submitted_calls(transactions: &[Vec<u8>]) -> Vec<Call> {
+    transactions
+        .iter()
+        .map(|tx| {
+            let tx = Extrinsic::decode(&mut &tx[..]).unwrap();
+            assert_eq!(tx.signature, Some((0, ())));
+            tx.call
+        })
+        .collect()
+}
+
+#[test]
+fn offchain_worker_relays_block_headers() {
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_offchain_test_ext(offchain, pool).execute_with(|| {
+        let miner = sample_miner();
+        let mut blocks = store_generated_main_chain(&miner, 2);
+        for height in 3..=4 {
+            let block = generated_block_header(&miner, blocks.last(), height);
+            blocks.push(block);
+        }
+        set_bitcoind_config();
+
+        {
+            let mut state = offchain_state.write();
+            state.expect_request(get_block_count_request(4));
+            state.expect_request(get_block_hash_request(2, &blocks[2]));
+            for height in 3..=4 {
+                state.expect_request(get_block_hash_request(height, &blocks[height as usize]));
+                state.expect_request(get_block_header_request(&blocks[height as usize]));
+            }
+        }
+
+        assert_eq!(BTCRelay::relay_block_headers(1), Ok(()));
+        assert_eq!(
+            submitted_calls(&pool_state.read().transactions),
+            vec![Call::BTCRelay(crate::Call::store_block_headers(
+                vec![
+                    raw_generated_block_header(&blocks[3]),
+                    raw_generated_block_header(&blocks[4]),
+                ],
+                u32::MAX
+            ))]
+        );
+    })
+}
+
+#[test]
+fn offchain_worker_relays_block_headers_from_common_ancestor() {
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_offchain_test_ext(offchain, pool).execute_with(|| {
+        let blocks = store_generated_main_chain(&sample_miner(), 3);
+        set_bitcoind_config();
+
+        // bitcoind switched to a fork diverging after height 1
+        let mut fork_blocks = vec![blocks[0].clone(), blocks[1].clone()];
+        for height in 2..=4 {
+            let block = generated_block_header(&sample_fork_miner(), fork_blocks.last(), height);
+            fork_blocks.push(block);
+        }
+
+        {
+            let mut state = offchain_state.write();
+            state.expect_request(get_block_count_request(4));
+            for height in (1..=3).rev() {
+                state.expect_request(get_block_hash_request(
+                    height,
+                    &fork_blocks[height as usize],
+                ));
+            }
+            for height in 2..=4 {
+                state.expect_request(get_block_hash_request(
+                    height,
+                    &fork_blocks[height as usize],
+                ));
+                state.expect_request(get_block_header_request(&fork_blocks[height as usize]));
+            }
+        }
+
+        assert_eq!(BTCRelay::relay_block_headers(1), Ok(()));
+        assert_eq!(
+            submitted_calls(&pool_state.read().transactions),
+            vec![Call::BTCRelay(crate::Call::store_block_headers(
+                fork_blocks[2..]
+                    .iter()
+                    .map(raw_generated_block_header)
+                    .collect(),
+                u32::MAX
+            ))]
+        );
+    })
+}
+
+#[test]
+fn offchain_worker_without_bitcoind_url_does_nothing() {
+    let (offchain, _) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_offchain_test_ext(offchain, pool).execute_with(|| {
+        store_generated_main_chain(&sample_miner(), 2);
+
+        assert_eq!(BTCRelay::relay_block_headers(1), Ok(()));
+        assert!(pool_state.read().transactions.is_empty());
+    })
+}
+
+#[test]
+fn offchain_worker_waits_for_pending_submission() {
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_offchain_test_ext(offchain, pool).execute_with(|| {
+        let miner = sample_miner();
+        let mut blocks = store_generated_main_chain(&miner, 2);
+        blocks.push(generated_block_header(&miner, blocks.last(), 3));
+        set_bitcoind_config();
+
+        {
+            let mut state = offchain_state.write();
+            state.expect_request(get_block_count_request(3));
+            state.expect_request(get_block_hash_request(2, &blocks[2]));
+            state.expect_request(get_block_hash_request(3, &blocks[3]));
+            state.expect_request(get_block_header_request(&blocks[3]));
+        }
+        assert_eq!(BTCRelay::relay_block_headers(1), Ok(()));
+
+        // the best block did not change, so bitcoind is not queried again
+        assert_eq!(
+            BTCRelay::relay_block_headers(1 + crate::OFFCHAIN_RESUBMISSION_DELAY as u64 - 1),
+            Ok(())
+        );
+        assert_eq!(pool_state.read().transactions.len(), 1);
+    })
+}
+
+#[test]
+fn offchain_worker_skips_while_locked() {
+    use sp_runtime::offchain::storage_lock::{StorageLock, Time};
+
+    let (offchain, _) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_offchain_test_ext(offchain, pool).execute_with(|| {
+        store_generated_main_chain(&sample_miner(), 2);
+        set_bitcoind_config();
+
+        // another worker is submitting, so bitcoind is not queried
+        let mut lock = StorageLock::<Time>::new(b"BTCRelay::lock");
+        let _guard = lock.lock();
+
+        assert_eq!(BTCRelay::relay_block_headers(1), Ok(()));
+        assert!(pool_state.read().transactions.is_empty());
+    })
+}
+
+#[test]
+fn offchain_worker_without_local_key_fails() {
+    let (offchain, _) = TestOffchainExt::new();
+    let (pool, _) = TestTransactionPoolExt::new();
+    new_offchain_test_ext(offchain, pool).execute_with(|| {
+        store_generated_main_chain(&sample_miner(), 2);
+        set_bitcoind_config();
+        UintAuthorityId::set_all_keys(Vec::<u64>::new());
+
+        assert_eq!(
+            BTCRelay::relay_block_headers(1),
+            Err(crate::OffchainError::NoLocalKey)
+        );
+    })
+}
+
+#[test]
+fn swap_main_blockchain_notifies_reorged_requests() {
+    run_test(|| {
+        use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageMap;
+        use crate::OnReorg;
+
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 2);
+        let reorged_block_hash = blocks[2].header.hash().unwrap();
+        let consumer = TransactionConsumer::Redeem(H256::from_low_u64_be(1));
+        <crate::BlockConsumers>::append(reorged_block_hash, consumer);
+
+        let notifications = Rc::new(RefCell::new(Vec::new()));
+        let recorded = notifications.clone();
+        <() as OnReorg>::on_reorg.mock_safe(move |consumer, block_hash| {
+            recorded.borrow_mut().push((consumer, block_hash));
+            MockResult::Return(())
+        });
+
+        let mut fork_block = blocks[0].clone();
+        for height in 1..=(2 + BITCOIN_CONFIRMATIONS + 1) {
+            fork_block = generated_block_header(&fork_miner, Some(&fork_block), height);
+            assert_ok!(BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&fork_block),
+                u32::MAX
+            ));
+        }
+
+        let reorg_event = TestEvent::btc_relay(Event::ChainReorg(
+            reorged_block_hash,
+            fork_block.header.hash().unwrap(),
+            2,
+        ));
+        assert!(System::events().iter().any(|a| a.event == reorg_event));
+        assert_eq!(
+            *notifications.borrow(),
+            vec![(consumer, reorged_block_hash)]
+        );
+    })
+}
+
+#[test]
+fn swap_main_blockchain_keeps_fulfilling_transactions_of_reorged_requests() {
+    run_test(|| {
+        use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageMap;
+
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 2);
+        let reorged_block_hash = blocks[2].header.hash().unwrap();
+        let stable_block_hash = blocks[0].header.hash().unwrap();
+        let reorged_consumer = TransactionConsumer::Redeem(H256::from_low_u64_be(1));
+        let stable_consumer = TransactionConsumer::Redeem(H256::from_low_u64_be(2));
+        <crate::BlockConsumers>::append(reorged_block_hash, reorged_consumer);
+        <crate::BlockConsumers>::append(stable_block_hash, stable_consumer);
+        <crate::FulfillingTransactions>::insert(reorged_consumer, H256Le::from_bytes_le(&[1; 32]));
+        <crate::FulfillingTransactions>::insert(stable_consumer, H256Le::from_bytes_le(&[2; 32]));
+
+        let mut fork_block = blocks[0].clone();
+        for height in 1..=(2 + BITCOIN_CONFIRMATIONS + 1) {
+            fork_block = generated_block_header(&fork_miner, Some(&fork_block), height);
+            assert_ok!(BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&fork_block),
+                u32::MAX
+            ));
+        }
+
+        // the requests stay executed by their transactions
+        assert_eq!(
+            BTCRelay::get_fulfilling_transaction(reorged_consumer),
+            Some(H256Le::from_bytes_le(&[1; 32]))
+        );
+        assert_eq!(
+            BTCRelay::get_fulfilling_transaction(stable_consumer),
+            Some(H256Le::from_bytes_le(&[2; 32]))
+        );
+    })
+}
+
+#[test]
+fn invalidate_block_promotes_fork_at_tip() {
+    run_test(|| {
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 3);
+        let fork_block = generated_block_header(&fork_miner, Some(&blocks[1]), 2);
+        assert_ok!(BTCRelay::store_block_header(
+            Origin::signed(3),
+            raw_generated_block_header(&fork_block),
+            u32::MAX
+        ));
+
+        let invalid_block_hash = blocks[2].header.hash().unwrap();
+        assert_ok!(BTCRelay::invalidate_block(
+            Origin::root(),
+            invalid_block_hash,
+            u32::MAX,
+            u32::MAX
+        ));
+
+        assert_eq!(
+            BTCRelay::get_best_block(),
+            fork_block.header.hash().unwrap()
+        );
+        assert_eq!(BTCRelay::get_best_block_height(), 2);
+        assert_eq!(BTCRelay::get_chains().len(), 1);
+        assert!(!BTCRelay::block_header_exists(invalid_block_hash));
+        assert!(!BTCRelay::block_header_exists(
+            blocks[3].header.hash().unwrap()
+        ));
+
+        let invalidate_event = TestEvent::btc_relay(Event::InvalidateBlock(invalid_block_hash, 2));
+        assert!(System::events().iter().any(|a| a.event == invalidate_event));
+        let reorg_event = TestEvent::btc_relay(Event::ChainReorg(
+            blocks[3].header.hash().unwrap(),
+            fork_block.header.hash().unwrap(),
+            2,
+        ));
+        assert!(System::events().iter().any(|a| a.event == reorg_event));
+
+        // the invalid block and its descendants can not be stored again
+        assert_err!(
+            BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&blocks[2]),
+                u32::MAX
+            ),
+            TestError::InvalidatedBlock
+        );
+        assert_err!(
+            BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&blocks[3]),
+                u32::MAX
+            ),
+            TestError::InvalidatedBlock
+        );
+        let child_block = generated_block_header(&fork_miner, Some(&blocks[2]), 3);
+        assert_err!(
+            BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&child_block),
+                u32::MAX
+            ),
+            TestError::InvalidatedBlock
+        );
+    })
+}
+
+#[test]
+fn invalidate_block_swaps_main_chain_with_heavier_fork() {
+    run_test(|| {
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 4);
+
+        // fork branching off below the invalid block with more work than the remaining main chain
+        let mut fork_block = blocks[1].clone();
+        for height in 2..=3 {
+            fork_block = generated_block_header(&fork_miner, Some(&fork_block), height);
+            assert_ok!(BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&fork_block),
+                u32::MAX
+            ));
+        }
+
+        assert_ok!(BTCRelay::invalidate_block(
+            Origin::root(),
+            blocks[3].header.hash().unwrap(),
+            u32::MAX,
+            u32::MAX
+        ));
+
+        assert_eq!(
+            BTCRelay::get_best_block(),
+            fork_block.header.hash().unwrap()
+        );
+        assert_eq!(BTCRelay::get_best_block_height(), 3);
+        assert_eq!(
+            BTCRelay::get_block_hash(crate::MAIN_CHAIN_ID, 2),
+            Ok(fork_block.header.hash_prev_block)
+        );
+        // the valid main chain block is kept on a fork
+        let chains = BTCRelay::get_chains();
+        assert_eq!(chains.len(), 2);
+        assert_eq!(
+            BTCRelay::get_block_header_from_hash(blocks[2].header.hash().unwrap())
+                .unwrap()
+                .chain_ref,
+            chains[1].chain_id
+        );
+    })
+}
+
+#[test]
+fn invalidate_block_on_fork_keeps_main_chain() {
+    run_test(|| {
+        let miner = sample_miner();
+        let fork_miner = sample_fork_miner();
+        let blocks = store_generated_main_chain(&miner, 3);
+        let fork_block = generated_block_header(&fork_miner, Some(&blocks[1]), 2);
+        let fork_child_block = generated_block_header(&fork_miner, Some(&fork_block), 3);
+        for block in [&fork_block, &fork_child_block].iter() {
+            assert_ok!(BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(block),
+                u32::MAX
+            ));
+        }
+
+        assert_ok!(BTCRelay::invalidate_block(
+            Origin::root(),
+            fork_block.header.hash().unwrap(),
+            u32::MAX,
+            u32::MAX
+        ));
+
+        assert_eq!(BTCRelay::get_best_block(), blocks[3].header.hash().unwrap());
+        assert_eq!(BTCRelay::get_chains().len(), 1);
+        assert!(!BTCRelay::block_header_exists(
+            fork_child_block.header.hash().unwrap()
+        ));
+    })
+}
+
+#[test]
+fn invalidate_block_fails() {
+    run_test(|| {
+        use frame_support::dispatch::DispatchError;
+
+        let blocks = store_generated_main_chain(&sample_miner(), 3);
+
+        assert_err!(
+            BTCRelay::invalidate_block(
+                Origin::signed(3),
+                blocks[2].header.hash().unwrap(),
+                u32::MAX,
+                u32::MAX
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_err!(
+            BTCRelay::invalidate_block(
+                Origin::root(),
+                blocks[0].header.hash().unwrap(),
+                u32::MAX,
+                u32::MAX
+            ),
+            TestError::InitialBlockInvalidation
+        );
+        // the blocks at heights 2 and 3 are removed
+        assert_err!(
+            BTCRelay::invalidate_block(Origin::root(), blocks[2].header.hash().unwrap(), 1, 1),
+            TestError::InvalidationBoundExceeded
+        );
+
+        assert_ok!(BTCRelay::set_checkpoint(
+            Origin::root(),
+            checkpoint_of(&blocks[3], 3, 0.into())
+        ));
+        assert_err!(
+            BTCRelay::invalidate_block(
+                Origin::root(),
+                blocks[2].header.hash().unwrap(),
+                u32::MAX,
+                u32::MAX
+            ),
+            TestError::CheckpointConflict
+        );
+    })
+}
+
+fn set_pruning_parameters(pruning_depth: u32, max_fork_depth: u32, max_pruned_headers: u32) {
+    use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageValue;
+    <crate::PruningDepth>::put(pruning_depth);
+    <crate::MaxForkDepth>::put(max_fork_depth);
+    <crate::MaxPrunedHeadersPerBlock>::put(max_pruned_headers);
+}
+
+#[test]
+fn prune_main_chain_succeeds() {
+    run_test(|| {
+        let main = store_blockchain_and_random_headers(0, 4000, 4100, 0);
+        BTCRelay::set_best_block(BTCRelay::get_block_hash(0, 4100).unwrap());
+        BTCRelay::set_best_block_height(main.max_height);
+        set_pruning_parameters(50, 0, 10);
+        let kept_header = BTCRelay::get_block_header_from_height(&main, 4010)
+            .unwrap()
+            .block_header;
+        let mut pruned_header = BTCRelay::get_block_header_from_height(&main, 4000)
+            .unwrap()
+            .block_header;
+        pruned_header.timestamp -= 1;
+        assert!(!BTCRelay::is_block_header_pruned(&pruned_header));
+
+        // work per block is bounded
+        BTCRelay::prune_block_headers();
+        assert_eq!(BTCRelay::pruned_height(), 4010);
+        // unknown headers older than the lowest remaining header were pruned
+        assert!(BTCRelay::is_block_header_pruned(&pruned_header));
+        assert!(!BTCRelay::is_block_header_pruned(&kept_header));
+
+        for _ in 0..5 {
+            BTCRelay::prune_block_headers();
+        }
+        // headers since the last retarget are kept
+        assert_eq!(BTCRelay::pruned_height(), 4032);
+        assert_err!(BTCRelay::get_block_hash(0, 4031), TestError::BlockPruned);
+        assert_ok!(BTCRelay::get_block_hash(0, 4032));
+        assert_eq!(
+            BTCRelay::get_block_header_from_height(&main, 4031),
+            Err(TestError::BlockPruned.into())
+        );
+    })
+}
+
+#[test]
+fn prune_main_chain_disabled_succeeds() {
+    run_test(|| {
+        let main = store_blockchain_and_random_headers(0, 4000, 4100, 0);
+        BTCRelay::set_best_block(BTCRelay::get_block_hash(0, 4100).unwrap());
+        BTCRelay::set_best_block_height(main.max_height);
+        set_pruning_parameters(0, 0, 10);
+
+        BTCRelay::prune_block_headers();
+        assert_eq!(BTCRelay::pruned_height(), 0);
+        assert_ok!(BTCRelay::get_block_hash(0, 4000));
+    })
+}
+
+/// Sets the parent of the first block of a fork
+fn link_fork(fork: &BlockChain, parent_hash: H256Le) {
+    let hash = BTCRelay::get_block_hash(fork.chain_id, fork.start_height).unwrap();
+    let mut header = BTCRelay::get_block_header_from_hash(hash).unwrap();
+    header.block_header.hash_prev_block = parent_hash;
+    BTCRelay::set_block_header_from_hash(hash, &header);
+}
+
+#[test]
+fn prune_stale_forks_succeeds() {
+    use crate::sp_api_hidden_includes_decl_storage::hidden_include::{
+        IterableStorageMap, StorageValue,
+    };
+    use crate::{ChainCounter, Chains};
+
+    run_test(|| {
+        let main = store_blockchain_and_random_headers(0, 0, 100, 0);
+        let stale_fork = store_blockchain_and_random_headers(1, 10, 20, 1);
+        let recent_fork = store_blockchain_and_random_headers(2, 90, 95, 2);
+        // a fork of the stale fork that is itself not stale
+        let child_fork = store_blockchain_and_random_headers(3, 16, 60, 3);
+        link_fork(&stale_fork, BTCRelay::get_block_hash(0, 9).unwrap());
+        link_fork(&recent_fork, BTCRelay::get_block_hash(0, 89).unwrap());
+        link_fork(&child_fork, BTCRelay::get_block_hash(1, 15).unwrap());
+        <ChainCounter>::put(3);
+        BTCRelay::set_best_block(BTCRelay::get_block_hash(0, 100).unwrap());
+        BTCRelay::set_best_block_height(main.max_height);
+        set_pruning_parameters(0, 50, 5);
+
+        // the stale fork is removed from its tip
+        let tip_hash = BTCRelay::get_block_hash(1, 20).unwrap();
+        assert!(BTCRelay::prune_block_headers() > 0);
+        let fork = BTCRelay::get_block_chain_from_id(stale_fork.chain_id).unwrap();
+        assert_eq!(fork.max_height, 15);
+        assert_eq!(fork.chain_work, 15.into());
+        assert!(!BTCRelay::block_header_exists(tip_hash));
+        // the partially removed fork is ordered by its remaining work
+        assert_eq!(BTCRelay::get_chain_position_from_chain_id(2), Ok(1));
+        assert_eq!(BTCRelay::get_chain_position_from_chain_id(3), Ok(2));
+        assert_eq!(BTCRelay::get_chain_position_from_chain_id(1), Ok(3));
+
+        BTCRelay::prune_block_headers();
+        BTCRelay::prune_block_headers();
+        assert_err!(
+            BTCRelay::get_block_chain_from_id(stale_fork.chain_id),
+            TestError::InvalidChainID
+        );
+        assert_err!(
+            BTCRelay::get_chain_position_from_chain_id(stale_fork.chain_id),
+            TestError::ForkIdNotFound
+        );
+
+        // the fork branching off the removed fork is removed as well
+        for _ in 0..9 {
+            BTCRelay::prune_block_headers();
+        }
+        assert_err!(
+            BTCRelay::get_block_chain_from_id(child_fork.chain_id),
+            TestError::InvalidChainID
+        );
+        assert_eq!(<Chains>::iter().count(), 2);
+
+        assert_eq!(
+            BTCRelay::get_block_chain_from_id(recent_fork.chain_id),
+            Ok(recent_fork)
+        );
+        assert_eq!(BTCRelay::get_chain_position_from_chain_id(2), Ok(1));
+        assert_eq!(BTCRelay::get_block_chain_from_id(0), Ok(main));
+    })
+}
+
+#[test]
+fn prune_stale_forks_skips_fork_that_can_not_be_pruned() {
+    use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageValue;
+    use crate::ChainCounter;
+
+    run_test(|| {
+        let main = store_blockchain_and_random_headers(0, 0, 100, 0);
+        let broken_fork = store_blockchain_and_random_headers(1, 10, 20, 1);
+        let stale_fork = store_blockchain_and_random_headers(2, 30, 32, 2);
+        link_fork(&broken_fork, BTCRelay::get_block_hash(0, 9).unwrap());
+        link_fork(&stale_fork, BTCRelay::get_block_hash(0, 29).unwrap());
+        <ChainCounter>::put(2);
+        BTCRelay::set_best_block(BTCRelay::get_block_hash(0, 100).unwrap());
+        BTCRelay::set_best_block_height(main.max_height);
+        set_pruning_parameters(0, 50, 5);
+        // a hash below the tip of the fork is missing
+        let tip_hash = BTCRelay::get_block_hash(1, 20).unwrap();
+        BTCRelay::remove_block_hash(1, 18);
+
+        BTCRelay::prune_block_headers();
+
+        // the broken fork is left untouched
+        assert_eq!(BTCRelay::get_block_chain_from_id(1), Ok(broken_fork));
+        assert!(BTCRelay::block_header_exists(tip_hash));
+        // the other stale fork is still removed
+        assert_err!(
+            BTCRelay::get_block_chain_from_id(stale_fork.chain_id),
+            TestError::InvalidChainID
+        );
+    })
+}
+
+#[test]
+fn test_verify_transaction_inclusion_pruned_block_fails() {
+    run_test(|| {
+        let proof = sample_merkle_proof();
+        let proof_result = sample_valid_proof_result();
+
+        BTCRelay::ensure_no_ongoing_fork.mock_safe(|_| MockResult::Return(Ok(())));
+        BTCRelay::parse_merkle_proof.mock_safe(move |_| MockResult::Return(Ok(proof.clone())));
+        BTCRelay::is_block_header_pruned.mock_safe(|_| MockResult::Return(true));
+
+        assert_err!(
+            BTCRelay::verify_transaction_inclusion(
+                Origin::signed(3),
+                proof_result.transaction_hash,
+                vec![0u8; 100],
+                None
+            ),
+            TestError::BlockPruned
+        );
+    })
+}
+
+#[test]
+fn get_chains_returns_main_chain_first() {
+    run_test(|| {
+        let fork_2 = store_blockchain_and_random_headers(2, 10, 12, 2);
+        let main = store_blockchain_and_random_headers(0, 0, 20, 0);
+        let fork_1 = store_blockchain_and_random_headers(1, 5, 15, 1);
+
+        assert_eq!(BTCRelay::get_chains(), vec![main, fork_1, fork_2]);
+    })
+}
+
+#[test]
+fn get_block_header_at_height_succeeds() {
+    run_test(|| {
+        store_blockchain_and_random_headers(0, 0, 20, 0);
+        store_blockchain_and_random_headers(1, 5, 15, 1);
+
+        let block_header = BTCRelay::get_block_header_at_height(10).unwrap();
+        assert_eq!(block_header.chain_ref, crate::MAIN_CHAIN_ID);
+        assert_eq!(block_header.block_height, 10);
+        assert_err!(
+            BTCRelay::get_block_header_at_height(21),
+            TestError::MissingBlockHeight
+        );
+    })
+}
+
+#[test]
+fn use_transaction_succeeds() {
+    run_test(|| {
+        let raw_tx = hex::decode(sample_example_real_rawtx()).unwrap();
+        let transaction = parse_transaction(&raw_tx).unwrap();
+        let tx_id = transaction.tx_id();
+        let (output_index, recipient_btc_address) = transaction
+            .outputs
+            .iter()
+            .enumerate()
+            .find_map(|(index, output)| output.extract_address().ok().map(|a| (index, a)))
+            .unwrap();
+        let consumer = TransactionConsumer::Issue(H256::from_low_u64_be(1));
+        BTCRelay::parse_merkle_proof.mock_safe(|_| MockResult::Return(Ok(sample_merkle_proof())));
+
+        assert_ok!(BTCRelay::_use_transaction(
+            tx_id,
+            vec![],
+            raw_tx.clone(),
+            recipient_btc_address,
+            None,
+            consumer
+        ));
+        assert_eq!(
+            BTCRelay::get_transaction_consumers(tx_id),
+            vec![(output_index as u32, consumer)]
+        );
+        assert_eq!(
+            BTCRelay::block_consumers(sample_merkle_proof().block_header.hash().unwrap()),
+            vec![consumer]
+        );
+        assert_eq!(BTCRelay::get_fulfilling_transaction(consumer), Some(tx_id));
+
+        assert_err!(
+            BTCRelay::_use_transaction(
+                tx_id,
+                vec![],
+                raw_tx,
+                recipient_btc_address,
+                None,
+                TransactionConsumer::Redeem(H256::from_low_u64_be(2))
+            ),
+            TestError::TransactionAlreadyUsed
+        );
+    })
+}
+
+#[test]
+fn use_transaction_consumers_are_cleared_once_stable() {
+    run_test(|| {
+        let miner = sample_miner();
+        let blocks = store_generated_main_chain(&miner, 1);
+        let raw_tx = hex::decode(sample_example_real_rawtx()).unwrap();
+        let transaction = parse_transaction(&raw_tx).unwrap();
+        let recipient_btc_address = transaction
+            .outputs
+            .iter()
+            .find_map(|output| output.extract_address().ok())
+            .unwrap();
+        let consumer = TransactionConsumer::Issue(H256::from_low_u64_be(1));
+        let block_hash = sample_merkle_proof().block_header.hash().unwrap();
+        BTCRelay::parse_merkle_proof.mock_safe(|_| MockResult::Return(Ok(sample_merkle_proof())));
+
+        assert_ok!(BTCRelay::_use_transaction(
+            transaction.tx_id(),
+            vec![],
+            raw_tx,
+            recipient_btc_address,
+            None,
+            consumer
+        ));
+
+        let mut block = blocks[1].clone();
+        for height in 2..=(1 + BITCOIN_CONFIRMATIONS) {
+            assert_eq!(BTCRelay::block_consumers(block_hash), vec![consumer]);
+            block = generated_block_header(&miner, Some(&block), height);
+            assert_ok!(BTCRelay::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&block),
+                u32::MAX
+            ));
+        }
+        assert!(BTCRelay::block_consumers(block_hash).is_empty());
+    })
+}
+
+#[test]
+fn use_transaction_fails_with_invalid_txid() {
+    run_test(|| {
+        let raw_tx = hex::decode(sample_example_real_rawtx()).unwrap();
+        let recipient_btc_address = BtcAddress::P2SH(H160::zero());
+
+        assert_err!(
+            BTCRelay::_use_transaction(
+                H256Le::zero(),
+                vec![],
+                raw_tx,
+                recipient_btc_address,
+                None,
+                TransactionConsumer::Issue(H256::zero())
+            ),
+            TestError::InvalidTxid
+        );
+        assert!(BTCRelay::get_transaction_consumers(H256Le::zero()).is_empty());
+        assert_eq!(
+            BTCRelay::get_fulfilling_transaction(TransactionConsumer::Issue(H256::zero())),
+            None
+        );
+    })
+}
+
+#[test]
+fn migrate_chain_work_v0_to_v1_succeeds() {
+    use crate::sp_api_hidden_includes_decl_storage::hidden_include::{StorageMap, StorageValue};
+    use crate::types::{v0, Version};
+    use crate::{
+        BestBlock, BestBlockHeight, BlockHeaders, ChainCounter, ChainsIndex, StorageVersion,
+    };
+    use codec::Encode;
+    use frame_support::storage::unhashed;
+    use frame_support::traits::{OnInitialize, OnRuntimeUpgrade};
+
+    run_test(|| {
+        <StorageVersion>::put(Version::V0);
+
+        // main chain 0..=3, fork 2..=4 branching off block 1
+        let main = store_blockchain_and_random_headers(0, 0, 3, 0);
+        let fork = store_blockchain_and_random_headers(1, 2, 4, 1);
+        let main_hash = |height| BTCRelay::get_block_hash(0, height).unwrap();
+        let fork_hash = |height| BTCRelay::get_block_hash(1, height).unwrap();
+        <BestBlock>::put(main_hash(3));
+        <BestBlockHeight>::put(3);
+        <ChainCounter>::put(1);
+
+        // rewrite the stored values in the legacy format
+        for (chain, hash) in vec![
+            (0, main_hash(0)),
+            (0, main_hash(1)),
+            (0, main_hash(2)),
+            (0, main_hash(3)),
+            (1, fork_hash(2)),
+            (1, fork_hash(3)),
+            (1, fork_hash(4)),
+        ] {
+            let header = BTCRelay::get_block_header_from_hash(hash).unwrap();
+            let mut block_header = header.block_header;
+            block_header.hash_prev_block = if header.block_height == 0 {
+                H256Le::zero()
+            } else if chain == 1 && header.block_height == 2 {
+                main_hash(1)
+            } else {
+                BTCRelay::get_block_hash(chain, header.block_height - 1).unwrap()
+            };
+            unhashed::put_raw(
+                &<BlockHeaders<Test>>::hashed_key_for(hash),
+                &v0::RichBlockHeader {
+                    block_hash: header.block_hash,
+                    block_header,
+                    block_height: header.block_height,
+                    chain_ref: header.chain_ref,
+                    account_id: header.account_id,
+                }
+                .encode(),
+            );
+        }
+        for chain in vec![&main, &fork] {
+            unhashed::put_raw(
+                &<ChainsIndex>::hashed_key_for(chain.chain_id),
+                &v0::BlockChain {
+                    chain_id: chain.chain_id,
+                    start_height: chain.start_height,
+                    max_height: chain.max_height,
+                    no_data: chain.no_data.clone(),
+                    invalid: chain.invalid.clone(),
+                }
+                .encode(),
+            );
+        }
+
+        assert!(BTCRelay::on_runtime_upgrade() > 0);
+        assert!(BTCRelay::storage_version() == Version::V3);
+        assert!(BTCRelay::chain_work_migration().is_some());
+        assert_err!(
+            BTCRelay::_invalidate_block(main_hash(3)),
+            TestError::MigrationInProgress
+        );
+
+        let mut blocks = 0;
+        while BTCRelay::chain_work_migration().is_some() {
+            assert!(BTCRelay::on_initialize(1) > 0);
+            blocks += 1;
+        }
+        assert_eq!(blocks, 1);
+
+        let block_work = BTCRelay::get_block_header_from_hash(main_hash(0))
+            .unwrap()
+            .block_header
+            .work();
+        let work = |blocks: u64| block_work * blocks;
+        assert_eq!(
+            BTCRelay::get_block_header_from_hash(main_hash(3))
+                .unwrap()
+                .chain_work,
+            work(4)
+        );
+        assert_eq!(
+            BTCRelay::get_block_header_from_hash(fork_hash(4))
+                .unwrap()
+                .chain_work,
+            work(5)
+        );
+        assert_eq!(
+            BTCRelay::get_block_chain_from_id(0).unwrap().chain_work,
+            work(4)
+        );
+        assert_eq!(
+            BTCRelay::get_block_chain_from_id(1).unwrap().chain_work,
+            work(5)
+        );
+        assert_eq!(BTCRelay::get_chain_id_from_position(1).unwrap(), 1);
+    })
+}
+
+#[test]
+fn migrate_network_v1_to_v2_succeeds() {
+    use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageValue;
+    use crate::types::Version;
+    use crate::StorageVersion;
+    use codec::Encode;
+    use frame_support::storage::unhashed;
+    use frame_support::traits::OnRuntimeUpgrade;
+
+    run_test(|| {
+        <StorageVersion>::put(Version::V1);
+        let network_key = [
+            sp_io::hashing::twox_128(b"BTCRelay"),
+            sp_io::hashing::twox_128(b"Network"),
+        ]
+        .concat();
+        unhashed::put_raw(&network_key, &BitcoinNetwork::Testnet.encode());
+
+        BTCRelay::on_runtime_upgrade();
+
+        assert!(BTCRelay::storage_version() == Version::V3);
+        assert!(!unhashed::exists(&network_key));
+    })
+}
+
+#[test]
+fn relay_instances_are_independent() {
+    run_test(|| {
+        assert_eq!(
+            BTCRelay::network_parameters(),
+            BitcoinNetwork::Mainnet.parameters()
+        );
+        assert_eq!(
+            BTCRelayTestnet::network_parameters(),
+            BitcoinNetwork::Testnet.parameters()
+        );
+
+        let blocks = store_generated_main_chain(&sample_miner(), 1);
+
+        // the testnet instance is initialized from a different block
+        let testnet_genesis = generated_block_header(&sample_fork_miner(), None, 0);
+        let testnet_genesis_hash = testnet_genesis.header.hash().unwrap();
+        assert_ok!(BTCRelayTestnet::initialize(
+            Origin::signed(3),
+            raw_generated_block_header(&testnet_genesis),
+            0
+        ));
+        let init_event = TestEvent::btc_relay_Instance1(
+            crate::Event::<Test, crate::Instance1>::Initialized(0, testnet_genesis_hash),
+        );
+        assert!(System::events().iter().any(|a| a.event == init_event));
+
+        // headers stored by one instance are unknown to the other
+        assert_err!(
+            BTCRelayTestnet::store_block_header(
+                Origin::signed(3),
+                raw_generated_block_header(&blocks[1]),
+                u32::MAX
+            ),
+            TestnetError::BlockNotFound
+        );
+        assert_err!(
+            BTCRelay::get_block_header_from_hash(testnet_genesis_hash),
+            TestError::BlockNotFound
+        );
+
+        assert_eq!(BTCRelay::get_best_block(), blocks[1].header.hash().unwrap());
+        assert_eq!(BTCRelay::get_best_block_height(), 1);
+        assert_eq!(BTCRelayTestnet::get_best_block(), testnet_genesis_hash);
+        assert_eq!(BTCRelayTestnet::get_best_block_height(), 0);
+    })
+}
+
+#[test]
+fn migrate_verification_profile_v2_to_v3_succeeds() {
+    use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageValue;
+    use crate::types::Version;
+    use crate::StorageVersion;
+    use codec::Encode;
+    use frame_support::storage::unhashed;
+    use frame_support::traits::OnRuntimeUpgrade;
+
+    run_test(|| {
+        <StorageVersion>::put(Version::V2);
+        let flag_key = |item: &[u8]| {
+            [
+                sp_io::hashing::twox_128(b"BTCRelay"),
+                sp_io::hashing::twox_128(item),
+            ]
+            .concat()
+        };
+        unhashed::put_raw(&flag_key(b"DisableDifficultyCheck"), &false.encode());
+        unhashed::put_raw(&flag_key(b"DisableInclusionCheck"), &true.encode());
+        unhashed::put_raw(&flag_key(b"DisableRelayerAuth"), &true.encode());
+
+        BTCRelay::on_runtime_upgrade();
+
+        assert!(BTCRelay::storage_version() == Version::V3);
+        // inclusion checks are enforced on mainnet
+        assert_eq!(
+            BTCRelay::verification_profile(),
+            VerificationProfile {
+                difficulty_check: true,
+                inclusion_check: true,
+                op_return_check: true,
+                relayer_auth: false,
+            }
+        );
+        assert!(!unhashed::exists(&flag_key(b"DisableInclusionCheck")));
+        assert!(!unhashed::exists(&flag_key(b"DisableRelayerAuth")));
+    })
+}
+
+#[test]
+fn test_extract_value_fails_with_wrong_recipient() {
+    run_test(|| {
+        let recipient_btc_address_0 = BtcAddress::P2SH(H160([0; 20]));
+        let recipient_btc_address_1 = BtcAddress::P2SH(H160([1; 20]));
+
+        let transaction = TransactionBuilder::new()
+            .with_version(2)
+            .add_output(TransactionOutput::payment(32, &recipient_btc_address_0))
+            .build();
+
+        assert_err!(
+            BTCRelay::extract_payment_value(transaction, recipient_btc_address_1),
+            TestError::WrongRecipient
+        );
+    })
+}
+
+#[test]
+fn test_extract_value_succeeds() {
+    run_test(|| {
+        let recipient_btc_address = BtcAddress::P2SH(H160([0; 20]));
+        let recipient_value = 64;
+
+        let transaction = TransactionBuilder::new()
+            .with_version(2)
             .add_output(TransactionOutput::payment(
                 recipient_value,
                 &recipient_btc_address,
@@ -1815,7 +3913,7 @@ fn test_extract_value_succeeds() {
 
         assert_eq!(
             BTCRelay::extract_payment_value(transaction, recipient_btc_address).unwrap(),
-            recipient_value
+            (0, recipient_value)
         );
     })
 }
@@ -1892,10 +3990,11 @@ fn test_extract_value_and_op_return_succeeds() {
             .add_output(TransactionOutput::op_return(0, &op_return))
             .build();
 
-        let (extr_value, extr_data) =
+        let (output_index, extr_value, extr_data) =
             BTCRelay::extract_payment_value_and_op_return(transaction, recipient_btc_address)
                 .unwrap();
 
+        assert_eq!(output_index, 0);
         assert_eq!(extr_value, recipient_value);
         assert_eq!(extr_data, op_return);
     })
@@ -1910,10 +4009,10 @@ fn test_check_and_do_reorg() {
 
     // data taken from testnet fork
     run_test(|| {
-        Chains::insert(0, 0);
-        Chains::insert(2, 7);
+        <Chains>::insert(0, 0);
+        <Chains>::insert(2, 7);
 
-        ChainsIndex::insert(
+        <ChainsIndex>::insert(
             0,
             BlockChain {
                 chain_id: 0,
@@ -1921,10 +4020,11 @@ fn test_check_and_do_reorg() {
                 max_height: 1_897_317,
                 no_data: BTreeSet::new(),
                 invalid: BTreeSet::new(),
+                chain_work: 1_897_317.into(),
             },
         );
 
-        ChainsIndex::insert(
+        <ChainsIndex>::insert(
             2,
             BlockChain {
                 chain_id: 2,
@@ -1932,10 +4032,11 @@ fn test_check_and_do_reorg() {
                 max_height: 1_893_831,
                 no_data: BTreeSet::new(),
                 invalid: BTreeSet::new(),
+                chain_work: 1_893_831.into(),
             },
         );
 
-        ChainsIndex::insert(
+        <ChainsIndex>::insert(
             4,
             BlockChain {
                 chain_id: 4,
@@ -1943,10 +4044,11 @@ fn test_check_and_do_reorg() {
                 max_height: 1_895_256,
                 no_data: BTreeSet::new(),
                 invalid: BTreeSet::new(),
+                chain_work: 1_895_256.into(),
             },
         );
 
-        ChainsIndex::insert(
+        <ChainsIndex>::insert(
             6,
             BlockChain {
                 chain_id: 6,
@@ -1954,10 +4056,11 @@ fn test_check_and_do_reorg() {
                 max_height: 1_896_846,
                 no_data: BTreeSet::new(),
                 invalid: BTreeSet::new(),
+                chain_work: 1_896_846.into(),
             },
         );
 
-        ChainsIndex::insert(
+        <ChainsIndex>::insert(
             7,
             BlockChain {
                 chain_id: 7,
@@ -1965,6 +4068,7 @@ fn test_check_and_do_reorg() {
                 max_height: 1_897_910,
                 no_data: BTreeSet::new(),
                 invalid: BTreeSet::new(),
+                chain_work: 1_897_910.into(),
             },
         );
 
@@ -1972,13 +4076,17 @@ fn test_check_and_do_reorg() {
 
         // we should skip empty `Chains`, this can occur if the
         // previous index is accidentally deleted
-        assert_ok!(BTCRelay::check_and_do_reorg(&BlockChain {
-            chain_id: 7,
-            start_height: 1_897_317,
-            max_height: 1_897_910,
-            no_data: BTreeSet::new(),
-            invalid: BTreeSet::new(),
-        }));
+        assert_ok!(BTCRelay::check_and_do_reorg(
+            &BlockChain {
+                chain_id: 7,
+                start_height: 1_897_317,
+                max_height: 1_897_910,
+                no_data: BTreeSet::new(),
+                invalid: BTreeSet::new(),
+                chain_work: 1_897_910.into(),
+            },
+            u32::MAX
+        ));
     })
 }
 
@@ -1989,9 +4097,9 @@ fn test_remove_blockchain_from_chain() {
     use crate::Chains;
 
     run_test(|| {
-        Chains::insert(0, 0);
-        Chains::insert(8, 5);
-        Chains::insert(2, 7);
+        <Chains>::insert(0, 0);
+        <Chains>::insert(8, 5);
+        <Chains>::insert(2, 7);
 
         assert_ok!(BTCRelay::remove_blockchain_from_chain(2));
 
@@ -2004,13 +4112,16 @@ fn test_remove_blockchain_from_chain() {
 #[test]
 fn test_ensure_relayer_authorized() {
     use crate::sp_api_hidden_includes_decl_storage::hidden_include::StorageValue;
-    use crate::DisableRelayerAuth;
+    use crate::ActiveVerificationProfile;
 
     run_test(|| {
-        DisableRelayerAuth::set(true);
+        <ActiveVerificationProfile>::put(VerificationProfile {
+            relayer_auth: false,
+            ..Default::default()
+        });
         assert_ok!(BTCRelay::ensure_relayer_authorized(0));
 
-        DisableRelayerAuth::set(false);
+        <ActiveVerificationProfile>::put(VerificationProfile::default());
         assert_err!(
             BTCRelay::ensure_relayer_authorized(0),
             TestError::RelayerNotAuthorized
@@ -2079,6 +4190,7 @@ fn get_empty_block_chain_from_chain_id_and_height(
         max_height: block_height,
         no_data: BTreeSet::new(),
         invalid: BTreeSet::new(),
+        chain_work: block_height.into(),
     };
 
     blockchain
@@ -2129,10 +4241,11 @@ fn store_blockchain_and_random_headers(
             block_height: height,
             chain_ref: id,
             account_id: Default::default(),
+            chain_work: height.into(),
         };
 
         BTCRelay::set_block_header_from_hash(block_hash, &rich_header);
-        chain = BTCRelay::extend_blockchain(height, &block_hash, chain).unwrap();
+        chain = BTCRelay::extend_blockchain(height, &block_hash, height.into(), chain).unwrap();
     }
     // insert the main chain in Chains and ChainsIndex
     BTCRelay::set_chain_from_position_and_id(position, id);
@@ -2153,6 +4266,7 @@ fn sample_parsed_genesis_header(chain_ref: u32, block_height: u32) -> RichBlockH
         block_height: block_height,
         chain_ref: chain_ref,
         account_id: Default::default(),
+        chain_work: Default::default(),
     }
 }
 
@@ -2177,6 +4291,7 @@ fn sample_parsed_first_block(chain_ref: u32, block_height: u32) -> RichBlockHead
         block_height: block_height,
         chain_ref: chain_ref,
         account_id: Default::default(),
+        chain_work: Default::default(),
     }
 }
 
@@ -2225,6 +4340,7 @@ fn sample_rich_tx_block_header(chain_ref: u32, block_height: u32) -> RichBlockHe
         block_height: block_height,
         chain_ref: chain_ref,
         account_id: Default::default(),
+        chain_work: Default::default(),
     }
 }
 
@@ -2313,6 +4429,10 @@ fn sample_example_real_transaction_hash() -> String {
     "b759d39a8596b70b3a46700b83e1edb247e17ba58df305421864fe7a9ac142ea".to_owned()
 }
 
+fn set_bitcoin_network(network: BitcoinNetwork) {
+    BTCRelay::network_parameters.mock_safe(move || MockResult::Return(network.parameters()));
+}
+
 fn set_parachain_nodata_error() {
     ext::security::insert_error::<Test>(ErrorCode::NoDataBTCRelay);
     ext::security::set_parachain_status::<Test>(StatusCode::Error);
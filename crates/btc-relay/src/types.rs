@@ -2,9 +2,207 @@ use bitcoin::parser::FromLeBytes;
 use bitcoin::types::{BlockHeader, H256Le, RawBlockHeader};
 use bitcoin::Error;
 use codec::{Decode, Encode};
+use primitive_types::U256;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+use sp_std::collections::btree_set::BTreeSet;
+use sp_std::prelude::*;
+
+use crate::{
+    DIFFICULTY_ADJUSTMENT_INTERVAL, REGTEST_MAX_TARGET, SIGNET_MAX_TARGET, TARGET_SPACING,
+    TARGET_TIMESPAN, UNROUNDED_MAX_TARGET,
+};
+
+/// Storage version.
+#[derive(Encode, Decode, Eq, PartialEq)]
+pub enum Version {
+    /// Initial version.
+    V0,
+    /// Chainwork tracked per block header and blockchain.
+    V1,
+    /// Network parameters moved from storage into the pallet configuration.
+    V2,
+    /// Disable* flags replaced by the `VerificationProfile`.
+    V3,
+}
+
+/// Progress of the multi-block chainwork migration to `Version::V1`.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChainWorkMigrationCursor {
+    /// Height of the next block header to migrate.
+    pub block_height: u32,
+    /// Height up to which block headers are migrated.
+    pub max_height: u32,
+    /// Id of the next chain to migrate, or to look up the block header in.
+    pub chain_id: u32,
+    /// Whether all block headers have been migrated and the chains are next.
+    pub headers_migrated: bool,
+}
+
+/// Bitcoin network whose consensus rules are enforced by BTC-Relay.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum BitcoinNetwork {
+    /// Bitcoin mainnet.
+    Mainnet,
+    /// Bitcoin testnet3, which allows minimum difficulty blocks.
+    Testnet,
+    /// Bitcoin signet. Blocks must be signed according to the signet challenge, which
+    /// is committed to in the coinbase transaction and can therefore not be checked
+    /// from the block header alone. Headers are validated like on mainnet against
+    /// the signet proof-of-work limit.
+    Signet,
+    /// Bitcoin regtest, which allows minimum difficulty blocks and never retargets.
+    Regtest,
+}
+
+impl Default for BitcoinNetwork {
+    fn default() -> Self {
+        BitcoinNetwork::Mainnet
+    }
+}
+
+impl BitcoinNetwork {
+    /// Highest target (lowest difficulty) a block may have on this network.
+    pub fn max_target(&self) -> U256 {
+        match self {
+            BitcoinNetwork::Mainnet | BitcoinNetwork::Testnet => UNROUNDED_MAX_TARGET,
+            BitcoinNetwork::Signet => SIGNET_MAX_TARGET,
+            BitcoinNetwork::Regtest => REGTEST_MAX_TARGET,
+        }
+    }
+
+    /// Whether a block may be mined at the minimum difficulty if no block was
+    /// found for twice the target spacing.
+    pub fn allows_min_difficulty_blocks(&self) -> bool {
+        match self {
+            BitcoinNetwork::Testnet | BitcoinNetwork::Regtest => true,
+            BitcoinNetwork::Mainnet | BitcoinNetwork::Signet => false,
+        }
+    }
+
+    /// Whether the difficulty is never adjusted.
+    pub fn no_retargeting(&self) -> bool {
+        *self == BitcoinNetwork::Regtest
+    }
+
+    /// Consensus and address parameters of this network. Other Bitcoin-like
+    /// networks declare their `NetworkParameters` directly.
+    pub fn parameters(&self) -> NetworkParameters {
+        let (p2pkh_prefix, p2sh_prefix, bech32_hrp) = match self {
+            BitcoinNetwork::Mainnet => (0x00, 0x05, b"bc".to_vec()),
+            BitcoinNetwork::Testnet | BitcoinNetwork::Signet => (0x6f, 0xc4, b"tb".to_vec()),
+            BitcoinNetwork::Regtest => (0x6f, 0xc4, b"bcrt".to_vec()),
+        };
+        NetworkParameters {
+            mainnet: *self == BitcoinNetwork::Mainnet,
+            difficulty_adjustment_interval: DIFFICULTY_ADJUSTMENT_INTERVAL,
+            target_timespan: TARGET_TIMESPAN,
+            target_spacing: TARGET_SPACING,
+            max_target: self.max_target(),
+            allows_min_difficulty_blocks: self.allows_min_difficulty_blocks(),
+            no_retargeting: self.no_retargeting(),
+            p2pkh_prefix,
+            p2sh_prefix,
+            bech32_hrp,
+        }
+    }
+}
+
+/// Parameters of a Bitcoin-like network, used to validate its block headers.
+/// The address prefixes are not needed for validation, but exposed so that
+/// clients can encode the addresses of the relayed network.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct NetworkParameters {
+    /// Whether the network is a production network, on which inclusion checks
+    /// can not be disabled
+    pub mainnet: bool,
+    /// Number of blocks between two difficulty adjustments
+    pub difficulty_adjustment_interval: u32,
+    /// Expected duration of a difficulty adjustment interval in seconds
+    pub target_timespan: u32,
+    /// Expected time between two blocks in seconds
+    pub target_spacing: u32,
+    /// Highest target (lowest difficulty) a block may have
+    pub max_target: U256,
+    /// Whether a block may be mined at the minimum difficulty if no block was
+    /// found for twice the target spacing
+    pub allows_min_difficulty_blocks: bool,
+    /// Whether the difficulty is never adjusted
+    pub no_retargeting: bool,
+    /// Version byte of base58 encoded P2PKH addresses
+    pub p2pkh_prefix: u8,
+    /// Version byte of base58 encoded P2SH addresses
+    pub p2sh_prefix: u8,
+    /// Human-readable part of bech32 encoded segwit addresses
+    pub bech32_hrp: Vec<u8>,
+}
+
+/// Trusted Bitcoin block from which BTC-Relay may be initialized and which
+/// all stored chains must agree with.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Checkpoint {
+    /// Height of the checkpointed block
+    pub block_height: u32,
+    /// Hash of the checkpointed block
+    pub block_hash: H256Le,
+    /// Accumulated work of all blocks up to and including the checkpointed block
+    pub chain_work: U256,
+}
+
+/// Checks performed by BTC-Relay. Disabling any of them is only intended for
+/// testing and development chains.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VerificationProfile {
+    /// Whether the proof-of-work and difficulty target of block headers are checked
+    pub difficulty_check: bool,
+    /// Whether the inclusion of transactions in the main chain is checked
+    pub inclusion_check: bool,
+    /// Whether the OP_RETURN output of transactions is checked
+    pub op_return_check: bool,
+    /// Whether only authorized relayers may submit block headers
+    pub relayer_auth: bool,
+}
+
+impl Default for VerificationProfile {
+    fn default() -> Self {
+        VerificationProfile {
+            difficulty_check: true,
+            inclusion_check: true,
+            op_return_check: true,
+            relayer_auth: true,
+        }
+    }
+}
+
+/// Request whose execution consumed a Bitcoin transaction output.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TransactionConsumer {
+    Issue(H256),
+    Redeem(H256),
+    Replace(H256),
+    Refund(H256),
+}
+
+/// Outcome of storing a block header submitted in a batch.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum BlockHeaderResult {
+    /// The block header was stored
+    Stored,
+    /// The block header was already stored and has been skipped
+    Duplicate,
+    /// The block header was rejected, no later headers of the batch were processed
+    Rejected(DispatchError),
+}
 
 /// Bitcoin Enriched Block Headers
 #[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct RichBlockHeader<AccountId> {
     pub block_hash: H256Le,
     pub block_header: BlockHeader,
@@ -12,6 +210,8 @@ pub struct RichBlockHeader<AccountId> {
     pub chain_ref: u32,
     // required for fault attribution
     pub account_id: AccountId,
+    /// Accumulated work of all blocks up to and including this one
+    pub chain_work: U256,
 }
 
 impl<AccountId> RichBlockHeader<AccountId> {
@@ -23,19 +223,49 @@ impl<AccountId> RichBlockHeader<AccountId> {
     /// * `chain_ref` - chain reference
     /// * `block_height` - chain height
     /// * `account_id` - submitter
+    /// * `prev_chain_work` - accumulated work of the previous block
     #[allow(dead_code)]
     pub fn new(
         raw_block_header: RawBlockHeader,
         chain_ref: u32,
         block_height: u32,
         account_id: AccountId,
+        prev_chain_work: U256,
     ) -> Result<Self, Error> {
+        let block_header = BlockHeader::from_le_bytes(raw_block_header.as_bytes())?;
+        let chain_work = prev_chain_work
+            .checked_add(block_header.work())
+            .ok_or(Error::ArithmeticOverflow)?;
         Ok(RichBlockHeader {
             block_hash: raw_block_header.hash(),
-            block_header: BlockHeader::from_le_bytes(raw_block_header.as_bytes())?,
+            block_header,
             block_height,
             chain_ref,
             account_id,
+            chain_work,
         })
     }
 }
+
+/// Storage layouts prior to `Version::V1`, only used for migrations.
+pub(crate) mod v0 {
+    use super::*;
+
+    #[derive(Encode, Decode)]
+    pub struct RichBlockHeader<AccountId> {
+        pub block_hash: H256Le,
+        pub block_header: BlockHeader,
+        pub block_height: u32,
+        pub chain_ref: u32,
+        pub account_id: AccountId,
+    }
+
+    #[derive(Encode, Decode)]
+    pub struct BlockChain {
+        pub chain_id: u32,
+        pub start_height: u32,
+        pub max_height: u32,
+        pub no_data: BTreeSet<u32>,
+        pub invalid: BTreeSet<u32>,
+    }
+}
@@ -40,6 +40,11 @@ pub trait WeightInfo {
     fn verify_and_validate_transaction() -> Weight;
     fn verify_transaction_inclusion() -> Weight;
     fn validate_transaction() -> Weight;
+    fn set_checkpoint() -> Weight;
+    fn remove_checkpoint() -> Weight;
+    fn set_verification_profile() -> Weight;
+    fn swap_main_blockchain(f: u32) -> Weight;
+    fn invalidate_block(f: u32, c: u32) -> Weight;
 }
 
 /// Weights for btc_relay using the Substrate node and recommended hardware.
@@ -52,8 +57,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     }
     fn store_block_header() -> Weight {
         (123_623_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(13 as Weight))
-            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+            .saturating_add(T::DbWeight::get().reads(17 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
     }
     fn verify_and_validate_transaction() -> Weight {
         (99_474_000 as Weight).saturating_add(T::DbWeight::get().reads(9 as Weight))
@@ -64,6 +69,38 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn validate_transaction() -> Weight {
         (15_739_000 as Weight).saturating_add(T::DbWeight::get().reads(1 as Weight))
     }
+    fn set_checkpoint() -> Weight {
+        (18_341_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_checkpoint() -> Weight {
+        (9_512_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_verification_profile() -> Weight {
+        (8_734_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn swap_main_blockchain(f: u32) -> Weight {
+        (64_381_000 as Weight)
+            .saturating_add((17_924_000 as Weight).saturating_mul(f as Weight))
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(f as Weight)))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(f as Weight)))
+    }
+    fn invalidate_block(f: u32, c: u32) -> Weight {
+        (87_216_000 as Weight)
+            .saturating_add((22_417_000 as Weight).saturating_mul(f as Weight))
+            .saturating_add((9_862_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(f as Weight)))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(f as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -75,8 +112,8 @@ impl WeightInfo for () {
     }
     fn store_block_header() -> Weight {
         (123_623_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(17 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
     }
     fn verify_and_validate_transaction() -> Weight {
         (99_474_000 as Weight).saturating_add(RocksDbWeight::get().reads(9 as Weight))
@@ -87,4 +124,36 @@ impl WeightInfo for () {
     fn validate_transaction() -> Weight {
         (15_739_000 as Weight).saturating_add(RocksDbWeight::get().reads(1 as Weight))
     }
+    fn set_checkpoint() -> Weight {
+        (18_341_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn remove_checkpoint() -> Weight {
+        (9_512_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_verification_profile() -> Weight {
+        (8_734_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn swap_main_blockchain(f: u32) -> Weight {
+        (64_381_000 as Weight)
+            .saturating_add((17_924_000 as Weight).saturating_mul(f as Weight))
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(f as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(f as Weight)))
+    }
+    fn invalidate_block(f: u32, c: u32) -> Weight {
+        (87_216_000 as Weight)
+            .saturating_add((22_417_000 as Weight).saturating_mul(f as Weight))
+            .saturating_add((9_862_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(f as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(f as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+    }
 }
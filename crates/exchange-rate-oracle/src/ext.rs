@@ -4,6 +4,8 @@ use mocktopus::macros::mockable;
 #[cfg_attr(test, mockable)]
 pub(crate) mod security {
     use frame_support::dispatch::DispatchResult;
+    use security::{ErrorCode, StatusChangeCause, StatusCode};
+    use sp_std::collections::btree_set::BTreeSet;
 
     pub fn ensure_parachain_status_not_shutdown<T: security::Config>() -> DispatchResult {
         <security::Module<T>>::ensure_parachain_status_not_shutdown()
@@ -12,4 +14,24 @@ pub(crate) mod security {
     pub fn recover_from_oracle_offline<T: security::Config>() -> DispatchResult {
         <security::Module<T>>::recover_from_oracle_offline()
     }
+
+    pub fn get_parachain_status<T: security::Config>() -> StatusCode {
+        <security::Module<T>>::get_parachain_status()
+    }
+
+    pub fn set_parachain_status<T: security::Config>(status_code: StatusCode) {
+        <security::Module<T>>::set_parachain_status(status_code)
+    }
+
+    pub fn get_errors<T: security::Config>() -> BTreeSet<ErrorCode> {
+        <security::Module<T>>::get_errors()
+    }
+
+    pub fn insert_error<T: security::Config>(error_code: ErrorCode) {
+        <security::Module<T>>::insert_error(error_code)
+    }
+
+    pub fn record_status_change<T: security::Config>(cause: StatusChangeCause) {
+        <security::Module<T>>::record_status_change(cause, None)
+    }
 }
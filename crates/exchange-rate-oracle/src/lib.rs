@@ -26,11 +26,12 @@ use mocktopus::macros::mockable;
 
 use codec::{Decode, Encode, EncodeLike};
 use frame_support::dispatch::{DispatchError, DispatchResult};
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, Get};
 use frame_support::transactional;
 use frame_support::weights::Weight;
 use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure};
 use frame_system::{ensure_root, ensure_signed};
+use security::{ErrorCode, StatusChangeCause, StatusCode};
 use sp_arithmetic::traits::UniqueSaturatedInto;
 use sp_arithmetic::traits::*;
 use sp_arithmetic::FixedPointNumber;
@@ -151,6 +152,16 @@ decl_module! {
             0
         }
 
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            match Self::begin_block(n) {
+                Ok(weight) => weight,
+                Err(e) => {
+                    sp_runtime::print(e);
+                    Self::update_oracle_status_weight()
+                }
+            }
+        }
+
         /// Sets the exchange rate.
         ///
         /// # Arguments
@@ -232,6 +243,48 @@ decl_module! {
 
 #[cfg_attr(test, mockable)]
 impl<T: Config> Module<T> {
+    fn begin_block(_height: T::BlockNumber) -> Result<Weight, DispatchError> {
+        Self::update_oracle_status()
+    }
+
+    /// Sets `ErrorCode::OracleOffline` if the maximum delay has passed since the
+    /// last exchange rate update, or clears it if the exchange rate is fresh again.
+    /// The status is left untouched while the parachain is shut down or before
+    /// the first exchange rate has been set. Returns the consumed weight.
+    fn update_oracle_status() -> Result<Weight, DispatchError> {
+        let db_weight = T::DbWeight::get();
+        if !<LastExchangeRateTime<T>>::exists() {
+            return Ok(db_weight.reads(1));
+        }
+        if ext::security::get_parachain_status::<T>() == StatusCode::Shutdown {
+            return Ok(db_weight.reads(2));
+        }
+
+        let is_flagged = ext::security::get_errors::<T>().contains(&ErrorCode::OracleOffline);
+        if Self::is_max_delay_passed() {
+            if !is_flagged {
+                ext::security::set_parachain_status::<T>(StatusCode::Error);
+                ext::security::insert_error::<T>(ErrorCode::OracleOffline);
+                ext::security::record_status_change::<T>(StatusChangeCause::OracleOffline);
+                Self::deposit_event(Event::<T>::OracleOffline());
+                return Ok(Self::update_oracle_status_weight());
+            }
+        } else if is_flagged {
+            Self::recover_from_oracle_offline()?;
+            Self::deposit_event(Event::<T>::OracleOnline());
+            return Ok(Self::update_oracle_status_weight());
+        }
+        // last update, status, errors, timestamp and max delay
+        Ok(db_weight.reads(5))
+    }
+
+    /// Weight of `update_oracle_status` if the `OracleOffline` error is set or cleared.
+    fn update_oracle_status_weight() -> Weight {
+        // additionally reads the block number and the status history, and writes the
+        // status, the errors, the status history and the events
+        T::DbWeight::get().reads_writes(7, 4)
+    }
+
     /// Public getters
 
     /// Get the exchange rate in planck per satoshi
@@ -365,6 +418,10 @@ decl_event! {
         SetExchangeRate(AccountId, UnsignedFixedPoint),
         /// Event emitted when the btc tx fees are set
         SetBtcTxFeesPerByte(AccountId, u32, u32, u32),
+        /// Event emitted when the exchange rate is detected to be outdated
+        OracleOffline(),
+        /// Event emitted when an outdated exchange rate is detected to be fresh again
+        OracleOnline(),
     }
 }
 
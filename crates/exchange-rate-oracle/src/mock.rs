@@ -107,8 +107,14 @@ impl treasury::Config for Test {
     type Event = TestEvent;
 }
 
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 100;
+}
+
 impl security::Config for Test {
     type Event = TestEvent;
+    type MaxStatusHistory = MaxStatusHistory;
+    type RandomnessSource = pallet_randomness_collective_flip::Module<Test>;
 }
 
 pub type TestEvent = Event;
@@ -1,7 +1,10 @@
-use crate::mock::{run_test, ExchangeRateOracle, Origin, System, Test, TestError, TestEvent};
+use crate::mock::{
+    run_test, ExchangeRateOracle, Origin, Security, System, Test, TestError, TestEvent,
+};
 use crate::BtcTxFeesPerByte;
 use frame_support::{assert_err, assert_ok, dispatch::DispatchError};
 use mocktopus::mocking::*;
+use security::{ErrorCode, StatusCode};
 use sp_arithmetic::FixedU128;
 use sp_runtime::FixedPointNumber;
 
@@ -258,3 +261,67 @@ fn set_btc_tx_fees_per_byte_succeeds() {
         assert_emitted!(Event::SetBtcTxFeesPerByte(3, 1, 1, 1));
     });
 }
+
+#[test]
+fn begin_block_sets_oracle_offline() {
+    run_test(|| {
+        ExchangeRateOracle::set_last_exchange_rate_time(0);
+        ExchangeRateOracle::is_max_delay_passed.mock_safe(|| MockResult::Return(true));
+
+        assert_ok!(ExchangeRateOracle::begin_block(1));
+        assert_eq!(Security::get_parachain_status(), StatusCode::Error);
+        assert!(Security::get_errors().contains(&ErrorCode::OracleOffline));
+        assert_emitted!(Event::OracleOffline());
+
+        // the error is only raised once
+        System::reset_events();
+        assert_ok!(ExchangeRateOracle::begin_block(2));
+        assert_not_emitted!(Event::OracleOffline());
+    });
+}
+
+#[test]
+fn begin_block_clears_oracle_offline() {
+    run_test(|| {
+        Security::set_parachain_status(StatusCode::Error);
+        Security::insert_error(ErrorCode::OracleOffline);
+        ExchangeRateOracle::set_last_exchange_rate_time(0);
+        ExchangeRateOracle::is_max_delay_passed.mock_safe(|| MockResult::Return(false));
+
+        assert_ok!(ExchangeRateOracle::begin_block(1));
+        assert_eq!(Security::get_parachain_status(), StatusCode::Running);
+        assert!(Security::get_errors().is_empty());
+        assert_emitted!(Event::OracleOnline());
+    });
+}
+
+#[test]
+fn begin_block_ignores_oracle_while_shutdown() {
+    run_test(|| {
+        Security::set_parachain_status(StatusCode::Shutdown);
+        ExchangeRateOracle::set_last_exchange_rate_time(0);
+        ExchangeRateOracle::is_max_delay_passed.mock_safe(|| MockResult::Return(true));
+
+        assert_ok!(ExchangeRateOracle::begin_block(1));
+        assert_eq!(Security::get_parachain_status(), StatusCode::Shutdown);
+        assert!(Security::get_errors().is_empty());
+        assert_not_emitted!(Event::OracleOffline());
+    });
+}
+
+#[test]
+fn begin_block_ignores_oracle_before_first_exchange_rate() {
+    run_test(|| {
+        ExchangeRateOracle::is_max_delay_passed.mock_safe(|| MockResult::Return(true));
+
+        assert_ok!(ExchangeRateOracle::begin_block(1));
+        assert_eq!(Security::get_parachain_status(), StatusCode::Running);
+        assert!(Security::get_errors().is_empty());
+        assert_not_emitted!(Event::OracleOffline());
+
+        // detection starts with the first exchange rate
+        ExchangeRateOracle::set_last_exchange_rate_time(0);
+        assert_ok!(ExchangeRateOracle::begin_block(2));
+        assert!(Security::get_errors().contains(&ErrorCode::OracleOffline));
+    });
+}
@@ -37,6 +37,10 @@ benchmarks! {
         <TotalRewardsDOT<T>>::insert(recipient.clone(), amount);
 
     }: _(RawOrigin::Signed(recipient), amount)
+
+    set_header_submission_reward {
+        let amount: PolkaBTC<T> = 100u32.into();
+    }: _(RawOrigin::Root, amount)
 }
 
 #[cfg(test)]
@@ -50,6 +54,7 @@ mod tests {
         ExtBuilder::build().execute_with(|| {
             assert_ok!(test_benchmark_withdraw_polka_btc::<Test>());
             assert_ok!(test_benchmark_withdraw_dot::<Test>());
+            assert_ok!(test_benchmark_set_header_submission_reward::<Test>());
         });
     }
 }
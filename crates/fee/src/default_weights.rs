@@ -16,4 +16,7 @@ impl crate::WeightInfo for () {
             .saturating_add(DbWeight::get().reads(5 as Weight))
             .saturating_add(DbWeight::get().writes(4 as Weight))
     }
+    fn set_header_submission_reward() -> Weight {
+        (18_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
 }
@@ -33,7 +33,7 @@ use frame_support::{
     ensure,
     weights::Weight,
 };
-use frame_system::ensure_signed;
+use frame_system::{ensure_root, ensure_signed};
 use sp_arithmetic::traits::*;
 use sp_arithmetic::FixedPointNumber;
 use sp_std::convert::TryInto;
@@ -43,6 +43,7 @@ use types::{Inner, PolkaBTC, UnsignedFixedPoint, DOT};
 pub trait WeightInfo {
     fn withdraw_polka_btc() -> Weight;
     fn withdraw_dot() -> Weight;
+    fn set_header_submission_reward() -> Weight;
 }
 
 /// The pallet's configuration trait.
@@ -56,6 +57,36 @@ pub trait Config:
 
     /// Weight information for the extrinsics in this module.
     type WeightInfo: WeightInfo;
+
+    /// Handler sharing the rewards of staked relayers, e.g. with their nominators.
+    type RelayerRewards: ShareRelayerRewards<Self::AccountId, PolkaBTC<Self>, DOT<Self>>;
+}
+
+/// Shares the epoch rewards of a staked relayer with other accounts.
+pub trait ShareRelayerRewards<AccountId, PolkaBTC, DOT> {
+    /// Splits the rewards of the staked relayer into the rewards of each account.
+    ///
+    /// # Arguments
+    ///
+    /// * `relayer` - account of the staked relayer
+    /// * `amount_in_polka_btc` - PolkaBTC rewards of the staked relayer
+    /// * `amount_in_dot` - DOT rewards of the staked relayer
+    fn share_relayer_rewards(
+        relayer: &AccountId,
+        amount_in_polka_btc: PolkaBTC,
+        amount_in_dot: DOT,
+    ) -> Result<Vec<(AccountId, PolkaBTC, DOT)>, DispatchError>;
+}
+
+impl<AccountId: Clone, PolkaBTC, DOT> ShareRelayerRewards<AccountId, PolkaBTC, DOT> for () {
+    fn share_relayer_rewards(
+        relayer: &AccountId,
+        amount_in_polka_btc: PolkaBTC,
+        amount_in_dot: DOT,
+    ) -> Result<Vec<(AccountId, PolkaBTC, DOT)>, DispatchError> {
+        use sp_std::vec;
+        Ok(vec![(relayer.clone(), amount_in_polka_btc, amount_in_dot)])
+    }
 }
 
 // The pallet's storage items.
@@ -141,6 +172,10 @@ decl_storage! {
 
         // NOTE: currently there are no collator rewards
         CollatorRewards get(fn collator_rewards) config(): UnsignedFixedPoint<T>;
+
+        /// Reward in `PolkaBTC` paid from the fees of the current epoch to the
+        /// relayer of each new main chain block header. Disabled if zero.
+        HeaderSubmissionReward get(fn header_submission_reward) config(): PolkaBTC<T>;
     }
     add_extra_genesis {
         // don't allow an invalid reward distribution
@@ -174,6 +209,10 @@ decl_event!(
     {
         WithdrawPolkaBTC(AccountId, PolkaBTC),
         WithdrawDOT(AccountId, DOT),
+        /// Relayer rewarded for a new main chain block header [relayer, amount]
+        RewardHeaderSubmission(AccountId, PolkaBTC),
+        /// Header submission reward changed [amount]
+        SetHeaderSubmissionReward(PolkaBTC),
     }
 );
 
@@ -234,6 +273,22 @@ decl_module! {
             ));
             Ok(())
         }
+
+        /// Sets the reward paid to the relayer of each new main chain block header
+        /// (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - reward in PolkaBTC, zero disables the reward
+        #[weight = <T as Config>::WeightInfo::set_header_submission_reward()]
+        #[transactional]
+        fn set_header_submission_reward(origin, amount: PolkaBTC<T>) -> DispatchResult
+        {
+            ensure_root(origin)?;
+            <HeaderSubmissionReward<T>>::set(amount);
+            Self::deposit_event(<Event<T>>::SetHeaderSubmissionReward(amount));
+            Ok(())
+        }
     }
 }
 
@@ -286,24 +341,35 @@ impl<T: Config> Module<T> {
         // calculate staked relayer rewards
         let total_relayer_rewards_in_polka_btc = Self::relayer_rewards_for_epoch_in_polka_btc()?;
         let total_relayer_rewards_in_dot = Self::relayer_rewards_for_epoch_in_dot()?;
-        for (account, amount_in_polka_btc, amount_in_dot) in ext::sla::get_relayer_rewards::<T>(
-            total_relayer_rewards_in_polka_btc,
-            total_relayer_rewards_in_dot,
-        )? {
-            // increase polka_btc rewards
-            <TotalRewardsPolkaBTC<T>>::insert(
-                account.clone(),
-                <TotalRewardsPolkaBTC<T>>::get(account.clone())
-                    .checked_add(&amount_in_polka_btc)
-                    .ok_or(Error::<T>::ArithmeticOverflow)?,
-            );
-            // increase dot rewards
-            <TotalRewardsDOT<T>>::insert(
-                account.clone(),
-                <TotalRewardsDOT<T>>::get(account.clone())
-                    .checked_add(&amount_in_dot)
-                    .ok_or(Error::<T>::ArithmeticOverflow)?,
-            );
+        for (relayer, relayer_rewards_in_polka_btc, relayer_rewards_in_dot) in
+            ext::sla::get_relayer_rewards::<T>(
+                total_relayer_rewards_in_polka_btc,
+                total_relayer_rewards_in_dot,
+            )?
+        {
+            // share with nominators
+            for (account, amount_in_polka_btc, amount_in_dot) in
+                T::RelayerRewards::share_relayer_rewards(
+                    &relayer,
+                    relayer_rewards_in_polka_btc,
+                    relayer_rewards_in_dot,
+                )?
+            {
+                // increase polka_btc rewards
+                <TotalRewardsPolkaBTC<T>>::insert(
+                    account.clone(),
+                    <TotalRewardsPolkaBTC<T>>::get(account.clone())
+                        .checked_add(&amount_in_polka_btc)
+                        .ok_or(Error::<T>::ArithmeticOverflow)?,
+                );
+                // increase dot rewards
+                <TotalRewardsDOT<T>>::insert(
+                    account.clone(),
+                    <TotalRewardsDOT<T>>::get(account.clone())
+                        .checked_add(&amount_in_dot)
+                        .ok_or(Error::<T>::ArithmeticOverflow)?,
+                );
+            }
         }
 
         // calculate maintainer rewards
@@ -350,6 +416,35 @@ impl<T: Config> Module<T> {
         <EpochRewardsDOT<T>>::set(Self::epoch_rewards_dot() + amount);
     }
 
+    /// Rewards the relayer of a new main chain block header with the
+    /// `HeaderSubmissionReward`, paid from the PolkaBTC rewards of the
+    /// current epoch. The reward is capped by the remaining epoch rewards.
+    ///
+    /// # Arguments
+    ///
+    /// * `relayer` - account of the relayer
+    pub fn reward_header_submission(relayer: &T::AccountId) -> DispatchResult {
+        let epoch_rewards = Self::epoch_rewards_polka_btc();
+        let reward = Self::header_submission_reward().min(epoch_rewards);
+        if reward.is_zero() {
+            return Ok(());
+        }
+
+        <EpochRewardsPolkaBTC<T>>::set(
+            epoch_rewards
+                .checked_sub(&reward)
+                .ok_or(Error::<T>::ArithmeticUnderflow)?,
+        );
+        <TotalRewardsPolkaBTC<T>>::insert(
+            relayer,
+            <TotalRewardsPolkaBTC<T>>::get(relayer)
+                .checked_add(&reward)
+                .ok_or(Error::<T>::ArithmeticOverflow)?,
+        );
+        Self::deposit_event(<Event<T>>::RewardHeaderSubmission(relayer.clone(), reward));
+        Ok(())
+    }
+
     pub fn get_polka_btc_rewards(account_id: &T::AccountId) -> PolkaBTC<T> {
         <TotalRewardsPolkaBTC<T>>::get(account_id)
     }
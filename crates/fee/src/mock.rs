@@ -143,8 +143,14 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 100;
+}
+
 impl security::Config for Test {
     type Event = TestEvent;
+    type MaxStatusHistory = MaxStatusHistory;
+    type RandomnessSource = pallet_randomness_collective_flip::Module<Test>;
 }
 
 impl sla::Config for Test {
@@ -156,6 +162,7 @@ impl Config for Test {
     type Event = TestEvent;
     type UnsignedFixedPoint = FixedU128;
     type WeightInfo = ();
+    type RelayerRewards = ();
 }
 
 pub type TestEvent = Event;
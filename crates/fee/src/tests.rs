@@ -186,3 +186,60 @@ fn test_withdraw_dot_succeeds() {
         assert_emitted!(Event::WithdrawDOT(0, 1000));
     })
 }
+
+#[test]
+fn test_reward_header_submission_succeeds() {
+    run_test(|| {
+        <EpochRewardsPolkaBTC<Test>>::set(100);
+        <HeaderSubmissionReward<Test>>::set(30);
+        assert_ok!(Fee::reward_header_submission(&1));
+        assert_eq!(<EpochRewardsPolkaBTC<Test>>::get(), 70);
+        assert_eq!(Fee::get_polka_btc_rewards(&1), 30);
+        assert_emitted!(Event::RewardHeaderSubmission(1, 30));
+    })
+}
+
+#[test]
+fn test_reward_header_submission_is_capped_by_epoch_rewards() {
+    run_test(|| {
+        <EpochRewardsPolkaBTC<Test>>::set(20);
+        <HeaderSubmissionReward<Test>>::set(30);
+        assert_ok!(Fee::reward_header_submission(&1));
+        assert_eq!(<EpochRewardsPolkaBTC<Test>>::get(), 0);
+        assert_eq!(Fee::get_polka_btc_rewards(&1), 20);
+    })
+}
+
+#[test]
+fn test_reward_header_submission_disabled_without_reward() {
+    run_test(|| {
+        <EpochRewardsPolkaBTC<Test>>::set(100);
+        assert_ok!(Fee::reward_header_submission(&1));
+        assert_eq!(<EpochRewardsPolkaBTC<Test>>::get(), 100);
+        assert_eq!(Fee::get_polka_btc_rewards(&1), 0);
+        assert!(System::events().is_empty());
+    })
+}
+
+#[test]
+fn test_set_header_submission_reward_requires_root() {
+    run_test(|| {
+        assert_err!(
+            Fee::set_header_submission_reward(Origin::signed(0), 30),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(Fee::header_submission_reward(), 0);
+    })
+}
+
+#[test]
+fn test_set_header_submission_reward_succeeds() {
+    run_test(|| {
+        assert_ok!(Fee::set_header_submission_reward(
+            frame_system::RawOrigin::Root.into(),
+            30
+        ));
+        assert_eq!(Fee::header_submission_reward(), 30);
+        assert_emitted!(Event::SetHeaderSubmissionReward(30));
+    })
+}
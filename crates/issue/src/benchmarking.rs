@@ -111,7 +111,7 @@ benchmarks! {
         let raw_tx = transaction.format_with(true);
 
         let block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
-        BtcRelay::<T>::_store_block_header(relayer_id, block_header).unwrap();
+        BtcRelay::<T>::_store_block_header(relayer_id, block_header, u32::MAX).unwrap();
 
         let mut vault = Vault::default();
         vault.id = vault_id.clone();
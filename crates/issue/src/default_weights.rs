@@ -8,8 +8,8 @@ use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 impl crate::WeightInfo for () {
     fn request_issue() -> Weight {
         (452_088_000 as Weight)
-            .saturating_add(DbWeight::get().reads(13 as Weight))
-            .saturating_add(DbWeight::get().writes(5 as Weight))
+            .saturating_add(DbWeight::get().reads(15 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
     }
     fn execute_issue() -> Weight {
         (211_260_000 as Weight)
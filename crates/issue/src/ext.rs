@@ -4,8 +4,8 @@ use mocktopus::macros::mockable;
 #[cfg_attr(test, mockable)]
 pub(crate) mod btc_relay {
     use bitcoin::types::H256Le;
-    use btc_relay::BtcAddress;
-    use frame_support::dispatch::DispatchError;
+    use btc_relay::{BtcAddress, TransactionConsumer};
+    use frame_support::dispatch::{DispatchError, DispatchResult};
     use sp_std::vec::Vec;
 
     pub fn verify_transaction_inclusion<T: btc_relay::Config>(
@@ -23,6 +23,24 @@ pub(crate) mod btc_relay {
     ) -> Result<(BtcAddress, i64), DispatchError> {
         <btc_relay::Module<T>>::_validate_transaction(raw_tx, amount, btc_address, issue_id)
     }
+
+    pub fn use_transaction<T: btc_relay::Config>(
+        tx_id: H256Le,
+        merkle_proof: Vec<u8>,
+        raw_tx: Vec<u8>,
+        btc_address: BtcAddress,
+        issue_id: Option<Vec<u8>>,
+        consumer: TransactionConsumer,
+    ) -> DispatchResult {
+        <btc_relay::Module<T>>::_use_transaction(
+            tx_id,
+            merkle_proof,
+            raw_tx,
+            btc_address,
+            issue_id,
+            consumer,
+        )
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -119,6 +137,7 @@ pub(crate) mod treasury {
 pub(crate) mod security {
     use frame_support::dispatch::DispatchResult;
     use primitive_types::H256;
+    use security::{Operation, VolumeType};
 
     pub fn get_secure_id<T: security::Config>(id: &T::AccountId) -> H256 {
         <security::Module<T>>::get_secure_id(id)
@@ -127,6 +146,19 @@ pub(crate) mod security {
     pub fn ensure_parachain_status_running<T: security::Config>() -> DispatchResult {
         <security::Module<T>>::ensure_parachain_status_running()
     }
+
+    pub fn ensure_operation_not_paused<T: security::Config>(
+        operation: Operation,
+    ) -> DispatchResult {
+        <security::Module<T>>::ensure_operation_not_paused(operation)
+    }
+
+    pub fn increase_volume<T: security::Config>(
+        volume_type: VolumeType,
+        amount: u128,
+    ) -> DispatchResult {
+        <security::Module<T>>::increase_volume(volume_type, amount)
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -30,7 +30,7 @@ pub use crate::types::IssueRequest;
 
 use crate::types::{PolkaBTC, Version, DOT};
 use bitcoin::types::H256Le;
-use btc_relay::{BtcAddress, BtcPublicKey};
+use btc_relay::{BtcAddress, BtcPublicKey, TransactionConsumer};
 use frame_support::transactional;
 use frame_support::weights::Weight;
 use frame_support::{
@@ -40,6 +40,7 @@ use frame_support::{
 };
 use frame_system::{ensure_root, ensure_signed};
 use primitive_types::H256;
+use security::{Operation, VolumeType};
 use sp_runtime::ModuleId;
 use sp_std::convert::TryInto;
 use sp_std::vec::Vec;
@@ -110,6 +111,8 @@ decl_event!(
         ),
         // [issue_id, requester, total_amount, vault]
         ExecuteIssue(H256, AccountId, PolkaBTC, AccountId),
+        // [issue_id, reorged_block_hash]
+        IssueReorged(H256, H256Le),
         // [issue_id, requester, griefing_collateral]
         CancelIssue(H256, AccountId, DOT),
     }
@@ -213,6 +216,7 @@ impl<T: Config> Module<T> {
     ) -> Result<H256, DispatchError> {
         // Check that Parachain is RUNNING
         ext::security::ensure_parachain_status_running::<T>()?;
+        ext::security::ensure_operation_not_paused::<T>(Operation::IssueRequest)?;
 
         let height = <frame_system::Module<T>>::block_number();
         let vault = ext::vault_registry::get_active_vault_from_id::<T>(&vault_id)?;
@@ -227,6 +231,11 @@ impl<T: Config> Module<T> {
             griefing_collateral >= expected_griefing_collateral,
             Error::<T>::InsufficientCollateral
         );
+
+        let volume =
+            TryInto::<u128>::try_into(amount_polkabtc).map_err(|_e| Error::<T>::TryIntoIntError)?;
+        ext::security::increase_volume::<T>(VolumeType::Issue, volume)?;
+
         ext::collateral::lock_collateral::<T>(&requester, griefing_collateral)?;
 
         let fee_polkabtc = ext::fee::get_issue_fee::<T>(amount_polkabtc)?;
@@ -277,6 +286,7 @@ impl<T: Config> Module<T> {
     ) -> Result<(), DispatchError> {
         // Check that Parachain is RUNNING
         ext::security::ensure_parachain_status_running::<T>()?;
+        ext::security::ensure_operation_not_paused::<T>(Operation::IssueExecute)?;
 
         let mut issue = Self::get_issue_request_from_id(&issue_id)?;
         // allow anyone to complete issue request
@@ -289,14 +299,22 @@ impl<T: Config> Module<T> {
         );
 
         let mut total_amount = issue.amount + issue.fee;
-        ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof)?;
+        ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof.clone())?;
         let (refund_address, amount_transferred) = ext::btc_relay::validate_transaction::<T>(
-            raw_tx,
+            raw_tx.clone(),
             TryInto::<u64>::try_into(total_amount).map_err(|_e| Error::<T>::TryIntoIntError)?
                 as i64,
             issue.btc_address,
             None,
         )?;
+        ext::btc_relay::use_transaction::<T>(
+            tx_id,
+            merkle_proof,
+            raw_tx,
+            issue.btc_address,
+            None,
+            TransactionConsumer::Issue(issue_id),
+        )?;
         let amount_transferred = Self::u128_to_btc(amount_transferred as u128)?;
 
         if amount_transferred > total_amount {
@@ -471,6 +489,16 @@ impl<T: Config> Module<T> {
     }
 }
 
+impl<T: Config> btc_relay::OnReorg for Module<T> {
+    /// Notifies monitoring about executed issue requests whose Bitcoin
+    /// transaction was included in a reorged out block.
+    fn on_reorg(consumer: TransactionConsumer, block_hash: H256Le) {
+        if let TransactionConsumer::Issue(issue_id) = consumer {
+            Self::deposit_event(<Event<T>>::IssueReorged(issue_id, block_hash));
+        }
+    }
+}
+
 fn has_request_expired<T: Config>(opentime: T::BlockNumber, period: T::BlockNumber) -> bool {
     let height = <frame_system::Module<T>>::block_number();
     height > opentime + period
@@ -30,7 +30,7 @@ frame_support::construct_runtime!(
         Treasury: treasury::{Module, Call, Storage, Event<T>},
 
         // Operational
-        BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event},
+        BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event<T>},
         Security: security::{Module, Call, Storage, Event},
         VaultRegistry: vault_registry::{Module, Call, Config<T>, Storage, Event<T>},
         ExchangeRateOracle: exchange_rate_oracle::{Module, Call, Config<T>, Storage, Event<T>},
@@ -124,13 +124,26 @@ impl collateral::Config for Test {
     type DOT = pallet_balances::Module<Test, pallet_balances::Instance1>;
 }
 
+parameter_types! {
+    pub BitcoinMainnet: btc_relay::NetworkParameters = btc_relay::BitcoinNetwork::Mainnet.parameters();
+}
+
 impl btc_relay::Config for Test {
     type Event = TestEvent;
     type WeightInfo = ();
+    type Network = BitcoinMainnet;
+    type OnReorg = ();
+    type OffchainWorker = ();
+}
+
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 100;
 }
 
 impl security::Config for Test {
     type Event = TestEvent;
+    type MaxStatusHistory = MaxStatusHistory;
+    type RandomnessSource = pallet_randomness_collective_flip::Module<Test>;
 }
 
 impl treasury::Config for Test {
@@ -164,6 +177,7 @@ impl fee::Config for Test {
     type Event = TestEvent;
     type UnsignedFixedPoint = FixedU128;
     type WeightInfo = ();
+    type RelayerRewards = ();
 }
 
 impl sla::Config for Test {
@@ -219,6 +233,7 @@ impl ExtBuilder {
             relayer_rewards: FixedU128::checked_from_rational(3, 100).unwrap(),
             maintainer_rewards: FixedU128::checked_from_rational(20, 100).unwrap(),
             collator_rewards: FixedU128::checked_from_integer(0).unwrap(),
+            header_submission_reward: 0,
         }
         .assimilate_storage(&mut storage)
         .unwrap();
@@ -3,7 +3,7 @@ use crate::PolkaBTC;
 use crate::RawEvent;
 use crate::{ext, has_request_expired, Config};
 use bitcoin::types::H256Le;
-use btc_relay::{BtcAddress, BtcPublicKey};
+use btc_relay::{BtcAddress, BtcPublicKey, OnReorg, TransactionConsumer};
 use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
 use mocktopus::mocking::*;
 use primitive_types::H256;
@@ -69,6 +69,9 @@ fn execute_issue_ok(origin: AccountId, issue_id: &H256) {
     ext::btc_relay::validate_transaction::<Test>
         .mock_safe(|_, _, _, _| MockResult::Return(Ok((BtcAddress::P2SH(H160::zero()), 0))));
 
+    ext::btc_relay::use_transaction::<Test>
+        .mock_safe(|_, _, _, _, _, _| MockResult::Return(Ok(())));
+
     assert_ok!(execute_issue(origin, issue_id));
 }
 
@@ -129,6 +132,66 @@ fn test_request_issue_insufficient_collateral_fails() {
     })
 }
 
+#[test]
+fn test_request_issue_paused_fails() {
+    run_test(|| {
+        assert_ok!(<security::Module<Test>>::pause_operation(
+            Origin::root(),
+            security::Operation::IssueRequest
+        ));
+
+        assert_noop!(
+            request_issue(ALICE, 3, BOB, 0),
+            SecurityError::OperationPaused,
+        );
+    })
+}
+
+#[test]
+fn test_execute_issue_paused_fails() {
+    run_test(|| {
+        ext::vault_registry::get_active_vault_from_id::<Test>
+            .mock_safe(|_| MockResult::Return(Ok(init_zero_vault::<Test>(BOB))));
+
+        let issue_id = request_issue_ok(ALICE, 3, BOB, 20);
+        assert_ok!(<security::Module<Test>>::pause_operation(
+            Origin::root(),
+            security::Operation::IssueExecute
+        ));
+        assert_noop!(
+            execute_issue(ALICE, &issue_id),
+            SecurityError::OperationPaused
+        );
+    })
+}
+
+#[test]
+fn test_request_issue_fails_with_volume_limit_exceeded() {
+    run_test(|| {
+        ext::vault_registry::get_active_vault_from_id::<Test>
+            .mock_safe(|_| MockResult::Return(Ok(init_zero_vault::<Test>(BOB))));
+        ext::vault_registry::ensure_not_banned::<Test>.mock_safe(|_, _| MockResult::Return(Ok(())));
+        assert_ok!(<security::Module<Test>>::set_volume_limit(
+            Origin::root(),
+            security::VolumeType::Issue,
+            Some(security::VolumeLimit {
+                max_volume: 5,
+                window: 10,
+            })
+        ));
+
+        request_issue_ok(ALICE, 3, BOB, 20);
+        assert_noop!(
+            request_issue(ALICE, 3, BOB, 20),
+            SecurityError::VolumeLimitExceeded,
+        );
+        assert_eq!(
+            <security::Module<Test>>::get_remaining_volume(security::VolumeType::Issue),
+            Some(2)
+        );
+    })
+}
+
 #[test]
 fn test_request_issue_succeeds() {
     run_test(|| {
@@ -230,6 +293,8 @@ fn test_execute_issue_overpayment_succeeds() {
         // pay 5 instead of the expected 3
         ext::btc_relay::validate_transaction::<Test>
             .mock_safe(|_, _, _, _| MockResult::Return(Ok((BtcAddress::P2SH(H160::zero()), 5))));
+        ext::btc_relay::use_transaction::<Test>
+            .mock_safe(|_, _, _, _, _, _| MockResult::Return(Ok(())));
 
         unsafe {
             let mut increase_tokens_called = false;
@@ -274,6 +339,8 @@ fn test_execute_issue_refund_succeeds() {
         // pay 103 instead of the expected 3
         ext::btc_relay::validate_transaction::<Test>
             .mock_safe(|_, _, _, _| MockResult::Return(Ok((BtcAddress::P2SH(H160::zero()), 103))));
+        ext::btc_relay::use_transaction::<Test>
+            .mock_safe(|_, _, _, _, _, _| MockResult::Return(Ok(())));
 
         // return some arbitrary error
         ext::vault_registry::increase_to_be_issued_tokens::<Test>.mock_safe(|_, _, amount| {
@@ -394,3 +461,24 @@ fn test_has_request_expired() {
         assert!(!has_request_expired::<Test>(30, 24));
     })
 }
+
+#[test]
+fn test_on_reorg_notifies_reorged_issue() {
+    run_test(|| {
+        let issue_id = H256::from_low_u64_be(1);
+        let block_hash = H256Le::from_bytes_le(&[1; 32]);
+
+        <Issue as OnReorg>::on_reorg(TransactionConsumer::Redeem(issue_id), block_hash);
+        <Issue as OnReorg>::on_reorg(TransactionConsumer::Issue(issue_id), block_hash);
+
+        let reorged_events: Vec<_> = System::events()
+            .into_iter()
+            .filter(|a| matches!(a.event, TestEvent::issue(RawEvent::IssueReorged(..))))
+            .collect();
+        assert_eq!(reorged_events.len(), 1);
+        assert_eq!(
+            reorged_events[0].event,
+            TestEvent::issue(RawEvent::IssueReorged(issue_id, block_hash))
+        );
+    })
+}
@@ -9,6 +9,7 @@ use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::transactional;
 use frame_support::{decl_error, decl_event, decl_module, decl_storage, traits::Get};
 use frame_system::ensure_signed;
+use security::Operation;
 use sp_runtime::traits::Convert;
 use sp_std::{convert::TryInto, prelude::*};
 pub use types::{CurrencyAdapter, CurrencyId, NativeAsset};
@@ -17,7 +18,9 @@ use xcm::v0::{Error as XcmError, ExecuteXcm, Junction::*, MultiAsset, NetworkId,
 use xcm_executor::traits::LocationConversion;
 
 /// Configuration trait of this pallet.
-pub trait Config: frame_system::Config + collateral::Config + treasury::Config {
+pub trait Config:
+    frame_system::Config + collateral::Config + security::Config + treasury::Config
+{
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
 
@@ -70,6 +73,7 @@ decl_module! {
             amount: DOT<T>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            <security::Module<T>>::ensure_operation_not_paused(Operation::XcmTransfer)?;
 
             if para_id == T::ParaId::get() {
                 return Ok(());
@@ -111,6 +115,7 @@ decl_module! {
             amount: PolkaBTC<T>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            <security::Module<T>>::ensure_operation_not_paused(Operation::XcmTransfer)?;
 
             if para_id == T::ParaId::get() {
                 return Ok(());
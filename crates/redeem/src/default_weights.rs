@@ -8,8 +8,8 @@ use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 impl crate::WeightInfo for () {
     fn request_redeem() -> Weight {
         (179_175_000 as Weight)
-            .saturating_add(DbWeight::get().reads(12 as Weight))
-            .saturating_add(DbWeight::get().writes(5 as Weight))
+            .saturating_add(DbWeight::get().reads(14 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
     }
     fn liquidation_redeem() -> Weight {
         (179_175_000 as Weight)
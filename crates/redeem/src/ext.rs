@@ -4,7 +4,7 @@ use mocktopus::macros::mockable;
 #[cfg_attr(test, mockable)]
 pub(crate) mod btc_relay {
     use bitcoin::types::H256Le;
-    use btc_relay::BtcAddress;
+    use btc_relay::{BtcAddress, TransactionConsumer};
     use frame_support::dispatch::{DispatchError, DispatchResult};
     use sp_std::vec::Vec;
 
@@ -23,6 +23,24 @@ pub(crate) mod btc_relay {
     ) -> Result<(BtcAddress, i64), DispatchError> {
         <btc_relay::Module<T>>::_validate_transaction(raw_tx, amount, btc_address, redeem_id)
     }
+
+    pub fn use_transaction<T: btc_relay::Config>(
+        tx_id: H256Le,
+        merkle_proof: Vec<u8>,
+        raw_tx: Vec<u8>,
+        btc_address: BtcAddress,
+        redeem_id: Option<Vec<u8>>,
+        consumer: TransactionConsumer,
+    ) -> DispatchResult {
+        <btc_relay::Module<T>>::_use_transaction(
+            tx_id,
+            merkle_proof,
+            raw_tx,
+            btc_address,
+            redeem_id,
+            consumer,
+        )
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -237,7 +255,7 @@ pub(crate) mod treasury {
 pub(crate) mod security {
     use frame_support::dispatch::DispatchResult;
     use primitive_types::H256;
-    use security::ErrorCode;
+    use security::{ErrorCode, Operation, VolumeType};
     use sp_std::vec::Vec;
 
     pub fn get_secure_id<T: security::Config>(id: &T::AccountId) -> H256 {
@@ -249,6 +267,19 @@ pub(crate) mod security {
     ) -> DispatchResult {
         <security::Module<T>>::ensure_parachain_is_running_or_only_has_errors(error_codes)
     }
+
+    pub fn ensure_operation_not_paused<T: security::Config>(
+        operation: Operation,
+    ) -> DispatchResult {
+        <security::Module<T>>::ensure_operation_not_paused(operation)
+    }
+
+    pub fn increase_volume<T: security::Config>(
+        volume_type: VolumeType,
+        amount: u128,
+    ) -> DispatchResult {
+        <security::Module<T>>::increase_volume(volume_type, amount)
+    }
 }
 
 #[cfg_attr(test, mockable)]
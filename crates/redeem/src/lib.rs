@@ -30,7 +30,7 @@ pub use crate::types::RedeemRequest;
 
 use crate::types::{PolkaBTC, Version, DOT};
 use bitcoin::types::H256Le;
-use btc_relay::BtcAddress;
+use btc_relay::{BtcAddress, TransactionConsumer};
 use frame_support::transactional;
 use frame_support::weights::Weight;
 use frame_support::{
@@ -40,7 +40,7 @@ use frame_support::{
 };
 use frame_system::{ensure_root, ensure_signed};
 use primitive_types::H256;
-use security::ErrorCode;
+use security::{ErrorCode, Operation, VolumeType};
 use sp_runtime::traits::CheckedAdd;
 use sp_runtime::traits::*;
 use sp_runtime::ModuleId;
@@ -116,6 +116,8 @@ decl_event!(
         LiquidationRedeem(AccountId, PolkaBTC),
         // [redeem_id, redeemer, amount_polka_btc, fee_polka_btc, vault]
         ExecuteRedeem(H256, AccountId, PolkaBTC, PolkaBTC, AccountId),
+        // [redeem_id, reorged_block_hash]
+        RedeemReorged(H256, H256Le),
         // [redeem_id, redeemer, vault_id, slashing_amount_in_dot, reimburse]
         CancelRedeem(H256, AccountId, AccountId, DOT, bool),
     }
@@ -264,6 +266,10 @@ impl<T: Config> Module<T> {
             Error::<T>::AmountBelowDustAmount
         );
 
+        let volume = TryInto::<u128>::try_into(amount_polka_btc)
+            .map_err(|_e| Error::<T>::TryIntoIntError)?;
+        ext::security::increase_volume::<T>(VolumeType::Redeem, volume)?;
+
         ext::vault_registry::increase_to_be_redeemed_tokens::<T>(
             &vault_id,
             redeem_amount_polka_btc,
@@ -360,15 +366,23 @@ impl<T: Config> Module<T> {
             .amount_btc
             .try_into()
             .map_err(|_e| Error::<T>::TryIntoIntError)?;
-        ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof)?;
+        ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof.clone())?;
         // NOTE: vault client must register change addresses before
         // sending the bitcoin transaction
         ext::btc_relay::validate_transaction::<T>(
-            raw_tx,
+            raw_tx.clone(),
             amount as i64,
             redeem.btc_address,
             Some(redeem_id.clone().as_bytes().to_vec()),
         )?;
+        ext::btc_relay::use_transaction::<T>(
+            tx_id,
+            merkle_proof,
+            raw_tx,
+            redeem.btc_address,
+            Some(redeem_id.clone().as_bytes().to_vec()),
+            TransactionConsumer::Redeem(redeem_id),
+        )?;
 
         let amount_polka_btc = redeem.amount_polka_btc;
         let fee_polka_btc = redeem.fee;
@@ -557,11 +571,13 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
-    /// Ensure that the parachain is running or the system is in liquidation
+    /// Ensure that the parachain is running or the system is in liquidation,
+    /// and that redeeming is not paused
     fn ensure_parachain_running_or_error_liquidated() -> DispatchResult {
         ext::security::ensure_parachain_is_running_or_only_has_errors::<T>(
             [ErrorCode::Liquidation].to_vec(),
-        )
+        )?;
+        ext::security::ensure_operation_not_paused::<T>(Operation::Redeem)
     }
 
     /// Insert a new redeem request into state.
@@ -670,6 +686,16 @@ impl<T: Config> Module<T> {
     }
 }
 
+impl<T: Config> btc_relay::OnReorg for Module<T> {
+    /// Notifies monitoring about executed redeem requests whose Bitcoin
+    /// transaction was included in a reorged out block.
+    fn on_reorg(consumer: TransactionConsumer, block_hash: H256Le) {
+        if let TransactionConsumer::Redeem(redeem_id) = consumer {
+            Self::deposit_event(<Event<T>>::RedeemReorged(redeem_id, block_hash));
+        }
+    }
+}
+
 fn has_request_expired<T: Config>(opentime: T::BlockNumber, period: T::BlockNumber) -> bool {
     let height = <frame_system::Module<T>>::block_number();
     height > opentime + period
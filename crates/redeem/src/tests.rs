@@ -419,6 +419,8 @@ fn test_execute_redeem_succeeds() {
             .mock_safe(|_, _| MockResult::Return(Ok(())));
         ext::btc_relay::validate_transaction::<Test>
             .mock_safe(|_, _, _, _| MockResult::Return(Ok((BtcAddress::P2SH(H160::zero()), 0))));
+        ext::btc_relay::use_transaction::<Test>
+            .mock_safe(|_, _, _, _, _, _| MockResult::Return(Ok(())));
 
         inject_redeem_request(
             H256([0u8; 32]),
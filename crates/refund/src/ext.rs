@@ -30,7 +30,7 @@ pub(crate) mod sla {
 #[cfg_attr(test, mockable)]
 pub(crate) mod btc_relay {
     use bitcoin::types::H256Le;
-    use btc_relay::BtcAddress;
+    use btc_relay::{BtcAddress, TransactionConsumer};
     use frame_support::dispatch::{DispatchError, DispatchResult};
     use sp_std::vec::Vec;
 
@@ -48,15 +48,41 @@ pub(crate) mod btc_relay {
     ) -> Result<(BtcAddress, i64), DispatchError> {
         <btc_relay::Module<T>>::_validate_transaction(raw_tx, amount, btc_address, refund_id)
     }
+
+    pub fn use_transaction<T: btc_relay::Config>(
+        tx_id: H256Le,
+        merkle_proof: Vec<u8>,
+        raw_tx: Vec<u8>,
+        btc_address: BtcAddress,
+        refund_id: Option<Vec<u8>>,
+        consumer: TransactionConsumer,
+    ) -> DispatchResult {
+        <btc_relay::Module<T>>::_use_transaction(
+            tx_id,
+            merkle_proof,
+            raw_tx,
+            btc_address,
+            refund_id,
+            consumer,
+        )
+    }
 }
 
 #[cfg_attr(test, mockable)]
 pub(crate) mod security {
+    use frame_support::dispatch::DispatchResult;
     use primitive_types::H256;
+    use security::Operation;
 
     pub fn get_secure_id<T: security::Config>(id: &T::AccountId) -> H256 {
         <security::Module<T>>::get_secure_id(id)
     }
+
+    pub fn ensure_operation_not_paused<T: security::Config>(
+        operation: Operation,
+    ) -> DispatchResult {
+        <security::Module<T>>::ensure_operation_not_paused(operation)
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -21,7 +21,7 @@ mod ext;
 pub mod types;
 
 use bitcoin::types::H256Le;
-use btc_relay::BtcAddress;
+use btc_relay::{BtcAddress, TransactionConsumer};
 use frame_support::transactional;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchError, ensure,
@@ -29,6 +29,7 @@ use frame_support::{
 };
 use frame_system::ensure_signed;
 use primitive_types::H256;
+use security::Operation;
 use sp_runtime::traits::CheckedSub;
 use sp_std::convert::TryInto;
 use sp_std::vec::Vec;
@@ -78,6 +79,8 @@ decl_event!(
         RequestRefund(H256, AccountId, PolkaBTC, AccountId, BtcAddress, H256),
         /// refund_id, issuer, vault, amount
         ExecuteRefund(H256, AccountId, AccountId, PolkaBTC),
+        /// refund_id, reorged_block_hash
+        RefundReorged(H256, H256Le),
     }
 );
 
@@ -180,6 +183,8 @@ impl<T: Config> Module<T> {
         merkle_proof: Vec<u8>,
         raw_tx: Vec<u8>,
     ) -> Result<(), DispatchError> {
+        ext::security::ensure_operation_not_paused::<T>(Operation::Refund)?;
+
         let request = Self::get_open_refund_request_from_id(&refund_id)?;
 
         // verify the payment
@@ -187,13 +192,21 @@ impl<T: Config> Module<T> {
             .amount_polka_btc
             .try_into()
             .map_err(|_e| Error::<T>::TryIntoIntError)?;
-        ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof)?;
+        ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof.clone())?;
         ext::btc_relay::validate_transaction::<T>(
-            raw_tx,
+            raw_tx.clone(),
             amount as i64,
             request.btc_address,
             Some(refund_id.as_bytes().to_vec()),
         )?;
+        ext::btc_relay::use_transaction::<T>(
+            tx_id,
+            merkle_proof,
+            raw_tx,
+            request.btc_address,
+            Some(refund_id.as_bytes().to_vec()),
+            TransactionConsumer::Refund(refund_id),
+        )?;
 
         // mint polkabtc corresponding to the fee
         ext::treasury::mint::<T>(request.vault.clone(), request.fee);
@@ -297,6 +310,16 @@ impl<T: Config> Module<T> {
     }
 }
 
+impl<T: Config> btc_relay::OnReorg for Module<T> {
+    /// Notifies monitoring about executed refund requests whose Bitcoin
+    /// transaction was included in a reorged out block.
+    fn on_reorg(consumer: TransactionConsumer, block_hash: H256Le) {
+        if let TransactionConsumer::Refund(refund_id) = consumer {
+            Self::deposit_event(<Event<T>>::RefundReorged(refund_id, block_hash));
+        }
+    }
+}
+
 decl_error! {
     pub enum Error for Module<T: Config> {
         ArithmeticUnderflow,
@@ -33,7 +33,7 @@ frame_support::construct_runtime!(
         Treasury: treasury::{Module, Call, Storage, Event<T>},
 
         // Operational
-        BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event},
+        BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event<T>},
         Security: security::{Module, Call, Storage, Event},
         VaultRegistry: vault_registry::{Module, Call, Config<T>, Storage, Event<T>},
         ExchangeRateOracle: exchange_rate_oracle::{Module, Call, Config<T>, Storage, Event<T>},
@@ -128,6 +128,7 @@ impl fee::Config for Test {
     type Event = TestEvent;
     type UnsignedFixedPoint = FixedU128;
     type WeightInfo = ();
+    type RelayerRewards = ();
 }
 
 impl sla::Config for Test {
@@ -140,13 +141,26 @@ impl collateral::Config for Test {
     type DOT = pallet_balances::Module<Test, pallet_balances::Instance1>;
 }
 
+parameter_types! {
+    pub BitcoinMainnet: btc_relay::NetworkParameters = btc_relay::BitcoinNetwork::Mainnet.parameters();
+}
+
 impl btc_relay::Config for Test {
     type Event = TestEvent;
     type WeightInfo = ();
+    type Network = BitcoinMainnet;
+    type OnReorg = ();
+    type OffchainWorker = ();
+}
+
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 100;
 }
 
 impl security::Config for Test {
     type Event = TestEvent;
+    type MaxStatusHistory = MaxStatusHistory;
+    type RandomnessSource = pallet_randomness_collective_flip::Module<Test>;
 }
 
 impl vault_registry::Config for Test {
@@ -16,6 +16,8 @@ fn test_refund_succeeds() {
             .mock_safe(|_, _| MockResult::Return(Ok(())));
         ext::btc_relay::validate_transaction::<Test>
             .mock_safe(|_, _, _, _| MockResult::Return(Ok((BtcAddress::P2SH(H160::zero()), 995))));
+        ext::btc_relay::use_transaction::<Test>
+            .mock_safe(|_, _, _, _, _, _| MockResult::Return(Ok(())));
 
         let issue_id = H256::zero();
         assert_ok!(Refund::request_refund(
@@ -203,7 +203,7 @@ benchmarks! {
         let raw_tx = transaction.format_with(true);
 
         let block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
-        BtcRelay::<T>::_store_block_header(relayer_id, block_header).unwrap();
+        BtcRelay::<T>::_store_block_header(relayer_id, block_header, u32::MAX).unwrap();
 
     }: _(RawOrigin::Signed(old_vault_id), replace_id, tx_id, proof, raw_tx)
 
@@ -4,7 +4,7 @@ use mocktopus::macros::mockable;
 #[cfg_attr(test, mockable)]
 pub(crate) mod btc_relay {
     use bitcoin::types::H256Le;
-    use btc_relay::BtcAddress;
+    use btc_relay::{BtcAddress, TransactionConsumer};
     use frame_support::dispatch::{DispatchError, DispatchResult};
     use sp_std::vec::Vec;
 
@@ -23,6 +23,24 @@ pub(crate) mod btc_relay {
     ) -> Result<(BtcAddress, i64), DispatchError> {
         <btc_relay::Module<T>>::_validate_transaction(raw_tx, amount, btc_address, replace_id)
     }
+
+    pub fn use_transaction<T: btc_relay::Config>(
+        tx_id: H256Le,
+        merkle_proof: Vec<u8>,
+        raw_tx: Vec<u8>,
+        btc_address: BtcAddress,
+        replace_id: Option<Vec<u8>>,
+        consumer: TransactionConsumer,
+    ) -> DispatchResult {
+        <btc_relay::Module<T>>::_use_transaction(
+            tx_id,
+            merkle_proof,
+            raw_tx,
+            btc_address,
+            replace_id,
+            consumer,
+        )
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -136,6 +154,7 @@ pub(crate) mod collateral {
 pub(crate) mod security {
     use frame_support::dispatch::DispatchResult;
     use primitive_types::H256;
+    use security::Operation;
 
     pub fn get_secure_id<T: security::Config>(id: &T::AccountId) -> H256 {
         <security::Module<T>>::get_secure_id(id)
@@ -144,6 +163,12 @@ pub(crate) mod security {
     pub fn ensure_parachain_status_running<T: security::Config>() -> DispatchResult {
         <security::Module<T>>::ensure_parachain_status_running()
     }
+
+    pub fn ensure_operation_not_paused<T: security::Config>(
+        operation: Operation,
+    ) -> DispatchResult {
+        <security::Module<T>>::ensure_operation_not_paused(operation)
+    }
 }
 
 #[cfg_attr(test, mockable)]
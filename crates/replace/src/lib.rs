@@ -24,12 +24,13 @@ use frame_system::{ensure_root, ensure_signed};
 #[cfg(test)]
 use mocktopus::macros::mockable;
 use primitive_types::H256;
+use security::Operation;
 use sp_runtime::ModuleId;
 use sp_std::convert::TryInto;
 use sp_std::vec::Vec;
 
 use bitcoin::types::H256Le;
-use btc_relay::BtcAddress;
+use btc_relay::{BtcAddress, TransactionConsumer};
 
 #[doc(inline)]
 pub use crate::types::ReplaceRequest;
@@ -272,6 +273,7 @@ impl<T: Config> Module<T> {
     ) -> DispatchResult {
         // Check that Parachain status is RUNNING
         ext::security::ensure_parachain_status_running::<T>()?;
+        ext::security::ensure_operation_not_paused::<T>(Operation::Replace)?;
 
         // check vault exists
         let vault = ext::vault_registry::get_active_vault_from_id::<T>(&vault_id)?;
@@ -391,6 +393,7 @@ impl<T: Config> Module<T> {
     ) -> Result<(), DispatchError> {
         // Check that Parachain status is RUNNING
         ext::security::ensure_parachain_status_running::<T>()?;
+        ext::security::ensure_operation_not_paused::<T>(Operation::Replace)?;
 
         // Retrieve the ReplaceRequest as per the replaceId parameter from ReplaceRequests.
         // Return ERR_REPLACE_ID_NOT_FOUND error if no such ReplaceRequest was found.
@@ -439,6 +442,7 @@ impl<T: Config> Module<T> {
     ) -> Result<(), DispatchError> {
         // Check that Parachain status is RUNNING
         ext::security::ensure_parachain_status_running::<T>()?;
+        ext::security::ensure_operation_not_paused::<T>(Operation::Replace)?;
 
         // Add the new replace address to the vault's wallet,
         // this should also verify that the vault exists
@@ -518,6 +522,7 @@ impl<T: Config> Module<T> {
     ) -> Result<(), DispatchError> {
         // Check that Parachain status is RUNNING
         ext::security::ensure_parachain_status_running::<T>()?;
+        ext::security::ensure_operation_not_paused::<T>(Operation::Replace)?;
 
         // Retrieve the ReplaceRequest as per the replaceId parameter from Vaults in the VaultRegistry
         let replace = Self::get_open_replace_request(&replace_id)?;
@@ -541,7 +546,7 @@ impl<T: Config> Module<T> {
         let _new_vault = ext::vault_registry::get_active_vault_from_id::<T>(&new_vault_id)?;
 
         // Call verifyTransactionInclusion in BTC-Relay, providing txid, txBlockHeight, txIndex, and merkleProof as parameters
-        ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof)?;
+        ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof.clone())?;
 
         // Call validateTransaction in BTC-Relay
         let amount = TryInto::<u64>::try_into(replace.amount)
@@ -555,11 +560,19 @@ impl<T: Config> Module<T> {
         };
 
         ext::btc_relay::validate_transaction::<T>(
-            raw_tx,
+            raw_tx.clone(),
             amount,
             btc_address,
             Some(replace_id.clone().as_bytes().to_vec()),
         )?;
+        ext::btc_relay::use_transaction::<T>(
+            tx_id,
+            merkle_proof,
+            raw_tx,
+            btc_address,
+            Some(replace_id.clone().as_bytes().to_vec()),
+            TransactionConsumer::Replace(replace_id),
+        )?;
 
         // Call the replaceTokens
         ext::vault_registry::replace_tokens::<T>(
@@ -590,6 +603,7 @@ impl<T: Config> Module<T> {
     fn _cancel_replace(caller: T::AccountId, replace_id: H256) -> Result<(), DispatchError> {
         // Check that Parachain status is RUNNING
         ext::security::ensure_parachain_status_running::<T>()?;
+        ext::security::ensure_operation_not_paused::<T>(Operation::Replace)?;
 
         // Retrieve the ReplaceRequest as per the replaceId parameter from Vaults in the VaultRegistry
         let replace = Self::get_open_replace_request(&replace_id)?;
@@ -822,6 +822,8 @@ fn test_execute_replace_succeeds() {
             .mock_safe(|_, _| MockResult::Return(Ok(())));
         ext::btc_relay::validate_transaction::<Test>
             .mock_safe(|_, _, _, _| MockResult::Return(Ok((BtcAddress::P2SH(H160::zero()), 0))));
+        ext::btc_relay::use_transaction::<Test>
+            .mock_safe(|_, _, _, _, _, _| MockResult::Return(Ok(())));
 
         ext::vault_registry::replace_tokens::<Test>
             .mock_safe(|_, _, _, _| MockResult::Return(Ok(())));
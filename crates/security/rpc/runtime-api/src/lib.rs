@@ -0,0 +1,27 @@
+//! Runtime API definition for the Security Module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use module_exchange_rate_oracle_rpc_runtime_api::BalanceWrapper;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait SecurityApi<Operation, StatusChange, VolumeType, Balance> where
+        Operation: Codec,
+        StatusChange: Codec,
+        VolumeType: Codec,
+        Balance: Codec,
+    {
+        /// Get the operations that are currently paused, either by governance
+        /// or by one of the current errors of the parachain
+        fn get_paused_operations() -> Vec<Operation>;
+
+        /// Get the most recent changes of the parachain status, oldest first
+        fn get_status_history() -> Vec<StatusChange>;
+
+        /// Get the volume that can still be requested within the current window,
+        /// or `None` if the volume is not capped
+        fn get_remaining_volume(volume_type: VolumeType) -> Option<BalanceWrapper<Balance>>;
+    }
+}
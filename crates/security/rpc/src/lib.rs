@@ -0,0 +1,110 @@
+//! RPC interface for the Security Module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_exchange_rate_oracle_rpc_runtime_api::BalanceWrapper;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as SecurityClient;
+pub use module_security_rpc_runtime_api::SecurityApi as SecurityRuntimeApi;
+
+#[rpc]
+pub trait SecurityApi<BlockHash, Operation, StatusChange, VolumeType, Balance>
+where
+    Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+    #[rpc(name = "security_getPausedOperations")]
+    fn get_paused_operations(&self, at: Option<BlockHash>) -> Result<Vec<Operation>>;
+
+    #[rpc(name = "security_getStatusHistory")]
+    fn get_status_history(&self, at: Option<BlockHash>) -> Result<Vec<StatusChange>>;
+
+    #[rpc(name = "security_getRemainingVolume")]
+    fn get_remaining_volume(
+        &self,
+        volume_type: VolumeType,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BalanceWrapper<Balance>>>;
+}
+
+/// A struct that implements the [`SecurityApi`].
+pub struct Security<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Security<C, B> {
+    /// Create new `Security` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Security {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+pub enum Error {
+    RuntimeError,
+}
+
+impl From<Error> for i64 {
+    fn from(e: Error) -> i64 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+fn runtime_error<E: std::fmt::Debug>(e: E, msg: &str) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(Error::RuntimeError.into()),
+        message: msg.into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+impl<C, Block, Operation, StatusChange, VolumeType, Balance>
+    SecurityApi<<Block as BlockT>::Hash, Operation, StatusChange, VolumeType, Balance>
+    for Security<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: SecurityRuntimeApi<Block, Operation, StatusChange, VolumeType, Balance>,
+    Operation: Codec,
+    StatusChange: Codec,
+    VolumeType: Codec,
+    Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+    fn get_paused_operations(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<Operation>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_paused_operations(&at)
+            .map_err(|e| runtime_error(e, "Unable to fetch paused operations."))
+    }
+
+    fn get_status_history(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<StatusChange>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_status_history(&at)
+            .map_err(|e| runtime_error(e, "Unable to fetch status history."))
+    }
+
+    fn get_remaining_volume(
+        &self,
+        volume_type: VolumeType,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<BalanceWrapper<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_remaining_volume(&at, volume_type)
+            .map_err(|e| runtime_error(e, "Unable to fetch remaining volume."))
+    }
+}
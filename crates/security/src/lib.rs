@@ -20,24 +20,50 @@ extern crate mocktopus;
 use mocktopus::macros::mockable;
 
 #[doc(inline)]
-pub use crate::types::{ErrorCode, StatusCode};
+pub use crate::types::{
+    ErrorCode, Operation, RequestedVolume, StatusChange, StatusChangeCause, StatusCode,
+    VolumeLimit, VolumeType, VOLUME_BUCKETS,
+};
 
+use bitcoin::types::H256Le;
 use codec::Encode;
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Get, Randomness},
+    transactional,
+};
+use frame_system::ensure_root;
 use primitive_types::H256;
 use sha2::{Digest, Sha256};
 use sp_core::U256;
+use sp_runtime::traits::UniqueSaturatedInto;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::iter::FromIterator;
 use sp_std::prelude::*;
 
 /// ## Configuration
 /// The pallet's configuration trait.
-pub trait Config: frame_system::Config {
+pub trait Config: frame_system::Config + pallet_timestamp::Config {
     /// The overarching event type.
     type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
+
+    /// Maximum number of entries kept in the status history.
+    type MaxStatusHistory: Get<u32>;
+
+    /// Source of on-chain randomness mixed into secure ids. The ids are only as
+    /// unpredictable as this source: a VRF output, such as the relay chain BABE
+    /// randomness, is required to prevent them from being computed before the block
+    /// is produced. Sources derived from block hashes, e.g.
+    /// `pallet_randomness_collective_flip`, are known to the block author and only
+    /// keep the ids unique.
+    type RandomnessSource: Randomness<H256>;
 }
 
+pub type StatusChangeOf<T> =
+    StatusChange<<T as frame_system::Config>::BlockNumber, <T as pallet_timestamp::Config>::Moment>;
+
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Config> as SecurityModule {
@@ -47,6 +73,21 @@ decl_storage! {
         /// Set of ErrorCodes, indicating the reason for an "Error" ParachainStatus.
         Errors get(fn errors): BTreeSet<ErrorCode>;
 
+        /// Operations paused by governance, regardless of the `ParachainStatus`.
+        PausedOperations get(fn paused_operations): BTreeSet<Operation>;
+
+        /// Operations paused while the parachain has the given `ErrorCode`.
+        ErrorPauses get(fn error_pauses): map hasher(blake2_128_concat) ErrorCode => BTreeSet<Operation>;
+
+        /// Most recent changes of the parachain status, oldest first.
+        StatusHistory get(fn status_history): Vec<StatusChangeOf<T>>;
+
+        /// Caps on the requested volume within a rolling window, if any.
+        VolumeLimits get(fn volume_limit): map hasher(blake2_128_concat) VolumeType => Option<VolumeLimit>;
+
+        /// Volume requested within the current window of the `VolumeLimit`.
+        RequestedVolumes get(fn requested_volumes): map hasher(blake2_128_concat) VolumeType => RequestedVolume;
+
         /// Integer increment-only counter, used to prevent collisions when generating identifiers
         /// for e.g. issue, redeem or replace requests (for OP_RETURN field in Bitcoin).
         Nonce: U256;
@@ -58,7 +99,87 @@ decl_module! {
     pub struct Module<T: Config> for enum Call where origin: T::Origin {
         type Error = Error<T>;
 
+        /// Maximum number of entries kept in the status history.
+        const MaxStatusHistory: u32 = T::MaxStatusHistory::get();
+
         fn deposit_event() = default;
+
+        /// Pauses the given operation until it is resumed (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `operation` - operation to pause
+        #[weight = 1000]
+        #[transactional]
+        fn pause_operation(origin, operation: Operation) -> DispatchResult {
+            ensure_root(origin)?;
+            <PausedOperations>::mutate(|operations| operations.insert(operation));
+            Self::deposit_event(Event::PauseOperation(operation));
+            Ok(())
+        }
+
+        /// Resumes an operation paused by `pause_operation` (only executable by the Root account).
+        /// The operation remains paused if an error of the parachain pauses it.
+        ///
+        /// # Arguments
+        ///
+        /// * `operation` - operation to resume
+        #[weight = 1000]
+        #[transactional]
+        fn resume_operation(origin, operation: Operation) -> DispatchResult {
+            ensure_root(origin)?;
+            <PausedOperations>::mutate(|operations| operations.remove(&operation));
+            Self::deposit_event(Event::ResumeOperation(operation));
+            Ok(())
+        }
+
+        /// Sets the operations that are paused while the parachain has the given error
+        /// (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `error_code` - error pausing the operations
+        /// * `operations` - operations to pause, replacing the previous ones
+        #[weight = 1000]
+        #[transactional]
+        fn set_error_pauses(origin, error_code: ErrorCode, operations: Vec<Operation>) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(error_code != ErrorCode::None, Error::<T>::InvalidErrorCode);
+            let operations: BTreeSet<Operation> = FromIterator::from_iter(operations);
+            if operations.is_empty() {
+                <ErrorPauses>::remove(&error_code);
+            } else {
+                <ErrorPauses>::insert(&error_code, &operations);
+            }
+            Self::deposit_event(Event::SetErrorPauses(error_code, operations.into_iter().collect()));
+            Ok(())
+        }
+
+        /// Sets or removes the cap on the requested volume within a rolling window
+        /// (only executable by the Root account)
+        ///
+        /// # Arguments
+        ///
+        /// * `volume_type` - volume to cap
+        /// * `volume_limit` - new cap, or `None` to remove it
+        ///
+        /// The buckets of the requested volume depend on the window, so the volume
+        /// requested so far is reset.
+        #[weight = 1000]
+        #[transactional]
+        fn set_volume_limit(origin, volume_type: VolumeType, volume_limit: Option<VolumeLimit>) -> DispatchResult {
+            ensure_root(origin)?;
+            match volume_limit.clone() {
+                Some(limit) => {
+                    ensure!(limit.window > 0, Error::<T>::InvalidVolumeLimit);
+                    <VolumeLimits>::insert(volume_type, limit);
+                }
+                None => <VolumeLimits>::remove(volume_type),
+            }
+            <RequestedVolumes>::remove(volume_type);
+            Self::deposit_event(Event::SetVolumeLimit(volume_type, volume_limit));
+            Ok(())
+        }
     }
 }
 
@@ -128,6 +249,112 @@ impl<T: Config> Module<T> {
         }
     }
 
+    /// Checks if the given operation is paused, either by governance or
+    /// by one of the current errors of the parachain
+    ///
+    /// # Arguments
+    ///
+    ///   * `operation` - operation to check
+    pub fn is_operation_paused(operation: Operation) -> bool {
+        Self::get_paused_operations().contains(&operation)
+    }
+
+    /// Ensures that the given operation is not paused
+    ///
+    /// # Arguments
+    ///
+    ///   * `operation` - operation to check
+    pub fn ensure_operation_not_paused(operation: Operation) -> DispatchResult {
+        ensure!(
+            !Self::is_operation_paused(operation),
+            Error::<T>::OperationPaused
+        );
+        Ok(())
+    }
+
+    /// Gets all operations that are currently paused, either by governance
+    /// or by one of the current errors of the parachain.
+    pub fn get_paused_operations() -> BTreeSet<Operation> {
+        let mut operations = <PausedOperations>::get();
+        if <ParachainStatus>::get() == StatusCode::Error {
+            for error_code in <Errors>::get().iter() {
+                operations.extend(<ErrorPauses>::get(error_code));
+            }
+        }
+        operations
+    }
+
+    /// Gets the number of blocks per bucket for the given window. The window spans
+    /// at most `VOLUME_BUCKETS` buckets, regardless of the block it ends at.
+    fn get_bucket_length(window: u32) -> u32 {
+        let buckets = VOLUME_BUCKETS as u32 - 1;
+        (window.saturating_add(buckets - 1) / buckets).max(1)
+    }
+
+    /// Gets the current block number, saturated to `u32`.
+    fn get_current_block() -> u32 {
+        UniqueSaturatedInto::<u32>::unique_saturated_into(frame_system::Module::<T>::block_number())
+    }
+
+    /// Gets the volume requested within the window of the given length, ending at the current block.
+    /// The volume of a bucket counts as long as any of its blocks is within the window, so the
+    /// volume may include requests of up to one bucket length before the window.
+    fn get_volume_in_window(volume_type: VolumeType, window: u32) -> u128 {
+        let now = Self::get_current_block();
+        let length = Self::get_bucket_length(window);
+        let requested = <RequestedVolumes>::get(volume_type);
+        (0..VOLUME_BUCKETS as u32)
+            .filter_map(|age| requested.latest_bucket.checked_sub(age))
+            // the last block of the bucket is within the window
+            .filter(|bucket| (*bucket as u64 + 1) * length as u64 + window as u64 > now as u64 + 1)
+            .fold(0u128, |total, bucket| {
+                total.saturating_add(requested.volumes[bucket as usize % VOLUME_BUCKETS])
+            })
+    }
+
+    /// Gets the volume that can still be requested within the current window,
+    /// or `None` if the volume is not capped.
+    ///
+    /// # Arguments
+    ///
+    ///   * `volume_type` - volume to check
+    pub fn get_remaining_volume(volume_type: VolumeType) -> Option<u128> {
+        let limit = <VolumeLimits>::get(volume_type)?;
+        let volume = Self::get_volume_in_window(volume_type, limit.window);
+        Some(limit.max_volume.saturating_sub(volume))
+    }
+
+    /// Adds the given amount to the volume requested in the current block,
+    /// failing if this would exceed the cap of the rolling window.
+    ///
+    /// # Arguments
+    ///
+    ///   * `volume_type` - volume to increase
+    ///   * `amount` - requested amount
+    pub fn increase_volume(volume_type: VolumeType, amount: u128) -> DispatchResult {
+        let limit = match <VolumeLimits>::get(volume_type) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+        let remaining = Self::get_remaining_volume(volume_type).unwrap_or_default();
+        ensure!(amount <= remaining, Error::<T>::VolumeLimitExceeded);
+
+        let bucket = Self::get_current_block() / Self::get_bucket_length(limit.window);
+        <RequestedVolumes>::mutate(volume_type, |requested| {
+            // reuse the buckets that fell out of the window
+            let stale = bucket
+                .saturating_sub(requested.latest_bucket)
+                .min(VOLUME_BUCKETS as u32);
+            for age in 0..stale {
+                requested.volumes[(bucket - age) as usize % VOLUME_BUCKETS] = 0;
+            }
+            requested.latest_bucket = requested.latest_bucket.max(bucket);
+            let volume = &mut requested.volumes[bucket as usize % VOLUME_BUCKETS];
+            *volume = volume.saturating_add(amount);
+        });
+        Ok(())
+    }
+
     /// Checks if the Parachain has a NoDataBTCRelay Error state
     pub fn is_parachain_error_no_data_btcrelay() -> bool {
         <ParachainStatus>::get() == StatusCode::Error
@@ -193,6 +420,31 @@ impl<T: Config> Module<T> {
         })
     }
 
+    /// Appends the current status and errors to the status history,
+    /// dropping the oldest entries beyond `MaxStatusHistory`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cause` - cause of the change
+    /// * `btc_block_hash` - related Bitcoin block, if any
+    pub fn record_status_change(cause: StatusChangeCause, btc_block_hash: Option<H256Le>) {
+        let status_change = StatusChange {
+            block_number: frame_system::Module::<T>::block_number(),
+            timestamp: pallet_timestamp::Module::<T>::get(),
+            status_code: Self::get_parachain_status(),
+            errors: Self::get_errors().into_iter().collect(),
+            cause,
+            btc_block_hash,
+        };
+        <StatusHistory<T>>::mutate(|history| {
+            history.push(status_change);
+            let max_len = T::MaxStatusHistory::get() as usize;
+            if history.len() > max_len {
+                history.drain(..history.len() - max_len);
+            }
+        });
+    }
+
     fn recover_from_(error_codes: Vec<ErrorCode>) -> DispatchResult {
         for error_code in error_codes.clone() {
             Self::remove_error(error_code);
@@ -201,6 +453,7 @@ impl<T: Config> Module<T> {
         if Self::get_errors().is_empty() {
             Self::set_parachain_status(StatusCode::Running);
         }
+        Self::record_status_change(StatusChangeCause::Recovery, None);
 
         Self::deposit_event(Event::RecoverFromErrors(
             Self::get_parachain_status(),
@@ -240,17 +493,20 @@ impl<T: Config> Module<T> {
 
     /// Generates a 256-bit unique hash from an `AccountId` and the
     /// internal (auto-incrementing) `Nonce` to prevent replay attacks.
+    /// The hash is mixed with the output of the `RandomnessSource`.
     ///
     /// # Arguments
     ///
     /// * `id`: Parachain account identifier.
     pub fn get_secure_id(id: &T::AccountId) -> H256 {
+        let nonce = Self::get_nonce().encode();
         let mut hasher = Sha256::default();
         hasher.input(id.encode());
-        hasher.input(Self::get_nonce().encode());
+        hasher.input(&nonce);
         // supplement with prev block hash to prevent replays
         // even if the `Nonce` is reset (i.e. purge-chain)
         hasher.input(frame_system::Module::<T>::parent_hash());
+        hasher.input(T::RandomnessSource::random(&nonce));
         let mut result = [0; 32];
         result.copy_from_slice(&hasher.result()[..]);
         H256(result)
@@ -260,6 +516,14 @@ impl<T: Config> Module<T> {
 decl_event!(
     pub enum Event {
         RecoverFromErrors(StatusCode, Vec<ErrorCode>),
+        /// Operation paused by governance [operation]
+        PauseOperation(Operation),
+        /// Operation resumed by governance [operation]
+        ResumeOperation(Operation),
+        /// Operations paused while the parachain has the error [error_code, operations]
+        SetErrorPauses(ErrorCode, Vec<Operation>),
+        /// Cap on the requested volume set or removed by governance [volume_type, volume_limit]
+        SetVolumeLimit(VolumeType, Option<VolumeLimit>),
     }
 );
 
@@ -273,6 +537,12 @@ decl_error! {
         ParachainOracleOfflineError,
         ParachainLiquidationError,
         InvalidErrorCode,
+        /// The operation is currently paused
+        OperationPaused,
+        /// The window of a volume limit must contain at least one block
+        InvalidVolumeLimit,
+        /// The requested amount exceeds the remaining volume of the rolling window
+        VolumeLimitExceeded,
     }
 }
 
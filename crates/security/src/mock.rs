@@ -1,13 +1,14 @@
 use crate as security;
 use crate::{Config, Error};
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::Randomness};
 use mocktopus::mocking::clear_mocks;
 use sp_core::H256;
 use sp_io;
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, Hash, IdentityLookup},
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -20,6 +21,7 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Module, Call, Storage, Config, Event<T>},
+        Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
         Security: security::{Module, Call, Storage, Event},
     }
 );
@@ -57,8 +59,43 @@ impl frame_system::Config for Test {
     type SS58Prefix = SS58Prefix;
 }
 
+parameter_types! {
+    pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 3;
+}
+
+thread_local! {
+    static RANDOM_SEED: RefCell<H256> = RefCell::new(H256::zero());
+}
+
+/// Sets the seed of the `TestRandomness`.
+pub fn set_random_seed(seed: H256) {
+    RANDOM_SEED.with(|random_seed| *random_seed.borrow_mut() = seed);
+}
+
+/// Randomness derived from a seed that can be set by tests.
+pub struct TestRandomness;
+
+impl Randomness<H256> for TestRandomness {
+    fn random(subject: &[u8]) -> H256 {
+        RANDOM_SEED.with(|seed| BlakeTwo256::hash(&[seed.borrow().as_bytes(), subject].concat()))
+    }
+}
+
 impl Config for Test {
     type Event = TestEvent;
+    type MaxStatusHistory = MaxStatusHistory;
+    type RandomnessSource = TestRandomness;
 }
 
 pub type TestEvent = Event;
@@ -81,6 +118,7 @@ where
     T: FnOnce() -> (),
 {
     clear_mocks();
+    set_random_seed(H256::zero());
     ExtBuilder::build().execute_with(|| {
         System::set_block_number(1);
         test();
@@ -1,8 +1,18 @@
 use crate::mock::*;
 use crate::ErrorCode;
+use crate::Nonce;
+use crate::Operation;
 use crate::StatusCode;
-use frame_support::{assert_noop, assert_ok, dispatch::DispatchResult};
-use sp_core::H256;
+use crate::{RequestedVolume, VolumeLimit, VolumeType};
+use crate::{StatusChange, StatusChangeCause};
+use bitcoin::types::H256Le;
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::{DispatchError, DispatchResult},
+    StorageValue,
+};
+use sp_core::{H256, U256};
+use sp_std::collections::btree_set::BTreeSet;
 
 type Event = crate::Event;
 
@@ -217,6 +227,287 @@ fn test_recover_from_btc_relay_failure_succeeds() {
     })
 }
 
+#[test]
+fn test_pause_and_resume_operation_succeeds() {
+    run_test(|| {
+        assert_noop!(
+            Security::pause_operation(Origin::signed(1), Operation::IssueRequest),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Security::pause_operation(
+            Origin::root(),
+            Operation::IssueRequest
+        ));
+        assert_emitted!(Event::PauseOperation(Operation::IssueRequest));
+        assert!(Security::is_operation_paused(Operation::IssueRequest));
+        assert_noop!(
+            Security::ensure_operation_not_paused(Operation::IssueRequest),
+            TestError::OperationPaused
+        );
+        // other operations are not affected
+        assert_ok!(Security::ensure_operation_not_paused(
+            Operation::IssueExecute
+        ));
+
+        assert_ok!(Security::resume_operation(
+            Origin::root(),
+            Operation::IssueRequest
+        ));
+        assert_emitted!(Event::ResumeOperation(Operation::IssueRequest));
+        assert_ok!(Security::ensure_operation_not_paused(
+            Operation::IssueRequest
+        ));
+    })
+}
+
+#[test]
+fn test_error_pauses_operations() {
+    run_test(|| {
+        assert_ok!(Security::set_error_pauses(
+            Origin::root(),
+            ErrorCode::OracleOffline,
+            vec![Operation::IssueRequest, Operation::Redeem]
+        ));
+        assert_emitted!(Event::SetErrorPauses(
+            ErrorCode::OracleOffline,
+            vec![Operation::IssueRequest, Operation::Redeem]
+        ));
+        assert!(Security::get_paused_operations().is_empty());
+
+        Security::set_parachain_status(StatusCode::Error);
+        Security::insert_error(ErrorCode::OracleOffline);
+        assert_eq!(
+            Security::get_paused_operations()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Operation::IssueRequest, Operation::Redeem]
+        );
+        assert_noop!(
+            Security::ensure_operation_not_paused(Operation::Redeem),
+            TestError::OperationPaused
+        );
+        assert_ok!(Security::ensure_operation_not_paused(Operation::Replace));
+
+        assert_ok!(Security::recover_from_oracle_offline());
+        assert_ok!(Security::ensure_operation_not_paused(Operation::Redeem));
+    })
+}
+
+#[test]
+fn test_set_error_pauses_fails_with_none_error_code() {
+    run_test(|| {
+        assert_noop!(
+            Security::set_error_pauses(
+                Origin::root(),
+                ErrorCode::None,
+                vec![Operation::IssueRequest]
+            ),
+            TestError::InvalidErrorCode
+        );
+    })
+}
+
+#[test]
+fn test_set_volume_limit_succeeds() {
+    run_test(|| {
+        let limit = VolumeLimit {
+            max_volume: 100,
+            window: 10,
+        };
+        assert_noop!(
+            Security::set_volume_limit(Origin::signed(1), VolumeType::Issue, Some(limit.clone())),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(Security::get_remaining_volume(VolumeType::Issue), None);
+
+        assert_ok!(Security::set_volume_limit(
+            Origin::root(),
+            VolumeType::Issue,
+            Some(limit.clone())
+        ));
+        assert_emitted!(Event::SetVolumeLimit(VolumeType::Issue, Some(limit)));
+        assert_eq!(Security::get_remaining_volume(VolumeType::Issue), Some(100));
+        assert_eq!(Security::get_remaining_volume(VolumeType::Redeem), None);
+
+        assert_ok!(Security::set_volume_limit(
+            Origin::root(),
+            VolumeType::Issue,
+            None
+        ));
+        assert_eq!(Security::get_remaining_volume(VolumeType::Issue), None);
+    })
+}
+
+#[test]
+fn test_set_volume_limit_fails_with_empty_window() {
+    run_test(|| {
+        assert_noop!(
+            Security::set_volume_limit(
+                Origin::root(),
+                VolumeType::Redeem,
+                Some(VolumeLimit {
+                    max_volume: 100,
+                    window: 0,
+                })
+            ),
+            TestError::InvalidVolumeLimit
+        );
+    })
+}
+
+#[test]
+fn test_increase_volume_within_rolling_window() {
+    run_test(|| {
+        // no limit, no restriction
+        assert_ok!(Security::increase_volume(VolumeType::Redeem, u128::MAX));
+
+        assert_ok!(Security::set_volume_limit(
+            Origin::root(),
+            VolumeType::Redeem,
+            Some(VolumeLimit {
+                max_volume: 100,
+                window: 3,
+            })
+        ));
+
+        System::set_block_number(1);
+        assert_ok!(Security::increase_volume(VolumeType::Redeem, 60));
+        System::set_block_number(2);
+        assert_ok!(Security::increase_volume(VolumeType::Redeem, 30));
+        assert_eq!(Security::get_remaining_volume(VolumeType::Redeem), Some(10));
+        assert_noop!(
+            Security::increase_volume(VolumeType::Redeem, 11),
+            TestError::VolumeLimitExceeded
+        );
+        // the issue volume is not capped
+        assert_ok!(Security::increase_volume(VolumeType::Issue, 11));
+
+        // the volume of block 1 is still in the window at block 3
+        System::set_block_number(3);
+        assert_eq!(Security::get_remaining_volume(VolumeType::Redeem), Some(10));
+
+        // but not at block 4
+        System::set_block_number(4);
+        assert_eq!(Security::get_remaining_volume(VolumeType::Redeem), Some(70));
+        assert_ok!(Security::increase_volume(VolumeType::Redeem, 70));
+        assert_eq!(Security::get_remaining_volume(VolumeType::Redeem), Some(0));
+        assert_eq!(
+            Security::requested_volumes(VolumeType::Redeem).volumes[..5],
+            [0, 60, 30, 0, 70]
+        );
+    })
+}
+
+#[test]
+fn test_increase_volume_reuses_buckets_outside_window() {
+    run_test(|| {
+        // 20 blocks, counted in buckets of 3 blocks
+        assert_ok!(Security::set_volume_limit(
+            Origin::root(),
+            VolumeType::Issue,
+            Some(VolumeLimit {
+                max_volume: 100,
+                window: 20,
+            })
+        ));
+
+        System::set_block_number(10);
+        assert_ok!(Security::increase_volume(VolumeType::Issue, 40));
+        System::set_block_number(11);
+        assert_ok!(Security::increase_volume(VolumeType::Issue, 20));
+        assert_eq!(
+            Security::requested_volumes(VolumeType::Issue),
+            RequestedVolume {
+                latest_bucket: 3,
+                volumes: [0, 0, 0, 60, 0, 0, 0, 0, 0, 0],
+            }
+        );
+
+        // the bucket of blocks 9 to 11 counts as long as block 11 is within the window
+        System::set_block_number(30);
+        assert_eq!(Security::get_remaining_volume(VolumeType::Issue), Some(40));
+        System::set_block_number(31);
+        assert_eq!(Security::get_remaining_volume(VolumeType::Issue), Some(100));
+
+        // bucket 13 wraps around to the slot of bucket 3
+        System::set_block_number(39);
+        assert_ok!(Security::increase_volume(VolumeType::Issue, 100));
+        assert_eq!(
+            Security::requested_volumes(VolumeType::Issue),
+            RequestedVolume {
+                latest_bucket: 13,
+                volumes: [0, 0, 0, 100, 0, 0, 0, 0, 0, 0],
+            }
+        );
+        assert_noop!(
+            Security::increase_volume(VolumeType::Issue, 1),
+            TestError::VolumeLimitExceeded
+        );
+
+        // changing the limit resets the requested volume
+        assert_ok!(Security::set_volume_limit(
+            Origin::root(),
+            VolumeType::Issue,
+            Some(VolumeLimit {
+                max_volume: 100,
+                window: 10,
+            })
+        ));
+        assert_eq!(Security::get_remaining_volume(VolumeType::Issue), Some(100));
+    })
+}
+
+#[test]
+fn test_record_status_change_succeeds() {
+    run_test(|| {
+        Timestamp::set_timestamp(1000);
+        Security::set_parachain_status(StatusCode::Error);
+        Security::insert_error(ErrorCode::NoDataBTCRelay);
+        Security::record_status_change(StatusChangeCause::StatusUpdate(0), Some(H256Le::zero()));
+
+        assert_eq!(
+            Security::status_history(),
+            vec![StatusChange {
+                block_number: 1,
+                timestamp: 1000,
+                status_code: StatusCode::Error,
+                errors: vec![ErrorCode::NoDataBTCRelay],
+                cause: StatusChangeCause::StatusUpdate(0),
+                btc_block_hash: Some(H256Le::zero()),
+            }]
+        );
+
+        // recoveries are recorded
+        System::set_block_number(2);
+        assert_ok!(Security::recover_from_btc_relay_failure());
+        let status_change = Security::status_history().pop().unwrap();
+        assert_eq!(status_change.block_number, 2);
+        assert_eq!(status_change.status_code, StatusCode::Running);
+        assert!(status_change.errors.is_empty());
+        assert_eq!(status_change.cause, StatusChangeCause::Recovery);
+    })
+}
+
+#[test]
+fn test_status_history_is_bounded() {
+    run_test(|| {
+        for block_number in 1..=5 {
+            System::set_block_number(block_number);
+            Security::record_status_change(StatusChangeCause::Governance, None);
+        }
+
+        // only the `MaxStatusHistory` most recent changes are kept
+        assert_eq!(
+            Security::status_history()
+                .iter()
+                .map(|status_change| status_change.block_number)
+                .collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+    })
+}
+
 #[test]
 fn test_get_nonce() {
     run_test(|| {
@@ -233,8 +524,8 @@ fn testget_secure_id() {
         assert_eq!(
             Security::get_secure_id(&1),
             H256::from_slice(&[
-                71, 121, 67, 63, 246, 65, 71, 242, 66, 184, 148, 234, 23, 56, 62, 52, 108, 82, 213,
-                33, 160, 200, 214, 1, 13, 46, 37, 138, 95, 245, 117, 109
+                156, 195, 93, 188, 187, 128, 181, 129, 76, 88, 44, 33, 155, 25, 38, 116, 205, 32,
+                134, 210, 229, 245, 222, 68, 135, 31, 138, 14, 210, 3, 222, 170
             ])
         );
     })
@@ -248,3 +539,39 @@ fn testget_secure_ids_not_equal() {
         assert_ne!(left, right);
     })
 }
+
+#[test]
+fn test_get_secure_id_depends_on_randomness() {
+    run_test(|| {
+        frame_system::Module::<Test>::set_parent_hash(H256::zero());
+        let left = Security::get_secure_id(&1);
+
+        // same account, nonce and parent hash
+        <Nonce>::put(U256::zero());
+        set_random_seed(H256::repeat_byte(1));
+        let right = Security::get_secure_id(&1);
+        assert_ne!(left, right);
+    })
+}
+
+#[test]
+fn test_get_secure_ids_unique_within_block() {
+    run_test(|| {
+        // concurrent requests of several accounts in the same block
+        let request_ids = || {
+            <Nonce>::put(U256::zero());
+            (0..100)
+                .flat_map(|_| vec![Security::get_secure_id(&1), Security::get_secure_id(&2)])
+                .collect::<BTreeSet<_>>()
+        };
+        frame_system::Module::<Test>::set_parent_hash(H256::zero());
+        let ids = request_ids();
+        assert_eq!(ids.len(), 200);
+
+        // same accounts, nonces and parent hash with another random seed
+        set_random_seed(H256::repeat_byte(1));
+        let reseeded_ids = request_ids();
+        assert_eq!(reseeded_ids.len(), 200);
+        assert!(ids.is_disjoint(&reseeded_ids));
+    })
+}
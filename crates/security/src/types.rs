@@ -1,9 +1,14 @@
+use bitcoin::types::H256Le;
 use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_std::cmp::Ord;
 use sp_std::fmt::Debug;
+use sp_std::vec::Vec;
 
 /// Enum indicating the status of the BTC Parachain.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum StatusCode {
     /// BTC Parachain is fully operational.
     Running = 0,
@@ -21,6 +26,7 @@ impl Default for StatusCode {
 
 /// Enum specifying errors which lead to the Error status, tacked in Errors
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Ord, PartialOrd)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ErrorCode {
     /// No error. Used as default value
     None = 0,
@@ -45,6 +51,88 @@ impl Default for ErrorCode {
     }
 }
 
+/// Operations of the BTC Parachain that can be paused individually.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, Ord, PartialOrd)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Operation {
+    /// Requesting the issue of PolkaBTC
+    IssueRequest,
+    /// Executing an issue request
+    IssueExecute,
+    /// Requesting and executing redeem requests
+    Redeem,
+    /// Requesting, accepting, executing and cancelling replace requests
+    Replace,
+    /// Executing refund requests
+    Refund,
+    /// Transferring DOT or PolkaBTC to other parachains over XCM
+    XcmTransfer,
+}
+
+/// Request volumes that can be capped within a rolling window.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VolumeType {
+    /// Amount of PolkaBTC requested to be issued
+    Issue,
+    /// Amount of PolkaBTC requested to be redeemed
+    Redeem,
+}
+
+/// Cap on the total requested volume within a rolling window of blocks.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, Debug)]
+pub struct VolumeLimit {
+    /// Maximum total amount that can be requested within the window
+    pub max_volume: u128,
+    /// Length of the window in blocks, including the current block
+    pub window: u32,
+}
+
+/// Number of buckets the window of a `VolumeLimit` is split into.
+pub const VOLUME_BUCKETS: usize = 10;
+
+/// Volume requested within the window of a `VolumeLimit`, summed per bucket of
+/// consecutive blocks so that its size does not depend on the length of the window.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, Debug)]
+pub struct RequestedVolume {
+    /// Index of the most recent bucket a volume was requested in
+    pub latest_bucket: u32,
+    /// Volume requested per bucket, at the bucket index modulo `VOLUME_BUCKETS`
+    pub volumes: [u128; VOLUME_BUCKETS],
+}
+
+/// Cause of a change of the BTC Parachain status.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum StatusChangeCause {
+    /// Status update suggested by a staked relayer and accepted by vote [status_update_id]
+    StatusUpdate(u64),
+    /// Status update forced by governance
+    Governance,
+    /// The exchange rate oracle was reported offline
+    OracleOffline,
+    /// The cause of one or more errors was resolved
+    Recovery,
+}
+
+/// Entry of the status history of the BTC Parachain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct StatusChange<BlockNumber, Moment> {
+    /// Parachain block number of the change
+    pub block_number: BlockNumber,
+    /// Timestamp of the parachain block of the change
+    pub timestamp: Moment,
+    /// Status of the BTC Parachain after the change
+    pub status_code: StatusCode,
+    /// Errors of the BTC Parachain after the change
+    pub errors: Vec<ErrorCode>,
+    /// Cause of the change
+    pub cause: StatusChangeCause,
+    /// LE Block hash of the related Bitcoin block, if any
+    pub btc_block_hash: Option<H256Le>,
+}
+
 #[macro_export]
 macro_rules! error_set {
     () => { BTreeSet::<ErrorCode>::new() };
@@ -131,8 +131,14 @@ impl exchange_rate_oracle::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 100;
+}
+
 impl security::Config for Test {
     type Event = TestEvent;
+    type MaxStatusHistory = MaxStatusHistory;
+    type RandomnessSource = pallet_randomness_collective_flip::Module<Test>;
 }
 
 parameter_types! {
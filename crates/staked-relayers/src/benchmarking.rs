@@ -38,10 +38,49 @@ benchmarks! {
     deregister_staked_relayer {
         let origin: T::AccountId = account("Origin", 0, 0);
         let stake: u32 = 100;
-        <ActiveStakedRelayers<T>>::insert(&origin, StakedRelayer { stake: stake.into(), height: System::<T>::block_number() });
+        StakedRelayers::<T>::insert_active_staked_relayer(&origin, stake.into(), System::<T>::block_number());
+        Collateral::<T>::lock_collateral(&origin, stake.into()).unwrap();
+    }: _(RawOrigin::Signed(origin))
+
+    withdraw_unbonded {
+        let origin: T::AccountId = account("Origin", 0, 0);
+        let stake: u32 = 100;
+        <UnbondingStakedRelayers<T>>::insert(&origin, StakedRelayer { stake: stake.into(), height: System::<T>::block_number() });
         Collateral::<T>::lock_collateral(&origin, stake.into()).unwrap();
     }: _(RawOrigin::Signed(origin))
 
+    nominate_staked_relayer {
+        let origin: T::AccountId = account("Origin", 0, 0);
+        let relayer: T::AccountId = account("Relayer", 0, 0);
+        let stake: u32 = 100;
+        StakedRelayers::<T>::insert_active_staked_relayer(&relayer, stake.into(), System::<T>::block_number());
+    }: _(RawOrigin::Signed(origin.clone()), relayer.clone(), stake.into())
+    verify {
+        assert_eq!(<Nominations<T>>::get(relayer, origin), stake.into());
+    }
+
+    withdraw_nomination {
+        let origin: T::AccountId = account("Origin", 0, 0);
+        let relayer: T::AccountId = account("Relayer", 0, 0);
+        let stake: u32 = 100;
+        <Nominations<T>>::insert(&relayer, &origin, <DOT<T>>::from(stake));
+        Collateral::<T>::lock_collateral(&origin, stake.into()).unwrap();
+    }: _(RawOrigin::Signed(origin), relayer)
+
+    withdraw_unbonded_nomination {
+        let origin: T::AccountId = account("Origin", 0, 0);
+        let relayer: T::AccountId = account("Relayer", 0, 0);
+        let stake: u32 = 100;
+        <UnbondingNominations<T>>::insert(&relayer, &origin, StakedRelayer { stake: stake.into(), height: System::<T>::block_number() });
+        Collateral::<T>::lock_collateral(&origin, stake.into()).unwrap();
+    }: _(RawOrigin::Signed(origin), relayer)
+
+    set_commission {
+        let origin: T::AccountId = account("Origin", 0, 0);
+        let stake: u32 = 100;
+        StakedRelayers::<T>::insert_active_staked_relayer(&origin, stake.into(), System::<T>::block_number());
+    }: _(RawOrigin::Signed(origin), Permill::from_percent(10))
+
     suggest_status_update {
         let origin: T::AccountId = account("Origin", 0, 0);
         let stake: u32 = 100;
@@ -67,14 +106,22 @@ benchmarks! {
         let origin: T::AccountId = account("Origin", 0, 0);
         let staked_relayer: T::AccountId = account("Vault", 0, 0);
         let stake: u32 = 100;
+        let n in 0 .. T::MaxNominatorsPerRelayer::get();
         StakedRelayers::<T>::insert_active_staked_relayer(&staked_relayer, stake.into(), System::<T>::block_number());
         Collateral::<T>::lock_collateral(&staked_relayer, stake.into()).unwrap();
+        for i in 0 .. n {
+            let nominator: T::AccountId = account("Nominator", i, 0);
+            <Nominations<T>>::insert(&staked_relayer, &nominator, <DOT<T>>::from(stake));
+            Collateral::<T>::lock_collateral(&nominator, stake.into()).unwrap();
+        }
+        <NominatorCount<T>>::insert(&staked_relayer, n);
 
     }: _(RawOrigin::Signed(origin), staked_relayer)
 
     report_vault_theft {
         let origin: T::AccountId = account("Origin", 0, 0);
         let relayer_id: T::AccountId = account("Relayer", 0, 0);
+        let n in 0 .. T::MaxPreviousTransactions::get();
 
         BtcRelay::<T>::register_authorized_relayer(relayer_id.clone());
 
@@ -148,9 +195,11 @@ benchmarks! {
         let raw_tx = transaction.format_with(true);
 
         let block_header = RawBlockHeader::from_bytes(&block.header.try_format().unwrap()).unwrap();
-        BtcRelay::<T>::_store_block_header(relayer_id, block_header).unwrap();
+        BtcRelay::<T>::_store_block_header(relayer_id, block_header, u32::MAX).unwrap();
+
+        let previous_transactions = vec![(proof.clone(), raw_tx.clone()); n as usize];
 
-    }: _(RawOrigin::Signed(origin), vault_id, tx_id, proof, raw_tx)
+    }: _(RawOrigin::Signed(origin), vault_id, tx_id, proof, raw_tx, previous_transactions)
 
     report_vault_under_liquidation_threshold {
         let origin: T::AccountId = account("Origin", 0, 0);
@@ -237,6 +286,7 @@ mod tests {
             GenesisConfig::<Test> {
                 gov_id: account("Origin", 0, 0),
                 maturity_period: 10,
+                unbonding_period: 10,
             }
             .assimilate_storage(storage)
             .unwrap();
@@ -244,6 +294,11 @@ mod tests {
         .execute_with(|| {
             assert_ok!(test_benchmark_register_staked_relayer::<Test>());
             assert_ok!(test_benchmark_deregister_staked_relayer::<Test>());
+            assert_ok!(test_benchmark_withdraw_unbonded::<Test>());
+            assert_ok!(test_benchmark_nominate_staked_relayer::<Test>());
+            assert_ok!(test_benchmark_withdraw_nomination::<Test>());
+            assert_ok!(test_benchmark_withdraw_unbonded_nomination::<Test>());
+            assert_ok!(test_benchmark_set_commission::<Test>());
             assert_ok!(test_benchmark_suggest_status_update::<Test>());
             assert_ok!(test_benchmark_vote_on_status_update::<Test>());
             assert_ok!(test_benchmark_force_status_update::<Test>());
@@ -14,12 +14,12 @@ impl crate::WeightInfo for () {
     }
     fn deregister_staked_relayer() -> Weight {
         (93_929_000 as Weight)
-            .saturating_add(DbWeight::get().reads(5 as Weight))
-            .saturating_add(DbWeight::get().writes(4 as Weight))
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
     }
     fn suggest_status_update() -> Weight {
         (86_591_000 as Weight)
-            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().reads(6 as Weight))
             .saturating_add(DbWeight::get().writes(4 as Weight))
     }
     fn vote_on_status_update() -> Weight {
@@ -27,19 +27,35 @@ impl crate::WeightInfo for () {
             .saturating_add(DbWeight::get().reads(2 as Weight))
             .saturating_add(DbWeight::get().writes(1 as Weight))
     }
+    fn submit_vote_attestations(n: u32) -> Weight {
+        (12_608_000 as Weight)
+            .saturating_add((45_633_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
     fn force_status_update() -> Weight {
         (30_442_000 as Weight)
             .saturating_add(DbWeight::get().reads(2 as Weight))
             .saturating_add(DbWeight::get().writes(2 as Weight))
     }
-    fn slash_staked_relayer() -> Weight {
+    fn slash_staked_relayer(n: u32) -> Weight {
         (109_555_000 as Weight)
-            .saturating_add(DbWeight::get().reads(6 as Weight))
-            .saturating_add(DbWeight::get().writes(5 as Weight))
+            .saturating_add((58_442_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+            .saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
     }
-    fn report_vault_theft() -> Weight {
+    fn report_vault_theft(n: u32) -> Weight {
         (251_206_000 as Weight)
+            .saturating_add((62_118_000 as Weight).saturating_mul(n as Weight))
             .saturating_add(DbWeight::get().reads(16 as Weight))
+            .saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn report_vault_double_payment() -> Weight {
+        (251_206_000 as Weight)
+            .saturating_add(DbWeight::get().reads(19 as Weight))
             .saturating_add(DbWeight::get().writes(5 as Weight))
     }
     fn report_vault_under_liquidation_threshold() -> Weight {
@@ -56,6 +72,37 @@ impl crate::WeightInfo for () {
     fn set_maturity_period() -> Weight {
         (5_571_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
     }
+    fn set_unbonding_period() -> Weight {
+        (5_571_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_vote_threshold() -> Weight {
+        (5_571_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn withdraw_unbonded() -> Weight {
+        (63_473_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn nominate_staked_relayer() -> Weight {
+        (64_215_000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn withdraw_nomination() -> Weight {
+        (31_874_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn withdraw_unbonded_nomination() -> Weight {
+        (63_473_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+    fn set_commission() -> Weight {
+        (17_102_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
     fn evaluate_status_update() -> Weight {
         (5_571_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
     }
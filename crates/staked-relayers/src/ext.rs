@@ -34,6 +34,10 @@ pub(crate) mod oracle {
     pub(crate) fn is_max_delay_passed<T: exchange_rate_oracle::Config>() -> bool {
         <exchange_rate_oracle::Module<T>>::is_max_delay_passed()
     }
+
+    pub(crate) fn get_last_exchange_rate_time<T: exchange_rate_oracle::Config>() -> T::Moment {
+        <exchange_rate_oracle::Module<T>>::get_last_exchange_rate_time()
+    }
 }
 
 #[cfg_attr(test, mockable)]
@@ -73,7 +77,8 @@ pub(crate) mod vault_registry {
 
 #[cfg_attr(test, mockable)]
 pub(crate) mod security {
-    use security::types::{ErrorCode, StatusCode};
+    use bitcoin::types::H256Le;
+    use security::types::{ErrorCode, StatusChangeCause, StatusCode};
     use sp_std::collections::btree_set::BTreeSet;
 
     pub(crate) fn get_parachain_status<T: security::Config>() -> StatusCode {
@@ -95,12 +100,19 @@ pub(crate) mod security {
     pub(crate) fn get_errors<T: security::Config>() -> BTreeSet<ErrorCode> {
         <security::Module<T>>::get_errors()
     }
+
+    pub(crate) fn record_status_change<T: security::Config>(
+        cause: StatusChangeCause,
+        btc_block_hash: Option<H256Le>,
+    ) {
+        <security::Module<T>>::record_status_change(cause, btc_block_hash)
+    }
 }
 
 #[cfg_attr(test, mockable)]
 pub(crate) mod btc_relay {
     use bitcoin::types::{H256Le, Transaction};
-    use btc_relay::BtcAddress;
+    use btc_relay::{BtcAddress, TransactionConsumer};
     use frame_support::dispatch::DispatchResult;
     use security::types::ErrorCode;
     use sp_std::prelude::*;
@@ -136,6 +148,12 @@ pub(crate) mod btc_relay {
         <btc_relay::Module<T>>::extract_outputs(tx)
     }
 
+    pub(crate) fn get_fulfilling_transaction<T: btc_relay::Config>(
+        consumer: TransactionConsumer,
+    ) -> Option<H256Le> {
+        <btc_relay::Module<T>>::get_fulfilling_transaction(consumer)
+    }
+
     pub(crate) fn register_authorized_relayer<T: btc_relay::Config>(who: T::AccountId) {
         <btc_relay::Module<T>>::register_authorized_relayer(who)
     }
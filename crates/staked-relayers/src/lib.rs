@@ -28,40 +28,82 @@ use mocktopus::macros::mockable;
 pub use security;
 
 use crate::types::{
-    PolkaBTC, ProposalStatus, StakedRelayer, StatusUpdate, StatusUpdateId, Tally, Votes, DOT,
+    PolkaBTC, ProposalStatus, StakedRelayer, StatusUpdate, StatusUpdateId, Tally,
+    VoteAttestationOf, VoteThreshold, Votes, DOT,
 };
 use bitcoin::parser::parse_transaction;
 use bitcoin::types::*;
-use btc_relay::BtcAddress;
+use btc_relay::{BtcAddress, TransactionConsumer};
+use codec::Encode;
 use frame_support::transactional;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
+    storage::with_transaction,
     traits::Get,
     weights::Weight,
-    IterableStorageMap,
+    IterableStorageDoubleMap, IterableStorageMap,
+};
+use frame_system::{
+    ensure_root, ensure_signed,
+    offchain::{AppCrypto, SigningTypes},
 };
-use frame_system::{ensure_root, ensure_signed};
 use primitive_types::H256;
-use security::types::{ErrorCode, StatusCode};
+use security::types::{ErrorCode, StatusChangeCause, StatusCode};
+use sp_arithmetic::{traits::Saturating, Perbill, Permill};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::traits::{IdentifyAccount, Zero};
+use sp_runtime::TransactionOutcome;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::convert::TryInto;
 use sp_std::vec::Vec;
 use vault_registry::Wallet;
 
+/// Key type of the keys used by Staked Relayers to sign vote attestations.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"srvt");
+
+/// Crypto of the keys used to sign vote attestations.
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    /// Identifier of the vote key of a Staked Relayer
+    pub struct VoteId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for VoteId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
 pub trait WeightInfo {
     fn register_staked_relayer() -> Weight;
     fn deregister_staked_relayer() -> Weight;
     fn suggest_status_update() -> Weight;
     fn vote_on_status_update() -> Weight;
+    fn submit_vote_attestations(n: u32) -> Weight;
     fn force_status_update() -> Weight;
-    fn slash_staked_relayer() -> Weight;
-    fn report_vault_theft() -> Weight;
+    fn slash_staked_relayer(n: u32) -> Weight;
+    fn report_vault_theft(n: u32) -> Weight;
+    fn report_vault_double_payment() -> Weight;
     fn report_vault_under_liquidation_threshold() -> Weight;
     fn remove_active_status_update() -> Weight;
     fn remove_inactive_status_update() -> Weight;
     fn set_maturity_period() -> Weight;
+    fn set_unbonding_period() -> Weight;
+    fn set_vote_threshold() -> Weight;
+    fn withdraw_unbonded() -> Weight;
+    fn nominate_staked_relayer() -> Weight;
+    fn withdraw_nomination() -> Weight;
+    fn withdraw_unbonded_nomination() -> Weight;
+    fn set_commission() -> Weight;
     fn evaluate_status_update() -> Weight;
 }
 
@@ -77,10 +119,14 @@ pub trait Config:
     + replace::Config
     + refund::Config
     + sla::Config
+    + SigningTypes
 {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
 
+    /// Key used by Staked Relayers to sign votes off-chain.
+    type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
     /// Weight information for the extrinsics in this module.
     type WeightInfo: WeightInfo;
 
@@ -90,11 +136,20 @@ pub trait Config:
     /// The minimum amount of stake required to participate.
     type MinimumStake: Get<DOT<Self>>;
 
+    /// The minimum amount a nominator must nominate to a staked relayer.
+    type MinimumNomination: Get<DOT<Self>>;
+
+    /// The maximum number of nominators of a staked relayer, including those that are unbonding.
+    type MaxNominatorsPerRelayer: Get<u32>;
+
     /// How often (in blocks) to check for new votes.
     type VotingPeriod: Get<Self::BlockNumber>;
 
     /// Maximum message size in bytes
     type MaximumMessageSize: Get<u32>;
+
+    /// Maximum number of spent transactions included in a theft report.
+    type MaxPreviousTransactions: Get<u32>;
 }
 
 // This pallet's storage items.
@@ -106,6 +161,33 @@ decl_storage! {
         /// Mapping from accounts of inactive staked relayers to the StakedRelayer struct.
         InactiveStakedRelayers get(fn inactive_staked_relayer): map hasher(blake2_128_concat) T::AccountId => StakedRelayer<DOT<T>, T::BlockNumber>;
 
+        /// Mapping from accounts of deregistered staked relayers to the StakedRelayer struct,
+        /// with the height at which the stake can be withdrawn. The stake remains slashable until then.
+        UnbondingStakedRelayers get(fn unbonding_staked_relayer): map hasher(blake2_128_concat) T::AccountId => StakedRelayer<DOT<T>, T::BlockNumber>;
+
+        /// Mapping from staked relayers and their nominators to the nominated DOT,
+        /// which adds to the voting stake of the staked relayer.
+        Nominations get(fn nomination): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => DOT<T>;
+
+        /// Mapping from staked relayers and their nominators to withdrawn nominations,
+        /// with the height at which the nomination can be released. The nomination remains slashable until then.
+        UnbondingNominations get(fn unbonding_nomination): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => StakedRelayer<DOT<T>, T::BlockNumber>;
+
+        /// Total voting stake of all active staked relayers, including their nominations.
+        TotalActiveStake get(fn total_active_stake): DOT<T>;
+
+        /// Total amount nominated to each staked relayer, excluding unbonding nominations.
+        TotalNominations get(fn total_nominations): map hasher(blake2_128_concat) T::AccountId => DOT<T>;
+
+        /// Number of nominators of each staked relayer, including those that are unbonding.
+        NominatorCount get(fn nominator_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// Share of the rewards a staked relayer keeps before sharing the rest with its nominators.
+        Commissions get(fn commission): map hasher(blake2_128_concat) T::AccountId => Permill;
+
+        /// Commissions set by staked relayers with the height from which they apply.
+        PendingCommissions get(fn pending_commission): map hasher(blake2_128_concat) T::AccountId => Option<(Permill, T::BlockNumber)>;
+
         /// Map of active StatusUpdates, identified by an integer key.
         ActiveStatusUpdates get(fn active_status_update): map hasher(blake2_128_concat) u64 => StatusUpdate<T::AccountId, T::BlockNumber, DOT<T>>;
 
@@ -122,11 +204,22 @@ decl_storage! {
         /// Mapping of Bitcoin block hashes to status update ids.
         BlockReports get(fn block_report): map hasher(blake2_128_concat) H256Le => u64;
 
+        /// Last exchange rate update time of the oracle outage that has already been
+        /// reported, so that only the first report per outage is rewarded.
+        OracleOfflineReport get(fn oracle_offline_report): Option<T::Moment>;
+
         /// AccountId of the governance mechanism, as specified in the genesis.
         GovernanceId get(fn gov_id) config(): T::AccountId;
 
         /// Number of blocks to wait until eligible to vote.
         MaturityPeriod get(fn maturity_period) config(): T::BlockNumber;
+
+        /// Number of blocks to wait after deregistering until the stake can be withdrawn.
+        UnbondingPeriod get(fn unbonding_period) config(): T::BlockNumber;
+
+        /// Thresholds for status updates adding or removing the given `ErrorCode`.
+        /// Status updates without an error use the entry of `ErrorCode::None`.
+        VoteThresholds get(fn vote_threshold): map hasher(blake2_128_concat) ErrorCode => VoteThreshold;
     }
 }
 
@@ -139,10 +232,16 @@ decl_module! {
 
         const MinimumStake: DOT<T> = T::MinimumStake::get();
 
+        const MinimumNomination: DOT<T> = T::MinimumNomination::get();
+
+        const MaxNominatorsPerRelayer: u32 = T::MaxNominatorsPerRelayer::get();
+
         const VotingPeriod: T::BlockNumber = T::VotingPeriod::get();
 
         const MaximumMessageSize: u32 = T::MaximumMessageSize::get();
 
+        const MaxPreviousTransactions: u32 = T::MaxPreviousTransactions::get();
+
         fn deposit_event() = default;
 
         /// Registers a new Staked Relayer, locking the provided collateral, which must exceed `STAKED_RELAYER_STAKE`.
@@ -166,6 +265,11 @@ decl_module! {
                 Error::<T>::AlreadyRegistered,
             );
 
+            ensure!(
+                !<UnbondingStakedRelayers<T>>::contains_key(&signer),
+                Error::<T>::AlreadyRegistered,
+            );
+
             ensure!(
                 stake >= T::MinimumStake::get(),
                 Error::<T>::InsufficientStake,
@@ -181,7 +285,8 @@ decl_module! {
             Ok(())
         }
 
-        /// Deregisters a Staked Relayer, releasing the associated stake.
+        /// Deregisters a Staked Relayer, moving the associated stake into the unbonding state.
+        /// The stake can be withdrawn with `withdraw_unbonded` after the unbonding period.
         ///
         /// # Arguments
         ///
@@ -192,17 +297,167 @@ decl_module! {
             let signer = ensure_signed(origin)?;
             let staked_relayer = Self::get_active_staked_relayer(&signer)?;
             Self::ensure_staked_relayer_is_not_voting(&signer)?;
-            ext::collateral::release_collateral::<T>(&signer, staked_relayer.stake)?;
 
             // TODO: check relayer has not proposed recently
             ext::btc_relay::deregister_authorized_relayer::<T>(signer.clone());
 
-            // TODO: require unbonding period
             Self::remove_active_staked_relayer(&signer);
+            let height = <frame_system::Module<T>>::block_number();
+            let unlock_height = height + Self::unbonding_period();
+            <UnbondingStakedRelayers<T>>::insert(&signer, StakedRelayer {
+                stake: staked_relayer.stake,
+                height: unlock_height,
+            });
             Self::deposit_event(<Event<T>>::DeregisterStakedRelayer(signer));
             Ok(())
         }
 
+        /// Releases the stake of a deregistered Staked Relayer once the unbonding period has passed.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin`: The account of the deregistered Staked Relayer
+        #[weight = <T as Config>::WeightInfo::withdraw_unbonded()]
+        #[transactional]
+        fn withdraw_unbonded(origin) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            ensure!(
+                <UnbondingStakedRelayers<T>>::contains_key(&signer),
+                Error::<T>::NotUnbonding,
+            );
+            let staked_relayer = <UnbondingStakedRelayers<T>>::get(&signer);
+            let height = <frame_system::Module<T>>::block_number();
+            ensure!(height >= staked_relayer.height, Error::<T>::UnbondingPeriodNotPassed);
+
+            ext::collateral::release_collateral::<T>(&signer, staked_relayer.stake)?;
+            <UnbondingStakedRelayers<T>>::remove(&signer);
+            Self::deposit_event(<Event<T>>::WithdrawUnbonded(signer, staked_relayer.stake));
+            Ok(())
+        }
+
+        /// Nominates a registered Staked Relayer, locking the provided collateral which adds to
+        /// the voting stake of the Staked Relayer. Nominators share the rewards of the Staked Relayer
+        /// and are slashed together with it.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin`: The account of the nominator
+        /// * `staked_relayer_id`: The account of the Staked Relayer to nominate
+        /// * `amount`: to-be-locked collateral in DOT
+        #[weight = <T as Config>::WeightInfo::nominate_staked_relayer()]
+        #[transactional]
+        fn nominate_staked_relayer(origin, staked_relayer_id: T::AccountId, amount: DOT<T>) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            ensure!(signer != staked_relayer_id, Error::<T>::CannotNominateSelf);
+            Self::ensure_relayer_is_registered_or_maturing(&staked_relayer_id)?;
+            ensure!(amount > 0u32.into(), Error::<T>::InsufficientStake);
+            ensure!(
+                Self::nomination(&staked_relayer_id, &signer).saturating_add(amount) >= T::MinimumNomination::get(),
+                Error::<T>::InsufficientStake,
+            );
+
+            let is_new_nominator = !<Nominations<T>>::contains_key(&staked_relayer_id, &signer)
+                && !<UnbondingNominations<T>>::contains_key(&staked_relayer_id, &signer);
+            if is_new_nominator {
+                ensure!(
+                    Self::nominator_count(&staked_relayer_id) < T::MaxNominatorsPerRelayer::get(),
+                    Error::<T>::TooManyNominators,
+                );
+                <NominatorCount<T>>::mutate(&staked_relayer_id, |count| *count += 1);
+            }
+
+            ext::collateral::lock_collateral::<T>(&signer, amount)?;
+            <Nominations<T>>::mutate(&staked_relayer_id, &signer, |nomination| {
+                *nomination = nomination.saturating_add(amount)
+            });
+            <TotalNominations<T>>::mutate(&staked_relayer_id, |total| *total = total.saturating_add(amount));
+            if <ActiveStakedRelayers<T>>::contains_key(&staked_relayer_id) {
+                <TotalActiveStake<T>>::mutate(|total| *total = total.saturating_add(amount));
+            }
+
+            Self::deposit_event(<Event<T>>::NominateStakedRelayer(signer, staked_relayer_id, amount));
+            Ok(())
+        }
+
+        /// Withdraws the nomination of a Staked Relayer, moving it into the unbonding state.
+        /// The nomination can be released with `withdraw_unbonded_nomination` after the unbonding period.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin`: The account of the nominator
+        /// * `staked_relayer_id`: The account of the nominated Staked Relayer
+        #[weight = <T as Config>::WeightInfo::withdraw_nomination()]
+        #[transactional]
+        fn withdraw_nomination(origin, staked_relayer_id: T::AccountId) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            ensure!(
+                <Nominations<T>>::contains_key(&staked_relayer_id, &signer),
+                Error::<T>::NotNominated,
+            );
+            let amount = <Nominations<T>>::take(&staked_relayer_id, &signer);
+            <TotalNominations<T>>::mutate(&staked_relayer_id, |total| *total = total.saturating_sub(amount));
+            if <ActiveStakedRelayers<T>>::contains_key(&staked_relayer_id) {
+                <TotalActiveStake<T>>::mutate(|total| *total = total.saturating_sub(amount));
+            }
+
+            let height = <frame_system::Module<T>>::block_number();
+            let unlock_height = height + Self::unbonding_period();
+            <UnbondingNominations<T>>::mutate(&staked_relayer_id, &signer, |unbonding| {
+                unbonding.stake = unbonding.stake.saturating_add(amount);
+                unbonding.height = unlock_height;
+            });
+
+            Self::deposit_event(<Event<T>>::WithdrawNomination(signer, staked_relayer_id, amount));
+            Ok(())
+        }
+
+        /// Releases a withdrawn nomination once the unbonding period has passed.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin`: The account of the nominator
+        /// * `staked_relayer_id`: The account of the previously nominated Staked Relayer
+        #[weight = <T as Config>::WeightInfo::withdraw_unbonded_nomination()]
+        #[transactional]
+        fn withdraw_unbonded_nomination(origin, staked_relayer_id: T::AccountId) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            ensure!(
+                <UnbondingNominations<T>>::contains_key(&staked_relayer_id, &signer),
+                Error::<T>::NotUnbonding,
+            );
+            let unbonding = <UnbondingNominations<T>>::get(&staked_relayer_id, &signer);
+            let height = <frame_system::Module<T>>::block_number();
+            ensure!(height >= unbonding.height, Error::<T>::UnbondingPeriodNotPassed);
+
+            ext::collateral::release_collateral::<T>(&signer, unbonding.stake)?;
+            <UnbondingNominations<T>>::remove(&staked_relayer_id, &signer);
+            if !<Nominations<T>>::contains_key(&staked_relayer_id, &signer) {
+                <NominatorCount<T>>::mutate(&staked_relayer_id, |count| *count = count.saturating_sub(1));
+            }
+            Self::deposit_event(<Event<T>>::WithdrawUnbondedNomination(signer, staked_relayer_id, unbonding.stake));
+            Ok(())
+        }
+
+        /// Sets the share of the rewards a Staked Relayer keeps before sharing the rest with its nominators.
+        /// The commission applies after the unbonding period, such that nominators can withdraw before.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin`: The account of the Staked Relayer
+        /// * `commission`: The share of the rewards to keep
+        #[weight = <T as Config>::WeightInfo::set_commission()]
+        #[transactional]
+        fn set_commission(origin, commission: Permill) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            Self::ensure_relayer_is_registered_or_maturing(&signer)?;
+
+            <Commissions<T>>::insert(&signer, Self::get_commission(&signer));
+            let height = <frame_system::Module<T>>::block_number() + Self::unbonding_period();
+            <PendingCommissions<T>>::insert(&signer, (commission, height));
+            Self::deposit_event(<Event<T>>::SetCommission(signer, commission, height));
+            Ok(())
+        }
+
         /// Suggest a new status update and opens it up for voting.
         ///
         /// # Arguments
@@ -283,7 +538,7 @@ decl_module! {
 
             // pre-approve
             let mut tally = Tally::default();
-            tally.aye.insert(signer.clone(), staked_relayer.stake);
+            tally.aye.insert(signer.clone(), Self::get_voting_stake(&signer, staked_relayer.stake));
 
             let height = <frame_system::Module<T>>::block_number();
             let status_update_id = Self::insert_active_status_update(StatusUpdate{
@@ -298,6 +553,7 @@ decl_module! {
                 proposer: signer.clone(),
                 deposit: deposit,
                 tally: tally,
+                total_active_stake: Self::total_active_stake(),
                 message: message,
             });
 
@@ -317,18 +573,57 @@ decl_module! {
         #[transactional]
         fn vote_on_status_update(origin, status_update_id: StatusUpdateId, approve: bool) -> DispatchResult {
             let signer = ensure_signed(origin)?;
+            Self::_vote_on_status_update(signer, status_update_id, approve)
+        }
 
-            // this call should revert if the signer is not registered
-            let staked_relayer = Self::get_active_staked_relayer(&signer)?;
-
-            let mut update = Self::get_status_update(&status_update_id)?;
-            ensure!(
-                update.tally.vote(signer.clone(), staked_relayer.stake, approve),
-                Error::<T>::VoteAlreadyCast,
-            );
-            <ActiveStatusUpdates<T>>::insert(&status_update_id, &update);
-
-            Self::deposit_event(<Event<T>>::VoteOnStatusUpdate(status_update_id, signer, approve));
+        /// Applies a batch of votes which Staked Relayers signed off-chain with their vote key,
+        /// so that a single account can aggregate the votes and pay for them once. The batch is
+        /// rejected if it is empty or any signature is invalid. Votes that can not be cast, e.g.
+        /// because the relayer already voted, are skipped and reported in a `SkipVoteAttestation`
+        /// event.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin`: Any signed user.
+        /// * `attestations`: Votes signed over the payload returned by `get_vote_attestation_payload`.
+        #[weight = <T as Config>::WeightInfo::submit_vote_attestations(attestations.len() as u32)]
+        #[transactional]
+        fn submit_vote_attestations(origin, attestations: Vec<VoteAttestationOf<T>>) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+            ensure!(!attestations.is_empty(), Error::<T>::NoVoteAttestations);
+
+            for attestation in attestations {
+                let payload = Self::get_vote_attestation_payload(
+                    attestation.status_update_id,
+                    attestation.approve,
+                );
+                ensure!(
+                    <T as Config>::AuthorityId::verify(
+                        &payload,
+                        attestation.signer.clone(),
+                        attestation.signature,
+                    ),
+                    Error::<T>::InvalidVoteSignature,
+                );
+                let relayer = attestation.signer.into_account();
+                let result = with_transaction(|| {
+                    match Self::_vote_on_status_update(
+                        relayer.clone(),
+                        attestation.status_update_id,
+                        attestation.approve,
+                    ) {
+                        Ok(()) => TransactionOutcome::Commit(Ok(())),
+                        Err(err) => TransactionOutcome::Rollback(Err(err)),
+                    }
+                });
+                if let Err(err) = result {
+                    Self::deposit_event(<Event<T>>::SkipVoteAttestation(
+                        attestation.status_update_id,
+                        relayer,
+                        err,
+                    ));
+                }
+            }
 
             Ok(())
         }
@@ -357,6 +652,7 @@ decl_module! {
             if let Some(error_code) = to_remove {
                 ext::security::remove_error::<T>(error_code);
             }
+            ext::security::record_status_change::<T>(StatusChangeCause::Governance, None);
 
             Self::deposit_event(<Event<T>>::ForceStatusUpdate(
                 status_code,
@@ -367,20 +663,29 @@ decl_module! {
         }
 
         /// Slashes the stake/collateral of a Staked Relayer and removes them from the list.
+        /// Staked Relayers that are still unbonding can be slashed as well.
         ///
         /// # Arguments
         ///
         /// * `origin`: The AccountId of the Governance Mechanism.
         /// * `staked_relayer_id`: The account of the Staked Relayer to be slashed.
-        #[weight = <T as Config>::WeightInfo::slash_staked_relayer()]
+        #[weight = <T as Config>::WeightInfo::slash_staked_relayer(
+            Module::<T>::nominator_count(staked_relayer_id)
+        )]
         #[transactional]
         fn slash_staked_relayer(origin, staked_relayer_id: T::AccountId) -> DispatchResult {
             let signer = ensure_signed(origin)?;
             Self::only_governance(&signer)?;
 
-            let staked_relayer = Self::get_active_staked_relayer(&staked_relayer_id)?;
-            ext::collateral::slash_collateral::<T>(staked_relayer_id.clone(), signer, staked_relayer.stake)?;
-            Self::remove_active_staked_relayer(&staked_relayer_id);
+            let stake = if <UnbondingStakedRelayers<T>>::contains_key(&staked_relayer_id) {
+                <UnbondingStakedRelayers<T>>::take(&staked_relayer_id).stake
+            } else {
+                let staked_relayer = Self::get_active_staked_relayer(&staked_relayer_id)?;
+                Self::remove_active_staked_relayer(&staked_relayer_id);
+                staked_relayer.stake
+            };
+            ext::collateral::slash_collateral::<T>(staked_relayer_id.clone(), signer.clone(), stake)?;
+            Self::slash_nominations(&staked_relayer_id, &signer)?;
 
             Self::deposit_event(<Event<T>>::SlashStakedRelayer(
                 staked_relayer_id,
@@ -390,6 +695,8 @@ decl_module! {
 
         /// A Staked Relayer reports misbehavior by a Vault, providing a fraud proof
         /// (malicious Bitcoin transaction and the corresponding transaction inclusion proof).
+        /// The transactions creating the outputs spent by the malicious transaction can be included
+        /// to prove which addresses its inputs spent from, e.g. if they cannot be derived from the inputs.
         ///
         /// # Arguments
         ///
@@ -398,11 +705,17 @@ decl_module! {
         /// * `tx_id`: The hash of the transaction
         /// * `merkle_proof`: The proof of tx inclusion.
         /// * `raw_tx`: The raw Bitcoin transaction.
-        #[weight = <T as Config>::WeightInfo::report_vault_theft()]
+        /// * `previous_transactions`: The proofs of tx inclusion and raw Bitcoin transactions of spent outputs,
+        /// at most `MaxPreviousTransactions`.
+        #[weight = <T as Config>::WeightInfo::report_vault_theft(previous_transactions.len() as u32)]
         #[transactional]
-        fn report_vault_theft(origin, vault_id: T::AccountId, tx_id: H256Le, merkle_proof: Vec<u8>, raw_tx: Vec<u8>) -> DispatchResult {
+        fn report_vault_theft(origin, vault_id: T::AccountId, tx_id: H256Le, merkle_proof: Vec<u8>, raw_tx: Vec<u8>, previous_transactions: Vec<(Vec<u8>, Vec<u8>)>) -> DispatchResult {
             let signer = ensure_signed(origin)?;
             Self::ensure_relayer_is_registered(&signer)?;
+            ensure!(
+                previous_transactions.len() as u32 <= T::MaxPreviousTransactions::get(),
+                Error::<T>::TooManyPreviousTransactions,
+            );
 
             // liquidated vaults are removed, so no need for check here
 
@@ -415,7 +728,15 @@ decl_module! {
             }
 
             ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof)?;
-            Self::is_transaction_invalid(&vault_id, raw_tx)?;
+
+            let mut spent_transactions = Vec::new();
+            for (previous_merkle_proof, previous_raw_tx) in previous_transactions {
+                let previous_tx = parse_transaction(previous_raw_tx.as_slice())
+                    .map_err(|_| Error::<T>::InvalidTransaction)?;
+                ext::btc_relay::verify_transaction_inclusion::<T>(previous_tx.tx_id(), previous_merkle_proof)?;
+                spent_transactions.push(previous_tx);
+            }
+            Self::is_transaction_invalid_spending(&vault_id, raw_tx, spent_transactions)?;
 
             ext::vault_registry::liquidate_theft_vault::<T>(&vault_id)?;
 
@@ -434,6 +755,52 @@ decl_module! {
             Ok(())
         }
 
+        /// A Staked Relayer reports that a Vault paid out a redeem, replace or refund request
+        /// more than once, providing a further vault transaction that carries the id of a request
+        /// fulfilled by another transaction and the corresponding transaction inclusion proof.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin`: Any signed user.
+        /// * `vault_id`: The account of the vault to check.
+        /// * `tx_id`: The hash of the transaction
+        /// * `merkle_proof`: The proof of tx inclusion.
+        /// * `raw_tx`: The raw Bitcoin transaction.
+        #[weight = <T as Config>::WeightInfo::report_vault_double_payment()]
+        #[transactional]
+        fn report_vault_double_payment(origin, vault_id: T::AccountId, tx_id: H256Le, merkle_proof: Vec<u8>, raw_tx: Vec<u8>) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            Self::ensure_relayer_is_registered(&signer)?;
+
+            // throw if already reported
+            if <TheftReports<T>>::contains_key(&tx_id) {
+                ensure!(
+                    !<TheftReports<T>>::get(&tx_id).contains(&vault_id),
+                    Error::<T>::VaultAlreadyReported,
+                );
+            }
+
+            ext::btc_relay::verify_transaction_inclusion::<T>(tx_id, merkle_proof)?;
+            let request_id = Self::get_double_payment_request(&vault_id, tx_id, raw_tx)?;
+
+            ext::vault_registry::liquidate_theft_vault::<T>(&vault_id)?;
+
+            <TheftReports<T>>::mutate(&tx_id, |reports| {
+                reports.insert(vault_id.clone());
+            });
+
+            // reward relayer for this report by increasing its sla
+            ext::sla::event_update_relayer_sla::<T>(signer, ext::sla::RelayerEvent::CorrectTheftReport)?;
+
+            Self::deposit_event(<Event<T>>::VaultDoublePayment(
+                vault_id,
+                tx_id,
+                request_id,
+            ));
+
+            Ok(())
+        }
+
         /// A Staked Relayer reports that a Vault is undercollateralized (i.e. below the LiquidationCollateralThreshold as defined in Vault Registry).
         /// If the collateral falls below this rate, we flag the Vault for liquidation.
         #[weight = <T as Config>::WeightInfo::report_vault_under_liquidation_threshold()]
@@ -465,6 +832,9 @@ decl_module! {
 
         /// A Staked Relayer reports that the Exchange Rate Oracle is offline. This function checks if the last exchange
         /// rate data in the Exchange Rate Oracle is indeed older than the indicated threshold.
+        ///
+        /// The oracle module detects outages automatically at the start of each block, so this is only a fallback.
+        /// The first report of each outage is rewarded, even if the error has already been raised.
         #[weight = 1000]
         #[transactional]
         fn report_oracle_offline(origin) -> DispatchResult {
@@ -472,17 +842,22 @@ decl_module! {
             Self::ensure_relayer_is_registered(&signer)?;
 
             ensure!(
-                !ext::security::get_errors::<T>().contains(&ErrorCode::OracleOffline),
-                Error::<T>::OracleAlreadyReported,
+                ext::oracle::is_max_delay_passed::<T>(),
+                Error::<T>::OracleOnline,
             );
 
+            let last_exchange_rate_time = ext::oracle::get_last_exchange_rate_time::<T>();
             ensure!(
-                ext::oracle::is_max_delay_passed::<T>(),
-                Error::<T>::OracleOnline,
+                <OracleOfflineReport<T>>::get() != Some(last_exchange_rate_time),
+                Error::<T>::OracleAlreadyReported,
             );
+            <OracleOfflineReport<T>>::put(last_exchange_rate_time);
 
-            ext::security::set_parachain_status::<T>(StatusCode::Error);
-            ext::security::insert_error::<T>(ErrorCode::OracleOffline);
+            if !ext::security::get_errors::<T>().contains(&ErrorCode::OracleOffline) {
+                ext::security::set_parachain_status::<T>(StatusCode::Error);
+                ext::security::insert_error::<T>(ErrorCode::OracleOffline);
+                ext::security::record_status_change::<T>(StatusChangeCause::OracleOffline, None);
+            }
 
             // reward relayer for this report by increasing its sla
             ext::sla::event_update_relayer_sla::<T>(signer, ext::sla::RelayerEvent::CorrectOracleOfflineReport)?;
@@ -537,6 +912,42 @@ decl_module! {
             <MaturityPeriod<T>>::set(period);
         }
 
+        /// Sets the unbonding period.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the dispatch origin of this call (must be _Root_)
+        /// * `period` - the number of blocks to wait after deregistering before the stake can be withdrawn.
+        ///
+        /// # Weight: `O(1)`
+        #[weight = <T as Config>::WeightInfo::set_unbonding_period()]
+        #[transactional]
+        fn set_unbonding_period(origin, period: T::BlockNumber) {
+            ensure_root(origin)?;
+            <UnbondingPeriod<T>>::set(period);
+        }
+
+        /// Sets the thresholds for status updates adding or removing the given error.
+        ///
+        /// # Arguments
+        ///
+        /// * `origin` - the dispatch origin of this call (must be _Root_)
+        /// * `error_code` - the error of the status updates, `ErrorCode::None` for status updates without an error.
+        /// * `threshold` - the quorum and approval threshold, the latter must be at least half.
+        ///
+        /// # Weight: `O(1)`
+        #[weight = <T as Config>::WeightInfo::set_vote_threshold()]
+        #[transactional]
+        fn set_vote_threshold(origin, error_code: ErrorCode, threshold: VoteThreshold) {
+            ensure_root(origin)?;
+            ensure!(
+                threshold.approval >= Permill::from_percent(50),
+                Error::<T>::InvalidVoteThreshold,
+            );
+            <VoteThresholds>::insert(&error_code, &threshold);
+            Self::deposit_event(<Event<T>>::SetVoteThreshold(error_code, threshold));
+        }
+
         /// Calls evaluate_status_update_at_height, for testing purposes.
         ///
         /// # Arguments
@@ -616,6 +1027,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Evaluates whether the `StatusUpdate` has been accepted or rejected.
+    /// Status updates that did not reach the quorum are rejected without slashing.
     ///
     /// # Arguments
     ///
@@ -625,7 +1037,9 @@ impl<T: Config> Module<T> {
         id: StatusUpdateId,
         mut status_update: &mut StatusUpdate<T::AccountId, T::BlockNumber, DOT<T>>,
     ) -> Result<(), DispatchError> {
-        if status_update.tally.is_approved() {
+        if !Self::has_quorum(&status_update) {
+            Self::reject_status_update_without_quorum(id, &mut status_update)?;
+        } else if Self::is_approved(&status_update) {
             Self::execute_status_update(id, &mut status_update)?;
             Self::update_sla_score_for_status_update(&status_update, true)?;
         } else {
@@ -637,6 +1051,55 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Casts the vote of an active staked relayer on a `StatusUpdate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `relayer` - account id of the relayer casting the vote
+    /// * `status_update_id` - id of the `StatusUpdate` voted upon
+    /// * `approve` - true iff the relayer agrees with the `StatusUpdate`
+    fn _vote_on_status_update(
+        relayer: T::AccountId,
+        status_update_id: StatusUpdateId,
+        approve: bool,
+    ) -> DispatchResult {
+        // this call should revert if the relayer is not registered
+        let staked_relayer = Self::get_active_staked_relayer(&relayer)?;
+
+        let mut update = Self::get_status_update(&status_update_id)?;
+        ensure!(
+            update.tally.vote(
+                relayer.clone(),
+                Self::get_voting_stake(&relayer, staked_relayer.stake),
+                approve
+            ),
+            Error::<T>::VoteAlreadyCast,
+        );
+        <ActiveStatusUpdates<T>>::insert(&status_update_id, &update);
+
+        Self::deposit_event(<Event<T>>::VoteOnStatusUpdate(
+            status_update_id,
+            relayer,
+            approve,
+        ));
+        Ok(())
+    }
+
+    /// Gets the payload a staked relayer signs to attest its vote off-chain. The payload
+    /// includes the genesis hash so that attestations cannot be replayed on other chains.
+    ///
+    /// # Arguments
+    ///
+    /// * `status_update_id` - id of the `StatusUpdate` voted upon
+    /// * `approve` - true iff the relayer agrees with the `StatusUpdate`
+    pub fn get_vote_attestation_payload(
+        status_update_id: StatusUpdateId,
+        approve: bool,
+    ) -> Vec<u8> {
+        let genesis_hash = <frame_system::Module<T>>::block_hash(T::BlockNumber::zero());
+        (b"vote", genesis_hash, status_update_id, approve).encode()
+    }
+
     /// Activate the staked relayer if mature.
     /// Used for external integration tests.
     ///
@@ -680,6 +1143,160 @@ impl<T: Config> Module<T> {
         Self::remove_inactive_staked_relayer(id);
     }
 
+    /// Gets the `VoteThreshold` for the error added or removed by the `StatusUpdate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `status_update` - `StatusUpdate` to get the threshold for
+    fn get_vote_threshold(
+        status_update: &StatusUpdate<T::AccountId, T::BlockNumber, DOT<T>>,
+    ) -> VoteThreshold {
+        let error_code = status_update
+            .add_error
+            .clone()
+            .or_else(|| status_update.remove_error.clone())
+            .unwrap_or(ErrorCode::None);
+        <VoteThresholds>::get(error_code)
+    }
+
+    /// Gets the total amount nominated to the staked relayer.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - account id of the relayer
+    pub fn get_total_nominations(id: &T::AccountId) -> DOT<T> {
+        <TotalNominations<T>>::get(id)
+    }
+
+    /// Gets the voting stake of the staked relayer, i.e. its own stake plus its nominations.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - account id of the relayer
+    /// * `stake` - own stake of the relayer
+    fn get_voting_stake(id: &T::AccountId, stake: DOT<T>) -> DOT<T> {
+        stake.saturating_add(Self::get_total_nominations(id))
+    }
+
+    /// Gets the own stake of a registered, maturing or unbonding staked relayer.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - account id of the relayer
+    fn get_own_stake(id: &T::AccountId) -> DOT<T> {
+        if <ActiveStakedRelayers<T>>::contains_key(id) {
+            <ActiveStakedRelayers<T>>::get(id).stake
+        } else if <InactiveStakedRelayers<T>>::contains_key(id) {
+            <InactiveStakedRelayers<T>>::get(id).stake
+        } else {
+            <UnbondingStakedRelayers<T>>::get(id).stake
+        }
+    }
+
+    /// Splits the rewards of the staked relayer: the relayer keeps its commission, the remainder
+    /// is shared pro rata between its own stake and its nominations. Rounding errors are
+    /// attributed to the relayer, which is always the first entry of the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - account id of the relayer
+    /// * `amount_in_polka_btc` - PolkaBTC rewards of the relayer
+    /// * `amount_in_dot` - DOT rewards of the relayer
+    pub fn share_rewards_with_nominators(
+        id: &T::AccountId,
+        amount_in_polka_btc: PolkaBTC<T>,
+        amount_in_dot: DOT<T>,
+    ) -> Vec<(T::AccountId, PolkaBTC<T>, DOT<T>)> {
+        let nominations: Vec<(T::AccountId, DOT<T>)> = <Nominations<T>>::iter_prefix(id).collect();
+        let total_stake = nominations
+            .iter()
+            .fold(Self::get_own_stake(id), |total, (_, amount)| {
+                total.saturating_add(*amount)
+            });
+        if total_stake == 0u32.into() {
+            return sp_std::vec![(id.clone(), amount_in_polka_btc, amount_in_dot)];
+        }
+
+        let commission = Self::get_commission(id);
+        let shared_polka_btc =
+            amount_in_polka_btc.saturating_sub(commission.mul_floor(amount_in_polka_btc));
+        let shared_dot = amount_in_dot.saturating_sub(commission.mul_floor(amount_in_dot));
+
+        let mut relayer_polka_btc = amount_in_polka_btc;
+        let mut relayer_dot = amount_in_dot;
+        let mut rewards = Vec::new();
+        for (nominator, amount) in nominations {
+            let share = Perbill::from_rational_approximation(amount, total_stake);
+            let nominator_polka_btc = share.mul_floor(shared_polka_btc);
+            let nominator_dot = share.mul_floor(shared_dot);
+            relayer_polka_btc = relayer_polka_btc.saturating_sub(nominator_polka_btc);
+            relayer_dot = relayer_dot.saturating_sub(nominator_dot);
+            rewards.push((nominator, nominator_polka_btc, nominator_dot));
+        }
+        rewards.insert(0, (id.clone(), relayer_polka_btc, relayer_dot));
+        rewards
+    }
+
+    /// Gets the commission of the staked relayer, including a pending commission that already applies.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - account id of the relayer
+    pub fn get_commission(id: &T::AccountId) -> Permill {
+        match <PendingCommissions<T>>::get(id) {
+            Some((commission, height)) if height <= <frame_system::Module<T>>::block_number() => {
+                commission
+            }
+            _ => <Commissions<T>>::get(id),
+        }
+    }
+
+    /// Slashes all nominations of the staked relayer, including those that are unbonding.
+    /// Staked relayers lose their entire stake, so nominators do as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - account id of the slashed relayer
+    /// * `receiver` - account receiving the slashed collateral
+    fn slash_nominations(id: &T::AccountId, receiver: &T::AccountId) -> DispatchResult {
+        let nominations: Vec<(T::AccountId, DOT<T>)> = <Nominations<T>>::drain_prefix(id)
+            .chain(
+                <UnbondingNominations<T>>::drain_prefix(id)
+                    .map(|(nominator, unbonding)| (nominator, unbonding.stake)),
+            )
+            .collect();
+        <TotalNominations<T>>::remove(id);
+        <NominatorCount<T>>::remove(id);
+        for (nominator, amount) in nominations {
+            ext::collateral::slash_collateral::<T>(nominator.clone(), receiver.clone(), amount)?;
+            Self::deposit_event(<Event<T>>::SlashNomination(nominator, id.clone(), amount));
+        }
+        Ok(())
+    }
+
+    /// Returns true if enough of the active stake at the time the `StatusUpdate` was
+    /// suggested voted on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `status_update` - `StatusUpdate` to check
+    fn has_quorum(status_update: &StatusUpdate<T::AccountId, T::BlockNumber, DOT<T>>) -> bool {
+        let threshold = Self::get_vote_threshold(status_update);
+        status_update
+            .tally
+            .has_quorum(threshold.quorum, status_update.total_active_stake)
+    }
+
+    /// Returns true if the votes in favour of the `StatusUpdate` exceed its approval threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `status_update` - `StatusUpdate` to check
+    fn is_approved(status_update: &StatusUpdate<T::AccountId, T::BlockNumber, DOT<T>>) -> bool {
+        let threshold = Self::get_vote_threshold(status_update);
+        status_update.tally.is_approved(threshold.approval)
+    }
+
     fn ensure_staked_relayer_is_not_voting(id: &T::AccountId) -> DispatchResult {
         for (_, update) in <ActiveStatusUpdates<T>>::iter() {
             ensure!(!update.tally.contains(id), Error::<T>::StatusUpdateFound);
@@ -710,6 +1327,20 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Ensure a staked relayer is registered, i.e. active or maturing.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - account id of the relayer
+    fn ensure_relayer_is_registered_or_maturing(id: &T::AccountId) -> DispatchResult {
+        ensure!(
+            <ActiveStakedRelayers<T>>::contains_key(id)
+                || <InactiveStakedRelayers<T>>::contains_key(id),
+            Error::<T>::NotRegistered,
+        );
+        Ok(())
+    }
+
     /// Gets the active staked relayer or throws an error.
     ///
     /// # Arguments
@@ -749,6 +1380,10 @@ impl<T: Config> Module<T> {
         stake: DOT<T>,
         height: T::BlockNumber,
     ) {
+        Self::remove_active_staked_relayer(id);
+        <TotalActiveStake<T>>::mutate(|total| {
+            *total = total.saturating_add(Self::get_voting_stake(id, stake))
+        });
         <ActiveStakedRelayers<T>>::insert(id, StakedRelayer { stake, height });
     }
 
@@ -767,13 +1402,19 @@ impl<T: Config> Module<T> {
         <InactiveStakedRelayers<T>>::insert(id, StakedRelayer { stake, height });
     }
 
-    /// Removes an active staked relayer, decrementing the total count.
+    /// Removes an active staked relayer, decrementing the total active stake.
+    /// Must be called before its nominations are removed.
     ///
     /// # Arguments
     ///
     /// * `id` - AccountId of the relayer.
     fn remove_active_staked_relayer(id: &T::AccountId) {
-        <ActiveStakedRelayers<T>>::remove(id);
+        if <ActiveStakedRelayers<T>>::contains_key(id) {
+            let staked_relayer = <ActiveStakedRelayers<T>>::take(id);
+            <TotalActiveStake<T>>::mutate(|total| {
+                *total = total.saturating_sub(Self::get_voting_stake(id, staked_relayer.stake))
+            });
+        }
     }
 
     /// Removes an inactive staked relayer.
@@ -856,6 +1497,7 @@ impl<T: Config> Module<T> {
                 staked_relayer.stake,
             )?;
             Self::remove_active_staked_relayer(acc);
+            Self::slash_nominations(acc, &<GovernanceId<T>>::get())?;
         }
 
         Ok(())
@@ -957,7 +1599,7 @@ impl<T: Config> Module<T> {
         mut status_update: &mut StatusUpdate<T::AccountId, T::BlockNumber, DOT<T>>,
     ) -> DispatchResult {
         ensure!(
-            status_update.tally.is_approved(),
+            Self::has_quorum(&status_update) && Self::is_approved(&status_update),
             Error::<T>::InsufficientYesVotes
         );
 
@@ -992,6 +1634,10 @@ impl<T: Config> Module<T> {
                 ext::security::remove_error::<T>(error_code.clone());
             }
         }
+        ext::security::record_status_change::<T>(
+            StatusChangeCause::StatusUpdate(status_update_id),
+            btc_block_hash,
+        );
 
         ext::collateral::release_collateral::<T>(&status_update.proposer, status_update.deposit)?;
         status_update.proposal_status = ProposalStatus::Accepted;
@@ -1017,7 +1663,7 @@ impl<T: Config> Module<T> {
         mut status_update: &mut StatusUpdate<T::AccountId, T::BlockNumber, DOT<T>>,
     ) -> DispatchResult {
         ensure!(
-            !status_update.tally.is_approved(),
+            !Self::is_approved(&status_update),
             Error::<T>::InsufficientNoVotes
         );
 
@@ -1032,6 +1678,30 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Rejects a suggested `StatusUpdate` that did not reach the quorum.
+    /// No votes are slashed and the deposit is returned to the proposer.
+    ///
+    /// # Arguments
+    ///
+    /// * `status_update_id`: Identifier of the `StatusUpdate` voted upon in `ActiveStatusUpdates`.
+    /// * `status_update`: `StatusUpdate` voted upon.
+    fn reject_status_update_without_quorum(
+        status_update_id: StatusUpdateId,
+        status_update: &mut StatusUpdate<T::AccountId, T::BlockNumber, DOT<T>>,
+    ) -> DispatchResult {
+        ensure!(!Self::has_quorum(&status_update), Error::<T>::QuorumReached);
+
+        ext::collateral::release_collateral::<T>(&status_update.proposer, status_update.deposit)?;
+        status_update.proposal_status = ProposalStatus::Rejected;
+        Self::deposit_event(<Event<T>>::RejectStatusUpdate(
+            status_update_id,
+            status_update.new_status_code.clone(),
+            status_update.add_error.clone(),
+            status_update.remove_error.clone(),
+        ));
+        Ok(())
+    }
+
     /// Checks if the vault is doing a valid merge transaction to move funds between
     /// addresses.
     ///
@@ -1106,29 +1776,45 @@ impl<T: Config> Module<T> {
     /// `vault_id`: the vault.
     /// `raw_tx`: the BTC transaction by the vault.
     pub fn is_transaction_invalid(vault_id: &T::AccountId, raw_tx: Vec<u8>) -> DispatchResult {
+        Self::is_transaction_invalid_spending(vault_id, raw_tx, Vec::new())
+    }
+
+    /// Same as `is_transaction_invalid`, but attributes the inputs spending outputs of the
+    /// given (verified) transactions to the addresses of these outputs.
+    ///
+    /// # Arguments
+    ///
+    /// `vault_id`: the vault.
+    /// `raw_tx`: the BTC transaction by the vault.
+    /// `spent_transactions`: transactions with outputs spent by `raw_tx`.
+    pub(crate) fn is_transaction_invalid_spending(
+        vault_id: &T::AccountId,
+        raw_tx: Vec<u8>,
+        spent_transactions: Vec<Transaction>,
+    ) -> DispatchResult {
         let vault = ext::vault_registry::get_active_vault_from_id::<T>(vault_id)?;
 
         // TODO: ensure this cannot fail on invalid
         let tx =
             parse_transaction(raw_tx.as_slice()).map_err(|_| Error::<T>::InvalidTransaction)?;
 
+        let spent_transactions: Vec<(H256Le, Transaction)> = spent_transactions
+            .into_iter()
+            .map(|spent_tx| (spent_tx.tx_id(), spent_tx))
+            .collect();
+
         // collect all addresses that feature in the inputs of the transaction
-        let input_addresses: Vec<Result<BtcAddress, _>> = tx
-            .clone()
+        let input_addresses: Vec<Option<BtcAddress>> = tx
             .inputs
-            .into_iter()
-            .map(|input| input.extract_address())
+            .iter()
+            .map(|input| Self::extract_spent_address(input, &spent_transactions))
             .collect();
 
         // check if vault's btc address features in an input of the transaction
         ensure!(
-            // TODO: can a vault steal funds if it registers a P2WPKH-P2SH since we
-            // would extract the `P2WPKHv0`?
-            input_addresses.into_iter().any(|address_result| {
-                match address_result {
-                    Ok(address) => vault.wallet.has_btc_address(&address),
-                    _ => false,
-                }
+            input_addresses.into_iter().any(|address| match address {
+                Some(address) => vault.wallet.has_btc_address(&address),
+                _ => false,
             }),
             // since the transaction does not have any inputs that correspond
             // to any of the vault's registered BTC addresses, return Err
@@ -1149,19 +1835,17 @@ impl<T: Config> Module<T> {
                 Error::<T>::ValidMergeTransaction
             );
 
-            if op_returns.len() != 1 {
-                // we only expect one op_return output
-                return Ok(());
-            } else if op_returns[0].0 > 0 {
-                // op_return output should not burn value
-                return Ok(());
-            } else if op_returns[0].1.len() < 32 {
-                // request id is expected to be 32 bytes (256 bits)
-                return Ok(());
-            }
+            let request_id = match Self::extract_request_id(&op_returns) {
+                Some(request_id) => request_id,
+                None => return Ok(()),
+            };
 
-            // op_return can be up to 83 bytes so slice first 32
-            let request_id = H256::from_slice(&op_returns[0].1[..32]);
+            // a request may only be fulfilled by a single transaction
+            if let Some(fulfilling_tx_id) = Self::get_fulfilling_transaction(request_id) {
+                if fulfilling_tx_id != tx.tx_id() {
+                    return Ok(());
+                }
+            }
 
             // redeem requests
             match ext::redeem::get_open_or_completed_redeem_request_from_id::<T>(&request_id) {
@@ -1218,6 +1902,108 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Checks that a vault transaction spends funds for a redeem, replace or refund request that
+    /// was already fulfilled by another transaction. Returns the id of the request if so.
+    ///
+    /// # Arguments
+    ///
+    /// `vault_id`: the vault.
+    /// `tx_id`: the id of the BTC transaction by the vault.
+    /// `raw_tx`: the BTC transaction by the vault.
+    pub(crate) fn get_double_payment_request(
+        vault_id: &T::AccountId,
+        tx_id: H256Le,
+        raw_tx: Vec<u8>,
+    ) -> Result<H256, DispatchError> {
+        let vault = ext::vault_registry::get_active_vault_from_id::<T>(vault_id)?;
+        let tx =
+            parse_transaction(raw_tx.as_slice()).map_err(|_| Error::<T>::InvalidTransaction)?;
+        ensure!(tx.tx_id() == tx_id, Error::<T>::InvalidTransactionId);
+
+        // only transactions signed by the vault are attributable
+        ensure!(
+            tx.inputs
+                .iter()
+                .any(|input| match Self::extract_spent_address(input, &[]) {
+                    Some(address) => vault.wallet.has_btc_address(&address),
+                    _ => false,
+                }),
+            Error::<T>::VaultNoInputToTransaction
+        );
+
+        let (_, op_returns) = ext::btc_relay::extract_outputs::<T>(tx)?;
+        let request_id =
+            Self::extract_request_id(&op_returns).ok_or(Error::<T>::RequestNotFulfilled)?;
+        let fulfilling_tx_id =
+            Self::get_fulfilling_transaction(request_id).ok_or(Error::<T>::RequestNotFulfilled)?;
+        ensure!(
+            fulfilling_tx_id != tx_id,
+            Error::<T>::TransactionFulfilledRequest
+        );
+
+        Ok(request_id)
+    }
+
+    /// Extracts the request id from the OP_RETURN outputs of a transaction. Returns `None`
+    /// unless there is exactly one OP_RETURN output without value and with a 32 byte id.
+    ///
+    /// # Arguments
+    ///
+    /// `op_returns`: all op_return outputs extracted from tx
+    fn extract_request_id(op_returns: &Vec<(i64, Vec<u8>)>) -> Option<H256> {
+        if op_returns.len() != 1 {
+            // we only expect one op_return output
+            None
+        } else if op_returns[0].0 > 0 {
+            // op_return output should not burn value
+            None
+        } else if op_returns[0].1.len() < 32 {
+            // request id is expected to be 32 bytes (256 bits)
+            None
+        } else {
+            // op_return can be up to 83 bytes so slice first 32
+            Some(H256::from_slice(&op_returns[0].1[..32]))
+        }
+    }
+
+    /// Gets the transaction that fulfilled the redeem, replace or refund request with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// `request_id`: the id of the request.
+    fn get_fulfilling_transaction(request_id: H256) -> Option<H256Le> {
+        [
+            TransactionConsumer::Redeem(request_id),
+            TransactionConsumer::Replace(request_id),
+            TransactionConsumer::Refund(request_id),
+        ]
+        .iter()
+        .find_map(|consumer| ext::btc_relay::get_fulfilling_transaction::<T>(*consumer))
+    }
+
+    /// Gets the address an input spent from, using the spent output if its transaction
+    /// is given or the input itself otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// `input`: the input of the transaction.
+    /// `spent_transactions`: ids and transactions with outputs which may be spent by the input.
+    fn extract_spent_address(
+        input: &TransactionInput,
+        spent_transactions: &[(H256Le, Transaction)],
+    ) -> Option<BtcAddress> {
+        match spent_transactions
+            .iter()
+            .find(|(tx_id, _)| *tx_id == input.previous_hash)
+        {
+            Some((_, spent_tx)) => spent_tx
+                .outputs
+                .get(input.previous_index as usize)
+                .and_then(|output| output.extract_address().ok()),
+            None => input.extract_address().ok(),
+        }
+    }
+
     /// Increments the current `StatusCounter` and returns the new value.
     pub fn get_status_counter() -> StatusUpdateId {
         <StatusCounter>::mutate(|c| {
@@ -1233,6 +2019,20 @@ impl<T: Config> Module<T> {
     }
 }
 
+impl<T: Config> fee::ShareRelayerRewards<T::AccountId, PolkaBTC<T>, DOT<T>> for Module<T> {
+    fn share_relayer_rewards(
+        relayer: &T::AccountId,
+        amount_in_polka_btc: PolkaBTC<T>,
+        amount_in_dot: DOT<T>,
+    ) -> Result<Vec<(T::AccountId, PolkaBTC<T>, DOT<T>)>, DispatchError> {
+        Ok(Self::share_rewards_with_nominators(
+            relayer,
+            amount_in_polka_btc,
+            amount_in_dot,
+        ))
+    }
+}
+
 decl_event!(
     pub enum Event<T>
     where
@@ -1242,6 +2042,11 @@ decl_event!(
     {
         RegisterStakedRelayer(AccountId, BlockNumber, DOT),
         DeregisterStakedRelayer(AccountId),
+        WithdrawUnbonded(AccountId, DOT),
+        NominateStakedRelayer(AccountId, AccountId, DOT),
+        WithdrawNomination(AccountId, AccountId, DOT),
+        WithdrawUnbondedNomination(AccountId, AccountId, DOT),
+        SetCommission(AccountId, Permill, BlockNumber),
         StatusUpdateSuggested(
             StatusUpdateId,
             AccountId,
@@ -1251,6 +2056,7 @@ decl_event!(
             Option<H256Le>,
         ),
         VoteOnStatusUpdate(StatusUpdateId, AccountId, bool),
+        SkipVoteAttestation(StatusUpdateId, AccountId, DispatchError),
         ExecuteStatusUpdate(
             StatusUpdateId,
             StatusCode,
@@ -1266,9 +2072,12 @@ decl_event!(
         ),
         ForceStatusUpdate(StatusCode, Option<ErrorCode>, Option<ErrorCode>),
         SlashStakedRelayer(AccountId),
+        SlashNomination(AccountId, AccountId, DOT),
         OracleOffline(),
         VaultTheft(AccountId, H256Le),
+        VaultDoublePayment(AccountId, H256Le, H256),
         VaultUnderLiquidationThreshold(AccountId),
+        SetVoteThreshold(ErrorCode, VoteThreshold),
     }
 );
 
@@ -1282,10 +2091,26 @@ decl_error! {
         InsufficientDeposit,
         /// Status update message is too big
         MessageTooBig,
+        /// Theft report includes too many spent transactions
+        TooManyPreviousTransactions,
         /// Participant is not registered
         NotRegistered,
         /// Staked relayer has not bonded
         NotMatured,
+        /// Staked relayer is not unbonding
+        NotUnbonding,
+        /// Unbonding period has not passed yet
+        UnbondingPeriodNotPassed,
+        /// Staked relayers cannot nominate themselves
+        CannotNominateSelf,
+        /// Account has not nominated the staked relayer
+        NotNominated,
+        /// Staked relayer has reached the maximum number of nominators
+        TooManyNominators,
+        /// Approval threshold must be at least half of the participating stake
+        InvalidVoteThreshold,
+        /// Status update has reached the quorum
+        QuorumReached,
         /// Caller is not governance module
         GovernanceOnly,
         /// Staked relayer is active
@@ -1298,6 +2123,10 @@ decl_error! {
         InsufficientNoVotes,
         /// Staked relayer has already cast vote
         VoteAlreadyCast,
+        /// Signature of the vote attestation is invalid
+        InvalidVoteSignature,
+        /// No vote attestations were submitted
+        NoVoteAttestations,
         /// Vault already reported
         VaultAlreadyReported,
         /// Vault already liquidated
@@ -1312,6 +2141,12 @@ decl_error! {
         ValidRefundTransaction,
         /// Valid merge transaction
         ValidMergeTransaction,
+        /// Transaction does not carry the id of a fulfilled request
+        RequestNotFulfilled,
+        /// Transaction fulfilled the request
+        TransactionFulfilledRequest,
+        /// Transaction id does not match the transaction
+        InvalidTransactionId,
         /// Oracle already reported
         OracleAlreadyReported,
         /// Oracle is online
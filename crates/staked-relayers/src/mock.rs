@@ -5,7 +5,7 @@ use mocktopus::mocking::clear_mocks;
 use sp_arithmetic::{FixedI128, FixedPointNumber, FixedU128};
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
 };
 
@@ -30,7 +30,7 @@ frame_support::construct_runtime!(
         Treasury: treasury::{Module, Call, Storage, Event<T>},
 
         // Operational
-        BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event},
+        BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event<T>},
         Security: security::{Module, Call, Storage, Event},
         StakedRelayers: staked_relayers::{Module, Call, Config<T>, Storage, Event<T>},
         VaultRegistry: vault_registry::{Module, Call, Config<T>, Storage, Event<T>},
@@ -125,8 +125,14 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 100;
+}
+
 impl security::Config for Test {
     type Event = TestEvent;
+    type MaxStatusHistory = MaxStatusHistory;
+    type RandomnessSource = pallet_randomness_collective_flip::Module<Test>;
 }
 
 impl vault_registry::Config for Test {
@@ -151,6 +157,7 @@ impl fee::Config for Test {
     type Event = TestEvent;
     type UnsignedFixedPoint = FixedU128;
     type WeightInfo = ();
+    type RelayerRewards = ();
 }
 
 impl sla::Config for Test {
@@ -168,9 +175,16 @@ impl collateral::Config for Test {
     type DOT = pallet_balances::Module<Test, pallet_balances::Instance1>;
 }
 
+parameter_types! {
+    pub BitcoinMainnet: btc_relay::NetworkParameters = btc_relay::BitcoinNetwork::Mainnet.parameters();
+}
+
 impl btc_relay::Config for Test {
     type Event = TestEvent;
     type WeightInfo = ();
+    type Network = BitcoinMainnet;
+    type OnReorg = ();
+    type OffchainWorker = ();
 }
 
 impl redeem::Config for Test {
@@ -186,17 +200,37 @@ impl replace::Config for Test {
 parameter_types! {
     pub const MinimumDeposit: u64 = 10;
     pub const MinimumStake: u64 = 10;
+    pub const MinimumNomination: u64 = 5;
+    pub const MaxNominatorsPerRelayer: u32 = 2;
     pub const VotingPeriod: u64 = 100;
     pub const MaximumMessageSize: u32 = 32;
+    pub const MaxPreviousTransactions: u32 = 2;
 }
 
 impl Config for Test {
     type Event = TestEvent;
+    type AuthorityId = TestAuthId;
     type WeightInfo = ();
     type MinimumDeposit = MinimumDeposit;
     type MinimumStake = MinimumStake;
+    type MinimumNomination = MinimumNomination;
+    type MaxNominatorsPerRelayer = MaxNominatorsPerRelayer;
     type VotingPeriod = VotingPeriod;
     type MaximumMessageSize = MaximumMessageSize;
+    type MaxPreviousTransactions = MaxPreviousTransactions;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
+}
+
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+    type RuntimeAppPublic = UintAuthorityId;
+    type GenericPublic = UintAuthorityId;
+    type GenericSignature = TestSignature;
 }
 
 pub type TestEvent = Event;
@@ -244,6 +278,7 @@ impl ExtBuilder {
             relayer_rewards: FixedU128::checked_from_rational(3, 100).unwrap(),
             maintainer_rewards: FixedU128::checked_from_rational(20, 100).unwrap(),
             collator_rewards: FixedU128::checked_from_integer(0).unwrap(),
+            header_submission_reward: 0,
         }
         .assimilate_storage(&mut storage)
         .unwrap();
@@ -270,6 +305,7 @@ impl ExtBuilder {
             staked_relayers::GenesisConfig::<Test> {
                 gov_id: CAROL,
                 maturity_period: 10,
+                unbonding_period: 10,
             }
             .assimilate_storage(storage)
             .unwrap();
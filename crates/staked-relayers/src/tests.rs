@@ -1,16 +1,24 @@
 extern crate hex;
-use crate::types::{ProposalStatus, StakedRelayer, StatusUpdate, Tally, Votes};
+use crate::types::{
+    ProposalStatus, StakedRelayer, StatusUpdate, Tally, VoteAttestation, VoteThreshold, Votes,
+};
 use crate::{ext, mock::*};
 use bitcoin::formatter::Formattable;
 use bitcoin::types::{H256Le, TransactionBuilder, TransactionInputBuilder, TransactionOutput};
-use btc_relay::{BtcAddress, BtcPublicKey};
-use frame_support::{assert_err, assert_noop, assert_ok, dispatch::DispatchError};
+use btc_relay::{BtcAddress, BtcPublicKey, TransactionConsumer};
+use frame_support::{
+    assert_err, assert_noop, assert_ok, dispatch::DispatchError, weights::GetDispatchInfo,
+};
 use mocktopus::mocking::*;
 use redeem::types::RedeemRequest;
 use replace::types::ReplaceRequest;
-use security::types::{ErrorCode, StatusCode};
+use security::types::{ErrorCode, StatusChangeCause, StatusCode};
+use sp_arithmetic::Permill;
 use sp_core::{H160, H256};
+use sp_runtime::testing::{TestSignature, UintAuthorityId};
+use std::cell::Cell;
 use std::convert::TryInto;
+use std::rc::Rc;
 use std::str::FromStr;
 use vault_registry::{Vault, VaultStatus, Wallet};
 
@@ -179,6 +187,7 @@ fn inject_status_update(proposer: AccountId) -> u64 {
         proposer: proposer,
         deposit: 10,
         tally: tally,
+        total_active_stake: StakedRelayers::total_active_stake(),
         message: vec![],
     })
 }
@@ -205,10 +214,318 @@ fn test_deregister_staked_relayer_succeeds() {
         let amount: Balance = 3;
         inject_active_staked_relayer(&ALICE, amount);
 
-        ext::collateral::release_collateral::<Test>.mock_safe(|_, _| MockResult::Return(Ok(())));
+        ext::collateral::release_collateral::<Test>
+            .mock_safe(|_, _| panic!("Stake should not be released before unbonding"));
 
         assert_ok!(StakedRelayers::deregister_staked_relayer(relayer));
         assert_emitted!(Event::DeregisterStakedRelayer(ALICE));
+        assert_err!(
+            StakedRelayers::get_active_staked_relayer(&ALICE),
+            TestError::NotRegistered
+        );
+        assert_eq!(
+            StakedRelayers::unbonding_staked_relayer(ALICE),
+            StakedRelayer {
+                stake: amount,
+                height: System::block_number() + StakedRelayers::unbonding_period(),
+            }
+        );
+    })
+}
+
+#[test]
+fn test_register_staked_relayer_fails_while_unbonding() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        assert_ok!(StakedRelayers::deregister_staked_relayer(Origin::signed(
+            ALICE
+        )));
+
+        assert_err!(
+            StakedRelayers::register_staked_relayer(Origin::signed(ALICE), 20),
+            TestError::AlreadyRegistered,
+        );
+    })
+}
+
+#[test]
+fn test_withdraw_unbonded_fails_with_not_unbonding() {
+    run_test(|| {
+        assert_err!(
+            StakedRelayers::withdraw_unbonded(Origin::signed(ALICE)),
+            TestError::NotUnbonding,
+        );
+    })
+}
+
+#[test]
+fn test_withdraw_unbonded_fails_before_unbonding_period() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        assert_ok!(StakedRelayers::deregister_staked_relayer(Origin::signed(
+            ALICE
+        )));
+
+        System::set_block_number(System::block_number() + StakedRelayers::unbonding_period() - 1);
+        assert_err!(
+            StakedRelayers::withdraw_unbonded(Origin::signed(ALICE)),
+            TestError::UnbondingPeriodNotPassed,
+        );
+    })
+}
+
+#[test]
+fn test_withdraw_unbonded_succeeds() {
+    run_test(|| {
+        let amount: Balance = 3;
+        inject_active_staked_relayer(&ALICE, amount);
+        assert_ok!(StakedRelayers::deregister_staked_relayer(Origin::signed(
+            ALICE
+        )));
+
+        ext::collateral::release_collateral::<Test>.mock_safe(move |sender, released| {
+            assert_eq!(sender, &ALICE);
+            assert_eq!(released, amount);
+            MockResult::Return(Ok(()))
+        });
+        System::set_block_number(System::block_number() + StakedRelayers::unbonding_period());
+        assert_ok!(StakedRelayers::withdraw_unbonded(Origin::signed(ALICE)));
+        assert_emitted!(Event::WithdrawUnbonded(ALICE, amount));
+        assert_err!(
+            StakedRelayers::withdraw_unbonded(Origin::signed(ALICE)),
+            TestError::NotUnbonding,
+        );
+    })
+}
+
+#[test]
+fn test_nominate_staked_relayer_fails_with_not_registered() {
+    run_test(|| {
+        assert_err!(
+            StakedRelayers::nominate_staked_relayer(Origin::signed(BOB), ALICE, 10),
+            TestError::NotRegistered,
+        );
+    })
+}
+
+#[test]
+fn test_nominate_staked_relayer_fails_with_self_nomination() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        assert_err!(
+            StakedRelayers::nominate_staked_relayer(Origin::signed(ALICE), ALICE, 10),
+            TestError::CannotNominateSelf,
+        );
+    })
+}
+
+#[test]
+fn test_nominate_staked_relayer_increases_voting_stake() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(BOB),
+            ALICE,
+            10
+        ));
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(CAROL),
+            ALICE,
+            5
+        ));
+        assert_emitted!(Event::NominateStakedRelayer(BOB, ALICE, 10));
+        assert_eq!(Collateral::get_collateral_from_account(&BOB), 10);
+        assert_eq!(StakedRelayers::get_total_nominations(&ALICE), 15);
+
+        assert_ok!(StakedRelayers::suggest_status_update(
+            Origin::signed(ALICE),
+            20,
+            StatusCode::Error,
+            Some(ErrorCode::OracleOffline),
+            None,
+            None,
+            vec![],
+        ));
+        let status_update_id = StakedRelayers::status_counter();
+        let status_update = StakedRelayers::get_status_update(&status_update_id).unwrap();
+        assert_eq!(status_update.tally.aye.total_stake, 18);
+    })
+}
+
+#[test]
+fn test_nominate_staked_relayer_fails_below_minimum_nomination() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        assert_err!(
+            StakedRelayers::nominate_staked_relayer(Origin::signed(BOB), ALICE, 4),
+            TestError::InsufficientStake,
+        );
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(BOB),
+            ALICE,
+            5
+        ));
+        // top-ups only need to keep the nomination above the minimum
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(BOB),
+            ALICE,
+            1
+        ));
+        assert_eq!(StakedRelayers::get_total_nominations(&ALICE), 6);
+    })
+}
+
+#[test]
+fn test_nominate_staked_relayer_fails_with_too_many_nominators() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(BOB),
+            ALICE,
+            10
+        ));
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(CAROL),
+            ALICE,
+            10
+        ));
+        assert_ok!(StakedRelayers::withdraw_nomination(
+            Origin::signed(CAROL),
+            ALICE
+        ));
+
+        // unbonding nominators still count towards the maximum
+        assert_eq!(StakedRelayers::nominator_count(&ALICE), 2);
+        assert_err!(
+            StakedRelayers::nominate_staked_relayer(Origin::signed(DAVE), ALICE, 10),
+            TestError::TooManyNominators,
+        );
+
+        System::set_block_number(System::block_number() + StakedRelayers::unbonding_period());
+        assert_ok!(StakedRelayers::withdraw_unbonded_nomination(
+            Origin::signed(CAROL),
+            ALICE
+        ));
+        assert_eq!(StakedRelayers::nominator_count(&ALICE), 1);
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(DAVE),
+            ALICE,
+            10
+        ));
+    })
+}
+
+#[test]
+fn test_withdraw_nomination_succeeds() {
+    run_test(|| {
+        let amount: Balance = 10;
+        inject_active_staked_relayer(&ALICE, 3);
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(BOB),
+            ALICE,
+            amount
+        ));
+
+        assert_ok!(StakedRelayers::withdraw_nomination(
+            Origin::signed(BOB),
+            ALICE
+        ));
+        assert_emitted!(Event::WithdrawNomination(BOB, ALICE, amount));
+        assert_eq!(StakedRelayers::get_total_nominations(&ALICE), 0);
+        assert_err!(
+            StakedRelayers::withdraw_nomination(Origin::signed(BOB), ALICE),
+            TestError::NotNominated,
+        );
+
+        System::set_block_number(System::block_number() + StakedRelayers::unbonding_period() - 1);
+        assert_err!(
+            StakedRelayers::withdraw_unbonded_nomination(Origin::signed(BOB), ALICE),
+            TestError::UnbondingPeriodNotPassed,
+        );
+
+        System::set_block_number(System::block_number() + 1);
+        assert_ok!(StakedRelayers::withdraw_unbonded_nomination(
+            Origin::signed(BOB),
+            ALICE
+        ));
+        assert_emitted!(Event::WithdrawUnbondedNomination(BOB, ALICE, amount));
+        assert_eq!(Collateral::get_collateral_from_account(&BOB), 0);
+        assert_err!(
+            StakedRelayers::withdraw_unbonded_nomination(Origin::signed(BOB), ALICE),
+            TestError::NotUnbonding,
+        );
+    })
+}
+
+#[test]
+fn test_set_commission_fails_with_not_registered() {
+    run_test(|| {
+        assert_err!(
+            StakedRelayers::set_commission(Origin::signed(ALICE), Permill::from_percent(10)),
+            TestError::NotRegistered,
+        );
+    })
+}
+
+#[test]
+fn test_set_commission_applies_after_unbonding_period() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 100);
+        let height = System::block_number() + StakedRelayers::unbonding_period();
+        assert_ok!(StakedRelayers::set_commission(
+            Origin::signed(ALICE),
+            Permill::from_percent(10)
+        ));
+        assert_emitted!(Event::SetCommission(
+            ALICE,
+            Permill::from_percent(10),
+            height
+        ));
+        assert_eq!(StakedRelayers::get_commission(&ALICE), Permill::zero());
+
+        System::set_block_number(height);
+        assert_eq!(
+            StakedRelayers::get_commission(&ALICE),
+            Permill::from_percent(10)
+        );
+
+        // raising the commission again is delayed as well
+        assert_ok!(StakedRelayers::set_commission(
+            Origin::signed(ALICE),
+            Permill::from_percent(100)
+        ));
+        assert_eq!(
+            StakedRelayers::get_commission(&ALICE),
+            Permill::from_percent(10)
+        );
+    })
+}
+
+#[test]
+fn test_share_rewards_with_nominators_succeeds() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 100);
+        assert_ok!(StakedRelayers::set_commission(
+            Origin::signed(ALICE),
+            Permill::from_percent(10)
+        ));
+        System::set_block_number(System::block_number() + StakedRelayers::unbonding_period());
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(CAROL),
+            ALICE,
+            100
+        ));
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(DAVE),
+            ALICE,
+            200
+        ));
+
+        let mut rewards = StakedRelayers::share_rewards_with_nominators(&ALICE, 1000, 100);
+        // relayer comes first and receives the commission and rounding errors
+        assert_eq!(rewards.remove(0), (ALICE, 325, 33));
+        rewards.sort();
+        assert_eq!(rewards, vec![(CAROL, 225, 22), (DAVE, 450, 45)]);
     })
 }
 
@@ -388,10 +705,29 @@ fn test_tally_is_approved_or_rejected() {
             nay: decl_votes!((4, 10), (5, 10), (6, 10)),
         };
 
-        assert_eq!(tally.is_approved(), false);
+        assert_eq!(tally.is_approved(Permill::from_percent(50)), false);
 
         tally.nay = decl_votes!();
-        assert_eq!(tally.is_approved(), true);
+        assert_eq!(tally.is_approved(Permill::from_percent(50)), true);
+    })
+}
+
+#[test]
+fn test_tally_thresholds() {
+    run_test(|| {
+        let tally = Tally {
+            aye: decl_votes!((1, 30), (2, 30)),
+            nay: decl_votes!((3, 40)),
+        };
+
+        // simple majority, but no supermajority
+        assert_eq!(tally.is_approved(Permill::from_percent(50)), true);
+        assert_eq!(tally.is_approved(Permill::from_percent(60)), false);
+        assert_eq!(tally.is_approved(Permill::from_percent(66)), false);
+
+        // 100 out of 200 total stake voted
+        assert_eq!(tally.has_quorum(Permill::from_percent(50), 200), true);
+        assert_eq!(tally.has_quorum(Permill::from_percent(51), 200), false);
     })
 }
 
@@ -525,6 +861,148 @@ fn test_vote_on_status_update_fails_with_vote_already_cast() {
     })
 }
 
+fn sign_vote(
+    relayer: AccountId,
+    status_update_id: u64,
+    approve: bool,
+) -> VoteAttestation<UintAuthorityId, TestSignature> {
+    VoteAttestation {
+        signer: UintAuthorityId(relayer),
+        status_update_id,
+        approve,
+        signature: TestSignature(
+            relayer,
+            StakedRelayers::get_vote_attestation_payload(status_update_id, approve),
+        ),
+    }
+}
+
+#[test]
+fn test_submit_vote_attestations_succeeds() {
+    run_test(|| {
+        let amount: Balance = 3;
+        inject_active_staked_relayer(&ALICE, amount);
+        inject_active_staked_relayer(&BOB, amount);
+        inject_active_staked_relayer(&CAROL, amount);
+        inject_active_staked_relayer(&DAVE, amount);
+
+        let status_update_id = inject_status_update(ALICE);
+        assert_ok!(StakedRelayers::submit_vote_attestations(
+            Origin::signed(ALICE),
+            vec![
+                sign_vote(BOB, status_update_id, true),
+                sign_vote(CAROL, status_update_id, false),
+            ]
+        ));
+        assert_emitted!(Event::VoteOnStatusUpdate(status_update_id, BOB, true));
+        assert_emitted!(Event::VoteOnStatusUpdate(status_update_id, CAROL, false));
+
+        let status_update = StakedRelayers::get_status_update(&status_update_id).unwrap();
+        assert!(status_update.tally.aye.contains(&BOB));
+        assert!(status_update.tally.nay.contains(&CAROL));
+    })
+}
+
+#[test]
+fn test_submit_vote_attestations_fails_with_invalid_signature() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        inject_active_staked_relayer(&BOB, 3);
+        let status_update_id = inject_status_update(ALICE);
+
+        // signed approval, submitted as rejection
+        let mut attestation = sign_vote(BOB, status_update_id, true);
+        attestation.approve = false;
+        assert_noop!(
+            StakedRelayers::submit_vote_attestations(Origin::signed(ALICE), vec![attestation]),
+            TestError::InvalidVoteSignature,
+        );
+
+        // signed by another key
+        let mut attestation = sign_vote(CAROL, status_update_id, true);
+        attestation.signer = UintAuthorityId(BOB);
+        assert_noop!(
+            StakedRelayers::submit_vote_attestations(Origin::signed(ALICE), vec![attestation]),
+            TestError::InvalidVoteSignature,
+        );
+    })
+}
+
+#[test]
+fn test_submit_vote_attestations_fails_without_attestations() {
+    run_test(|| {
+        assert_noop!(
+            StakedRelayers::submit_vote_attestations(Origin::signed(ALICE), vec![]),
+            TestError::NoVoteAttestations,
+        );
+    })
+}
+
+#[test]
+fn test_submit_vote_attestations_skips_unregistered_relayers() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        inject_active_staked_relayer(&CAROL, 3);
+        let status_update_id = inject_status_update(ALICE);
+
+        assert_ok!(StakedRelayers::submit_vote_attestations(
+            Origin::signed(ALICE),
+            vec![
+                sign_vote(BOB, status_update_id, true),
+                sign_vote(CAROL, status_update_id, true),
+            ]
+        ));
+        assert_emitted!(Event::SkipVoteAttestation(
+            status_update_id,
+            BOB,
+            TestError::NotRegistered.into()
+        ));
+        assert_emitted!(Event::VoteOnStatusUpdate(status_update_id, CAROL, true));
+    })
+}
+
+#[test]
+fn test_submit_vote_attestations_skips_votes_already_cast() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        inject_active_staked_relayer(&BOB, 3);
+        inject_active_staked_relayer(&CAROL, 3);
+        let status_update_id = inject_status_update(ALICE);
+
+        // a relayer front-runs the batch with its own vote
+        assert_ok!(StakedRelayers::vote_on_status_update(
+            Origin::signed(BOB),
+            status_update_id,
+            true
+        ));
+
+        assert_ok!(StakedRelayers::submit_vote_attestations(
+            Origin::signed(ALICE),
+            vec![
+                sign_vote(BOB, status_update_id, true),
+                sign_vote(CAROL, status_update_id, false),
+                sign_vote(CAROL, status_update_id, true),
+            ]
+        ));
+        assert_emitted!(Event::SkipVoteAttestation(
+            status_update_id,
+            BOB,
+            TestError::VoteAlreadyCast.into()
+        ));
+        assert_emitted!(Event::VoteOnStatusUpdate(status_update_id, CAROL, false));
+        assert_emitted!(Event::SkipVoteAttestation(
+            status_update_id,
+            CAROL,
+            TestError::VoteAlreadyCast.into()
+        ));
+
+        let status_update = StakedRelayers::get_status_update(&status_update_id).unwrap();
+        assert!(status_update.tally.aye.contains(&BOB));
+        assert!(status_update.tally.nay.contains(&CAROL));
+        assert!(!status_update.tally.aye.contains(&CAROL));
+    })
+}
+
 #[test]
 fn test_execute_status_update_fails_with_insufficient_yes_votes() {
     run_test(|| {
@@ -650,45 +1128,278 @@ fn test_reject_status_update_fails_with_insufficient_no_votes() {
         status_update.tally.aye = decl_votes!((ALICE, 10), (BOB, 10), (CAROL, 10));
         let status_update_id = StakedRelayers::insert_active_status_update(status_update.clone());
 
-        assert_err!(
-            StakedRelayers::reject_status_update(status_update_id, &mut status_update),
-            TestError::InsufficientNoVotes
-        );
+        assert_err!(
+            StakedRelayers::reject_status_update(status_update_id, &mut status_update),
+            TestError::InsufficientNoVotes
+        );
+
+        assert_not_emitted!(Event::RejectStatusUpdate(
+            status_update_id,
+            StatusCode::default(),
+            None,
+            None
+        ));
+    })
+}
+
+#[test]
+fn test_reject_status_update_succeeds() {
+    run_test(|| {
+        let amount: Balance = 3;
+        inject_active_staked_relayer(&ALICE, amount);
+        inject_active_staked_relayer(&BOB, amount);
+        inject_active_staked_relayer(&CAROL, amount);
+        inject_active_staked_relayer(&DAVE, amount);
+        inject_active_staked_relayer(&EVE, amount);
+
+        let mut status_update = StatusUpdate::default();
+        status_update.tally.nay = decl_votes!((ALICE, 10), (BOB, 10), (CAROL, 10));
+        let status_update_id = StakedRelayers::insert_active_status_update(status_update.clone());
+
+        assert_ok!(StakedRelayers::reject_status_update(
+            status_update_id,
+            &mut status_update
+        ));
+
+        assert_emitted!(Event::RejectStatusUpdate(
+            status_update_id,
+            StatusCode::default(),
+            None,
+            None
+        ));
+    })
+}
+
+#[test]
+fn test_set_vote_threshold_succeeds() {
+    run_test(|| {
+        let threshold = VoteThreshold {
+            quorum: Permill::from_percent(30),
+            approval: Permill::from_percent(66),
+        };
+        assert_err!(
+            StakedRelayers::set_vote_threshold(
+                Origin::signed(ALICE),
+                ErrorCode::OracleOffline,
+                threshold.clone()
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_eq!(
+            StakedRelayers::vote_threshold(ErrorCode::OracleOffline),
+            VoteThreshold::default()
+        );
+        assert_ok!(StakedRelayers::set_vote_threshold(
+            Origin::root(),
+            ErrorCode::OracleOffline,
+            threshold.clone()
+        ));
+        assert_eq!(
+            StakedRelayers::vote_threshold(ErrorCode::OracleOffline),
+            threshold.clone()
+        );
+        assert_emitted!(Event::SetVoteThreshold(ErrorCode::OracleOffline, threshold));
+    })
+}
+
+#[test]
+fn test_set_vote_threshold_fails_without_majority() {
+    run_test(|| {
+        assert_err!(
+            StakedRelayers::set_vote_threshold(
+                Origin::root(),
+                ErrorCode::None,
+                VoteThreshold {
+                    quorum: Permill::zero(),
+                    approval: Permill::from_percent(49),
+                }
+            ),
+            TestError::InvalidVoteThreshold
+        );
+    })
+}
+
+#[test]
+fn test_evaluate_status_update_rejects_without_quorum() {
+    run_test(|| {
+        let amount: Balance = 10;
+        inject_active_staked_relayer(&ALICE, amount);
+        inject_active_staked_relayer(&BOB, amount);
+        inject_active_staked_relayer(&CAROL, amount);
+        inject_active_staked_relayer(&DAVE, amount);
+        assert_ok!(StakedRelayers::set_vote_threshold(
+            Origin::root(),
+            ErrorCode::OracleOffline,
+            VoteThreshold {
+                quorum: Permill::from_percent(50),
+                approval: Permill::from_percent(50),
+            }
+        ));
+
+        let mut status_update = StatusUpdate {
+            new_status_code: StatusCode::Error,
+            old_status_code: StatusCode::Running,
+            add_error: Some(ErrorCode::OracleOffline),
+            proposer: ALICE,
+            deposit: 5,
+            tally: Tally {
+                aye: decl_votes!((ALICE, 10)),
+                nay: decl_votes!(),
+            },
+            total_active_stake: StakedRelayers::total_active_stake(),
+            ..Default::default()
+        };
+        let status_update_id = StakedRelayers::insert_active_status_update(status_update.clone());
+
+        ext::collateral::release_collateral::<Test>.mock_safe(|sender, amount| {
+            assert_eq!(sender, &ALICE);
+            assert_eq!(amount, 5);
+            MockResult::Return(Ok(()))
+        });
+        ext::collateral::slash_collateral::<Test>
+            .mock_safe(|_, _, _| panic!("Votes should not be slashed without quorum"));
+        ext::sla::event_update_relayer_sla::<Test>
+            .mock_safe(|_, _| panic!("SLA should not change without quorum"));
+
+        assert_ok!(StakedRelayers::_evaluate_status_update(
+            status_update_id,
+            &mut status_update
+        ));
+        assert_eq!(status_update.proposal_status, ProposalStatus::Rejected);
+        assert_eq!(Security::get_parachain_status(), StatusCode::Running);
+        assert_emitted!(Event::RejectStatusUpdate(
+            status_update_id,
+            StatusCode::Error,
+            Some(ErrorCode::OracleOffline),
+            None
+        ));
+    })
+}
+
+#[test]
+fn test_total_active_stake_is_tracked() {
+    run_test(|| {
+        StakedRelayers::only_governance.mock_safe(|_| MockResult::Return(Ok(())));
+        ext::collateral::slash_collateral::<Test>.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+        inject_active_staked_relayer(&ALICE, 10);
+        inject_active_staked_relayer(&BOB, 20);
+        assert_eq!(StakedRelayers::total_active_stake(), 30);
+
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(CAROL),
+            BOB,
+            5
+        ));
+        assert_eq!(StakedRelayers::total_active_stake(), 35);
+
+        assert_ok!(StakedRelayers::deregister_staked_relayer(Origin::signed(
+            ALICE
+        )));
+        assert_eq!(StakedRelayers::total_active_stake(), 25);
+
+        assert_ok!(StakedRelayers::withdraw_nomination(
+            Origin::signed(CAROL),
+            BOB
+        ));
+        assert_eq!(StakedRelayers::total_active_stake(), 20);
+
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(DAVE),
+            BOB,
+            5
+        ));
+        assert_ok!(StakedRelayers::slash_staked_relayer(
+            Origin::signed(ALICE),
+            BOB
+        ));
+        assert_eq!(StakedRelayers::total_active_stake(), 0);
+    })
+}
+
+#[test]
+fn test_evaluate_status_update_measures_quorum_at_suggestion() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 10);
+        inject_active_staked_relayer(&BOB, 10);
+        assert_ok!(StakedRelayers::set_vote_threshold(
+            Origin::root(),
+            ErrorCode::OracleOffline,
+            VoteThreshold {
+                quorum: Permill::from_percent(50),
+                approval: Permill::from_percent(50),
+            }
+        ));
+        assert_ok!(StakedRelayers::suggest_status_update(
+            Origin::signed(ALICE),
+            20,
+            StatusCode::Error,
+            Some(ErrorCode::OracleOffline),
+            None,
+            None,
+            vec![],
+        ));
+        let status_update_id = StakedRelayers::status_counter();
+        let mut status_update = StakedRelayers::get_status_update(&status_update_id).unwrap();
+        assert_eq!(status_update.total_active_stake, 20);
+
+        // relayers joining after the suggestion do not dilute the quorum
+        inject_active_staked_relayer(&CAROL, 10);
+        inject_active_staked_relayer(&DAVE, 10);
+        assert!(StakedRelayers::has_quorum(&status_update));
 
-        assert_not_emitted!(Event::RejectStatusUpdate(
+        ext::sla::event_update_relayer_sla::<Test>.mock_safe(|_, _| MockResult::Return(Ok(())));
+        assert_ok!(StakedRelayers::_evaluate_status_update(
             status_update_id,
-            StatusCode::default(),
-            None,
-            None
+            &mut status_update
         ));
+        assert_eq!(status_update.proposal_status, ProposalStatus::Accepted);
     })
 }
 
 #[test]
-fn test_reject_status_update_succeeds() {
+fn test_evaluate_status_update_rejects_without_supermajority() {
     run_test(|| {
-        let amount: Balance = 3;
+        let amount: Balance = 10;
         inject_active_staked_relayer(&ALICE, amount);
         inject_active_staked_relayer(&BOB, amount);
         inject_active_staked_relayer(&CAROL, amount);
-        inject_active_staked_relayer(&DAVE, amount);
-        inject_active_staked_relayer(&EVE, amount);
+        assert_ok!(StakedRelayers::set_vote_threshold(
+            Origin::root(),
+            ErrorCode::OracleOffline,
+            VoteThreshold {
+                quorum: Permill::from_percent(50),
+                approval: Permill::from_percent(67),
+            }
+        ));
 
-        let mut status_update = StatusUpdate::default();
-        status_update.tally.nay = decl_votes!((ALICE, 10), (BOB, 10), (CAROL, 10));
+        let mut status_update = StatusUpdate {
+            new_status_code: StatusCode::Error,
+            old_status_code: StatusCode::Running,
+            add_error: Some(ErrorCode::OracleOffline),
+            proposer: ALICE,
+            deposit: 5,
+            tally: Tally {
+                aye: decl_votes!((ALICE, 10), (BOB, 10)),
+                nay: decl_votes!((CAROL, 10)),
+            },
+            total_active_stake: StakedRelayers::total_active_stake(),
+            ..Default::default()
+        };
         let status_update_id = StakedRelayers::insert_active_status_update(status_update.clone());
 
-        assert_ok!(StakedRelayers::reject_status_update(
-            status_update_id,
-            &mut status_update
-        ));
+        ext::collateral::slash_collateral::<Test>.mock_safe(|_, _, _| MockResult::Return(Ok(())));
+        ext::sla::event_update_relayer_sla::<Test>.mock_safe(|_, _| MockResult::Return(Ok(())));
 
-        assert_emitted!(Event::RejectStatusUpdate(
+        assert_err!(
+            StakedRelayers::execute_status_update(status_update_id, &mut status_update.clone()),
+            TestError::InsufficientYesVotes
+        );
+        assert_ok!(StakedRelayers::_evaluate_status_update(
             status_update_id,
-            StatusCode::default(),
-            None,
-            None
+            &mut status_update
         ));
+        assert_eq!(status_update.proposal_status, ProposalStatus::Rejected);
     })
 }
 
@@ -735,6 +1446,10 @@ fn test_force_status_update_succeeds() {
 
         let errors = ext::security::get_errors::<Test>();
         assert_eq!(errors.contains(&ErrorCode::Liquidation), true);
+
+        let status_change = <security::Module<Test>>::status_history().pop().unwrap();
+        assert_eq!(status_change.status_code, StatusCode::Shutdown);
+        assert_eq!(status_change.cause, StatusChangeCause::Governance);
     })
 }
 
@@ -787,6 +1502,85 @@ fn test_slash_staked_relayer_succeeds() {
     })
 }
 
+#[test]
+fn test_slash_unbonding_staked_relayer_succeeds() {
+    run_test(|| {
+        StakedRelayers::only_governance.mock_safe(|_| MockResult::Return(Ok(())));
+        let amount: Balance = 5;
+        inject_active_staked_relayer(&BOB, amount);
+        assert_ok!(StakedRelayers::deregister_staked_relayer(Origin::signed(
+            BOB
+        )));
+
+        ext::collateral::slash_collateral::<Test>.mock_safe(move |sender, receiver, slashed| {
+            assert_eq!(sender, BOB);
+            assert_eq!(receiver, ALICE);
+            assert_eq!(slashed, amount);
+            MockResult::Return(Ok(()))
+        });
+        assert_ok!(StakedRelayers::slash_staked_relayer(
+            Origin::signed(ALICE),
+            BOB
+        ));
+        assert_emitted!(Event::SlashStakedRelayer(BOB));
+        assert_err!(
+            StakedRelayers::withdraw_unbonded(Origin::signed(BOB)),
+            TestError::NotUnbonding,
+        );
+    })
+}
+
+#[test]
+fn test_slash_staked_relayer_slashes_nominations() {
+    run_test(|| {
+        StakedRelayers::only_governance.mock_safe(|_| MockResult::Return(Ok(())));
+        inject_active_staked_relayer(&BOB, 5);
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(CAROL),
+            BOB,
+            10
+        ));
+        assert_ok!(StakedRelayers::nominate_staked_relayer(
+            Origin::signed(DAVE),
+            BOB,
+            20
+        ));
+        assert_ok!(StakedRelayers::withdraw_nomination(
+            Origin::signed(DAVE),
+            BOB
+        ));
+
+        // the unbonding nominator is slashed as well
+        assert_eq!(
+            crate::Call::<Test>::slash_staked_relayer(BOB)
+                .get_dispatch_info()
+                .weight,
+            <() as crate::WeightInfo>::slash_staked_relayer(2)
+        );
+
+        let slashed = Rc::new(Cell::new(0));
+        let slashed_clone = slashed.clone();
+        ext::collateral::slash_collateral::<Test>.mock_safe(move |_, receiver, amount| {
+            assert_eq!(receiver, ALICE);
+            slashed_clone.set(slashed_clone.get() + amount);
+            MockResult::Return(Ok(()))
+        });
+        assert_ok!(StakedRelayers::slash_staked_relayer(
+            Origin::signed(ALICE),
+            BOB
+        ));
+        assert_eq!(slashed.get(), 35);
+        assert_emitted!(Event::SlashNomination(CAROL, BOB, 10));
+        assert_emitted!(Event::SlashNomination(DAVE, BOB, 20));
+        assert_eq!(StakedRelayers::get_total_nominations(&BOB), 0);
+        assert_eq!(StakedRelayers::nominator_count(&BOB), 0);
+        assert_err!(
+            StakedRelayers::withdraw_unbonded_nomination(Origin::signed(DAVE), BOB),
+            TestError::NotUnbonding,
+        );
+    })
+}
+
 #[test]
 fn test_report_vault_theft_fails_with_not_registered() {
     run_test(|| {
@@ -796,7 +1590,8 @@ fn test_report_vault_theft_fails_with_not_registered() {
                 CAROL,
                 H256Le::zero(),
                 vec![0u8; 32],
-                vec![0u8; 32]
+                vec![0u8; 32],
+                vec![]
             ),
             TestError::NotRegistered,
         );
@@ -829,7 +1624,8 @@ fn test_report_vault_passes_with_vault_transaction() {
             CAROL,
             H256Le::zero(),
             vec![0u8; 32],
-            hex::decode(&raw_tx).unwrap()
+            hex::decode(&raw_tx).unwrap(),
+            vec![]
         ),);
     })
 }
@@ -861,7 +1657,8 @@ fn test_report_vault_fails_with_non_vault_transaction() {
                 CAROL,
                 H256Le::zero(),
                 vec![0u8; 32],
-                hex::decode(&raw_tx).unwrap()
+                hex::decode(&raw_tx).unwrap(),
+                vec![]
             ),
             TestError::VaultNoInputToTransaction
         );
@@ -877,9 +1674,9 @@ fn test_report_vault_succeeds_with_segwit_transaction() {
         inject_active_staked_relayer(&ALICE, amount);
         let vault = CAROL;
 
-        let btc_address = BtcAddress::P2WPKHv0(H160::from_slice(&[
-            164, 180, 202, 72, 222, 11, 63, 255, 193, 84, 4, 161, 172, 220, 141, 186, 174, 34, 105,
-            85,
+        // P2SH-P2WPKH
+        let btc_address = BtcAddress::P2SH(H160::from_slice(&[
+            41, 40, 244, 58, 241, 141, 45, 96, 232, 168, 67, 84, 13, 128, 134, 179, 5, 52, 19, 57,
         ]));
         ext::vault_registry::get_active_vault_from_id::<Test>.mock_safe(move |_| {
             MockResult::Return(Ok(init_zero_vault(vault.clone(), Some(btc_address))))
@@ -894,8 +1691,87 @@ fn test_report_vault_succeeds_with_segwit_transaction() {
             CAROL,
             H256Le::zero(),
             vec![0u8; 32],
-            hex::decode(&raw_tx).unwrap()
+            hex::decode(&raw_tx).unwrap(),
+            vec![]
+        ));
+    })
+}
+
+#[test]
+fn test_report_vault_theft_fails_with_too_many_previous_transactions() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        ext::btc_relay::verify_transaction_inclusion::<Test>
+            .mock_safe(|_, _| panic!("Transactions should not be verified"));
+
+        assert_err!(
+            StakedRelayers::report_vault_theft(
+                Origin::signed(ALICE),
+                CAROL,
+                H256Le::zero(),
+                vec![0u8; 32],
+                vec![0u8; 32],
+                vec![(vec![0u8; 32], vec![0u8; 32]); 3]
+            ),
+            TestError::TooManyPreviousTransactions
+        );
+    })
+}
+
+#[test]
+fn test_report_vault_theft_succeeds_with_previous_transaction() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        let vault_address = BtcAddress::P2WPKHv0(H160::random());
+        ext::vault_registry::get_active_vault_from_id::<Test>.mock_safe(move |_| {
+            MockResult::Return(Ok(init_zero_vault(CAROL, Some(vault_address))))
+        });
+        ext::btc_relay::verify_transaction_inclusion::<Test>
+            .mock_safe(move |_, _| MockResult::Return(Ok(())));
+        ext::vault_registry::liquidate_theft_vault::<Test>
+            .mock_safe(|_| MockResult::Return(Ok(())));
+
+        let previous_transaction = TransactionBuilder::new()
+            .with_version(2)
+            .add_input(TransactionInputBuilder::new().with_coinbase(false).build())
+            .add_output(TransactionOutput::payment(100, &BtcAddress::random()))
+            .add_output(TransactionOutput::payment(100, &vault_address))
+            .build();
+
+        // the input cannot be attributed to an address without the spent output
+        let transaction = TransactionBuilder::new()
+            .with_version(2)
+            .add_input(
+                TransactionInputBuilder::new()
+                    .with_coinbase(false)
+                    .with_previous_hash(previous_transaction.tx_id())
+                    .with_previous_index(1)
+                    .build(),
+            )
+            .add_output(TransactionOutput::payment(100, &BtcAddress::random()))
+            .build();
+
+        assert_noop!(
+            StakedRelayers::report_vault_theft(
+                Origin::signed(ALICE),
+                CAROL,
+                transaction.tx_id(),
+                vec![0u8; 32],
+                transaction.format(),
+                vec![]
+            ),
+            TestError::VaultNoInputToTransaction
+        );
+
+        assert_ok!(StakedRelayers::report_vault_theft(
+            Origin::signed(ALICE),
+            CAROL,
+            transaction.tx_id(),
+            vec![0u8; 32],
+            transaction.format(),
+            vec![(vec![0u8; 32], previous_transaction.format())]
         ));
+        assert_emitted!(Event::VaultTheft(CAROL, transaction.tx_id()));
     })
 }
 
@@ -908,7 +1784,8 @@ fn test_report_vault_theft_succeeds() {
 
         ext::btc_relay::verify_transaction_inclusion::<Test>
             .mock_safe(move |_, _| MockResult::Return(Ok(())));
-        StakedRelayers::is_transaction_invalid.mock_safe(move |_, _| MockResult::Return(Ok(())));
+        StakedRelayers::is_transaction_invalid_spending
+            .mock_safe(move |_, _, _| MockResult::Return(Ok(())));
         ext::vault_registry::liquidate_theft_vault::<Test>
             .mock_safe(move |_| MockResult::Return(Ok(())));
 
@@ -918,11 +1795,193 @@ fn test_report_vault_theft_succeeds() {
             H256Le::zero(),
             vec![0u8; 32],
             vec![0u8; 32],
+            vec![],
         ));
         assert_emitted!(Event::VaultTheft(BOB, H256Le::zero()));
     })
 }
 
+fn init_double_payment(request_id: H256) -> bitcoin::types::Transaction {
+    let vault_address = BtcAddress::P2PKH(H160::from_slice(&[
+        126, 125, 148, 208, 221, 194, 29, 131, 191, 188, 252, 119, 152, 228, 84, 126, 223, 8, 50,
+        170,
+    ]));
+    ext::vault_registry::get_active_vault_from_id::<Test>
+        .mock_safe(move |_| MockResult::Return(Ok(init_zero_vault(BOB, Some(vault_address)))));
+
+    TransactionBuilder::new()
+        .with_version(1)
+        .add_input(
+            TransactionInputBuilder::new()
+                .with_coinbase(false)
+                .with_sequence(4294967295)
+                .with_previous_index(1)
+                .with_previous_hash(H256Le::from_bytes_le(&[
+                    193, 80, 65, 160, 109, 235, 107, 56, 24, 176, 34, 250, 197, 88, 218, 76, 226,
+                    9, 127, 8, 96, 200, 246, 66, 16, 91, 186, 217, 210, 155, 224, 42,
+                ]))
+                .with_script(&[
+                    73, 48, 70, 2, 33, 0, 207, 210, 162, 211, 50, 178, 154, 220, 225, 25, 197, 90,
+                    159, 173, 211, 192, 115, 51, 32, 36, 183, 226, 114, 81, 62, 81, 98, 60, 161,
+                    89, 147, 72, 2, 33, 0, 155, 72, 45, 127, 123, 77, 71, 154, 255, 98, 189, 205,
+                    174, 165, 70, 103, 115, 125, 86, 248, 212, 214, 61, 208, 62, 195, 239, 101, 30,
+                    217, 162, 84, 1, 33, 3, 37, 248, 176, 57, 161, 24, 97, 101, 156, 155, 240, 63,
+                    67, 252, 78, 160, 85, 243, 167, 28, 214, 12, 123, 31, 212, 116, 171, 87, 143,
+                    153, 119, 250,
+                ])
+                .build(),
+        )
+        .add_output(TransactionOutput::payment(
+            100,
+            &BtcAddress::P2PKH(
+                H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap(),
+            ),
+        ))
+        .add_output(TransactionOutput::op_return(0, request_id.as_bytes()))
+        .build()
+}
+
+#[test]
+fn test_report_vault_double_payment_fails_with_not_registered() {
+    run_test(|| {
+        assert_err!(
+            StakedRelayers::report_vault_double_payment(
+                Origin::signed(ALICE),
+                BOB,
+                H256Le::zero(),
+                vec![0u8; 32],
+                vec![0u8; 32]
+            ),
+            TestError::NotRegistered,
+        );
+    })
+}
+
+#[test]
+fn test_report_vault_double_payment_fails_with_request_not_fulfilled() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        let transaction = init_double_payment(H256::random());
+        ext::btc_relay::verify_transaction_inclusion::<Test>
+            .mock_safe(move |_, _| MockResult::Return(Ok(())));
+
+        assert_err!(
+            StakedRelayers::report_vault_double_payment(
+                Origin::signed(ALICE),
+                BOB,
+                transaction.tx_id(),
+                vec![0u8; 32],
+                transaction.format()
+            ),
+            TestError::RequestNotFulfilled,
+        );
+    })
+}
+
+#[test]
+fn test_report_vault_double_payment_fails_with_transaction_fulfilled_request() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        let transaction = init_double_payment(H256::random());
+        let tx_id = transaction.tx_id();
+        ext::btc_relay::verify_transaction_inclusion::<Test>
+            .mock_safe(move |_, _| MockResult::Return(Ok(())));
+        ext::btc_relay::get_fulfilling_transaction::<Test>
+            .mock_safe(move |_| MockResult::Return(Some(tx_id)));
+
+        assert_err!(
+            StakedRelayers::report_vault_double_payment(
+                Origin::signed(ALICE),
+                BOB,
+                tx_id,
+                vec![0u8; 32],
+                transaction.format()
+            ),
+            TestError::TransactionFulfilledRequest,
+        );
+    })
+}
+
+#[test]
+fn test_report_vault_double_payment_succeeds() {
+    run_test(|| {
+        inject_active_staked_relayer(&ALICE, 3);
+        let request_id = H256::random();
+        let transaction = init_double_payment(request_id);
+        let tx_id = transaction.tx_id();
+        ext::btc_relay::verify_transaction_inclusion::<Test>
+            .mock_safe(move |_, _| MockResult::Return(Ok(())));
+        ext::btc_relay::get_fulfilling_transaction::<Test>.mock_safe(move |consumer| {
+            MockResult::Return(match consumer {
+                TransactionConsumer::Redeem(id) if id == request_id => Some(H256Le::zero()),
+                _ => None,
+            })
+        });
+        ext::vault_registry::liquidate_theft_vault::<Test>
+            .mock_safe(move |_| MockResult::Return(Ok(())));
+
+        assert_ok!(StakedRelayers::report_vault_double_payment(
+            Origin::signed(ALICE),
+            BOB,
+            tx_id,
+            vec![0u8; 32],
+            transaction.format()
+        ));
+        assert_emitted!(Event::VaultDoublePayment(BOB, tx_id, request_id));
+
+        // the same transaction cannot be reported twice
+        assert_err!(
+            StakedRelayers::report_vault_double_payment(
+                Origin::signed(ALICE),
+                BOB,
+                tx_id,
+                vec![0u8; 32],
+                transaction.format()
+            ),
+            TestError::VaultAlreadyReported,
+        );
+    })
+}
+
+#[test]
+fn test_is_transaction_invalid_succeeds_with_double_payment() {
+    run_test(|| {
+        let request_id = H256::random();
+        let transaction = init_double_payment(request_id);
+        ext::redeem::get_open_or_completed_redeem_request_from_id::<Test>.mock_safe(move |_| {
+            MockResult::Return(Ok(RedeemRequest {
+                vault: BOB,
+                opentime: 0,
+                amount_polka_btc: 0,
+                fee: 0,
+                amount_btc: 100,
+                amount_dot: 0,
+                premium_dot: 0,
+                redeemer: ALICE,
+                btc_address: BtcAddress::P2PKH(
+                    H160::from_str(&"66c7060feb882664ae62ffad0051fe843e318e85").unwrap(),
+                ),
+                completed: true,
+                cancelled: false,
+                reimburse: false,
+            }))
+        });
+
+        // the payment is valid for the request as long as it fulfilled it
+        assert_err!(
+            StakedRelayers::is_transaction_invalid(&BOB, transaction.format()),
+            TestError::ValidRedeemTransaction
+        );
+
+        ext::btc_relay::get_fulfilling_transaction::<Test>
+            .mock_safe(move |_| MockResult::Return(Some(H256Le::zero())));
+        assert_ok!(StakedRelayers::is_transaction_invalid(
+            &BOB,
+            transaction.format()
+        ));
+    })
+}
+
 #[test]
 fn test_report_vault_under_liquidation_threshold_fails() {
     run_test(|| {
@@ -997,8 +2056,8 @@ fn test_report_oracle_offline_fails_with_already_reported() {
         let amount: Balance = 3;
         inject_active_staked_relayer(&ALICE, amount);
 
-        ext::security::get_errors::<Test>
-            .mock_safe(|| MockResult::Return([ErrorCode::OracleOffline].iter().cloned().collect()));
+        ext::oracle::is_max_delay_passed::<Test>.mock_safe(|| MockResult::Return(true));
+        assert_ok!(StakedRelayers::report_oracle_offline(relayer.clone()));
         assert_err!(
             StakedRelayers::report_oracle_offline(relayer),
             TestError::OracleAlreadyReported,
@@ -1035,6 +2094,38 @@ fn test_report_oracle_offline_succeeds() {
     })
 }
 
+#[test]
+fn test_report_oracle_offline_rewards_first_reporter_of_detected_outage() {
+    run_test(|| {
+        let relayer = Origin::signed(ALICE);
+        let amount: Balance = 3;
+        inject_active_staked_relayer(&ALICE, amount);
+
+        ext::oracle::is_max_delay_passed::<Test>.mock_safe(|| MockResult::Return(true));
+        ext::security::get_errors::<Test>
+            .mock_safe(|| MockResult::Return([ErrorCode::OracleOffline].iter().cloned().collect()));
+        ext::security::insert_error::<Test>
+            .mock_safe(|_| panic!("Should not raise the error again"));
+
+        let rewarded = Rc::new(Cell::new(false));
+        let rewarded_ref = rewarded.clone();
+        ext::sla::event_update_relayer_sla::<Test>.mock_safe(move |_, event| {
+            assert!(matches!(
+                event,
+                ext::sla::RelayerEvent::CorrectOracleOfflineReport
+            ));
+            rewarded_ref.set(true);
+            MockResult::Return(Ok(()))
+        });
+        assert_ok!(StakedRelayers::report_oracle_offline(relayer.clone()));
+        assert!(rewarded.get());
+
+        // a new outage can be reported again
+        ext::oracle::get_last_exchange_rate_time::<Test>.mock_safe(|| MockResult::Return(1));
+        assert_ok!(StakedRelayers::report_oracle_offline(relayer));
+    })
+}
+
 #[test]
 fn test_is_valid_merge_transaction_fails() {
     run_test(|| {
@@ -1215,9 +2306,9 @@ fn test_is_transaction_invalid_fails_with_valid_merge_transaction() {
 #[test]
 fn test_is_transaction_invalid_fails_with_valid_request_or_redeem() {
     run_test(|| {
-        let vault_address = BtcAddress::P2WPKHv0(H160::from_slice(&[
-            164, 180, 202, 72, 222, 11, 63, 255, 193, 84, 4, 161, 172, 220, 141, 186, 174, 34, 105,
-            85,
+        // P2SH-P2WPKH
+        let vault_address = BtcAddress::P2SH(H160::from_slice(&[
+            41, 40, 244, 58, 241, 141, 45, 96, 232, 168, 67, 84, 13, 128, 134, 179, 5, 52, 19, 57,
         ]));
 
         let mut wallet = Wallet::new(dummy_public_key());
@@ -1373,8 +2464,8 @@ fn test_is_transaction_invalid_fails_with_valid_merge_testnet_transaction() {
         let raw_tx = hex::decode(&raw_tx_hex).unwrap();
 
         // 2MsqorfMrsvXiVM8pD9bPWxGnccSWsj16XE (P2WPKH-P2SH)
-        let vault_btc_address_0 = BtcAddress::P2WPKHv0(H160::from_slice(
-            &hex::decode("473ca3f4d726ce9c21af7cdc3fcc13264f681b04").unwrap(),
+        let vault_btc_address_0 = BtcAddress::P2SH(H160::from_slice(
+            &hex::decode("068a6a2ec6be7d6e7aac1657445154c52db0cef8").unwrap(),
         ));
 
         // 2NGRwGkzypA4fEz9m4KhA2ZBs7fTg3B7Zjo
@@ -1419,8 +2510,8 @@ fn test_is_transaction_invalid_succeeds_with_testnet_transaction() {
         let raw_tx = hex::decode(&raw_tx_hex).unwrap();
 
         // 2MsqorfMrsvXiVM8pD9bPWxGnccSWsj16XE (P2WPKH-P2SH)
-        let btc_address = BtcAddress::P2WPKHv0(H160::from_slice(
-            &hex::decode("473ca3f4d726ce9c21af7cdc3fcc13264f681b04").unwrap(),
+        let btc_address = BtcAddress::P2SH(H160::from_slice(
+            &hex::decode("068a6a2ec6be7d6e7aac1657445154c52db0cef8").unwrap(),
         ));
 
         ext::vault_registry::get_active_vault_from_id::<Test>
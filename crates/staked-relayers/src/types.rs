@@ -2,7 +2,8 @@ use bitcoin::types::*;
 use codec::{Decode, Encode};
 use frame_support::traits::Currency;
 use security::types::{ErrorCode, StatusCode};
-use sp_arithmetic::traits::Saturating;
+use sp_arithmetic::traits::{AtLeast32BitUnsigned, Saturating};
+use sp_arithmetic::Permill;
 use sp_std::cmp::Ord;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::fmt::Debug;
@@ -15,6 +16,11 @@ pub(crate) type PolkaBTC<T> = <<T as treasury::Config>::PolkaBTC as Currency<
     <T as frame_system::Config>::AccountId,
 >>::Balance;
 
+pub(crate) type VoteAttestationOf<T> = VoteAttestation<
+    <T as frame_system::offchain::SigningTypes>::Public,
+    <T as frame_system::offchain::SigningTypes>::Signature,
+>;
+
 pub type StatusUpdateId = u64;
 
 /// Indicates the state of a proposed StatusUpdate.
@@ -60,10 +66,45 @@ pub struct StatusUpdate<AccountId: Ord + Clone, BlockNumber, DOT: Clone + Partia
     pub deposit: DOT,
     /// Bookkeeping for this proposal.
     pub tally: Tally<AccountId, DOT>,
+    /// Total active stake when this proposal was suggested, against which the quorum is measured.
+    pub total_active_stake: DOT,
     /// Message providing more details on the change of status (detailed error message or recovery reason).
     pub message: Vec<u8>,
 }
 
+/// Thresholds a `StatusUpdate` must reach when it is evaluated at its end.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct VoteThreshold {
+    /// Minimum stake that must vote, as a fraction of the total active stake.
+    pub quorum: Permill,
+    /// Fraction of the participating stake that the aye stake must exceed.
+    pub approval: Permill,
+}
+
+impl Default for VoteThreshold {
+    /// No quorum and a simple majority.
+    fn default() -> Self {
+        VoteThreshold {
+            quorum: Permill::zero(),
+            approval: Permill::from_percent(50),
+        }
+    }
+}
+
+/// Vote of a staked relayer on a `StatusUpdate`, signed off-chain with its relayer key
+/// so that it can be submitted by any account.
+#[derive(Encode, Decode, Clone, PartialEq, Debug)]
+pub struct VoteAttestation<Public, Signature> {
+    /// Public key of the staked relayer casting the vote.
+    pub signer: Public,
+    /// Identifier of the `StatusUpdate` voted upon.
+    pub status_update_id: StatusUpdateId,
+    /// `True` or `False`, depending on whether the staked relayer agrees with the `StatusUpdate`.
+    pub approve: bool,
+    /// Signature over the payload returned by `get_vote_attestation_payload`.
+    pub signature: Signature,
+}
+
 #[derive(Encode, Decode, Default, Clone, PartialEq, Debug)]
 pub struct Votes<AccountId: Ord, Balance: Clone + Saturating> {
     pub(crate) accounts: BTreeSet<AccountId>,
@@ -91,12 +132,27 @@ pub struct Tally<AccountId: Ord, Balance: Clone + PartialOrd + Saturating> {
     pub(crate) nay: Votes<AccountId, Balance>,
 }
 
-impl<AccountId: Ord + Clone, Balance: Clone + PartialOrd + Saturating> Tally<AccountId, Balance> {
-    /// Returns true if the majority of votes are in favour.
-    pub(crate) fn is_approved(&self) -> bool {
-        self.aye.total_stake > self.nay.total_stake
+impl<AccountId: Ord + Clone, Balance: AtLeast32BitUnsigned + Clone> Tally<AccountId, Balance> {
+    /// Returns the total stake of all votes.
+    pub(crate) fn participation(&self) -> Balance {
+        self.aye
+            .total_stake
+            .clone()
+            .saturating_add(self.nay.total_stake.clone())
+    }
+
+    /// Returns true if at least the `quorum` fraction of the total stake has voted.
+    pub(crate) fn has_quorum(&self, quorum: Permill, total_stake: Balance) -> bool {
+        self.participation() >= quorum.mul_ceil(total_stake)
     }
 
+    /// Returns true if the votes in favour exceed the `approval` fraction of all votes.
+    pub(crate) fn is_approved(&self, approval: Permill) -> bool {
+        self.aye.total_stake > approval.mul_floor(self.participation())
+    }
+}
+
+impl<AccountId: Ord + Clone, Balance: Clone + PartialOrd + Saturating> Tally<AccountId, Balance> {
     /// Checks if the account has already voted in this poll.
     pub(crate) fn contains(&self, id: &AccountId) -> bool {
         self.nay.contains(&id) || self.aye.contains(&id)
@@ -6,8 +6,9 @@
 #![warn(missing_docs)]
 
 use btc_parachain_runtime::{
-    opaque::Block, AccountId, Balance, BlockNumber, Index, IssueRequest, RedeemRequest,
-    RefundRequest, ReplaceRequest,
+    opaque::Block, AccountId, Balance, BlockChain, BlockNumber, H256Le, Index, IssueRequest,
+    Operation, RedeemRequest, RefundRequest, ReplaceRequest, RichBlockHeader, StatusChange,
+    TransactionConsumer, VerificationProfile, VolumeType,
 };
 pub use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
@@ -38,8 +39,23 @@ where
     C: Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: module_btc_relay_rpc::BtcRelayRuntimeApi<
+        Block,
+        H256Le,
+        RichBlockHeader<AccountId>,
+        BlockChain,
+        TransactionConsumer,
+        VerificationProfile,
+    >,
     C::Api: module_exchange_rate_oracle_rpc::ExchangeRateOracleRuntimeApi<Block, Balance, Balance>,
     C::Api: module_staked_relayers_rpc::StakedRelayersRuntimeApi<Block, AccountId>,
+    C::Api: module_security_rpc::SecurityRuntimeApi<
+        Block,
+        Operation,
+        StatusChange<BlockNumber, u64>,
+        VolumeType,
+        Balance,
+    >,
     C::Api: module_vault_registry_rpc::VaultRegistryRuntimeApi<
         Block,
         AccountId,
@@ -74,11 +90,13 @@ where
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
 {
+    use module_btc_relay_rpc::{BtcRelay, BtcRelayApi};
     use module_exchange_rate_oracle_rpc::{ExchangeRateOracle, ExchangeRateOracleApi};
     use module_issue_rpc::{Issue, IssueApi};
     use module_redeem_rpc::{Redeem, RedeemApi};
     use module_refund_rpc::{Refund, RefundApi};
     use module_replace_rpc::{Replace, ReplaceApi};
+    use module_security_rpc::{Security, SecurityApi};
     use module_staked_relayers_rpc::{StakedRelayers, StakedRelayersApi};
     use module_vault_registry_rpc::{VaultRegistry, VaultRegistryApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
@@ -101,6 +119,8 @@ where
         client.clone(),
     )));
 
+    io.extend_with(BtcRelayApi::to_delegate(BtcRelay::new(client.clone())));
+
     io.extend_with(ExchangeRateOracleApi::to_delegate(ExchangeRateOracle::new(
         client.clone(),
     )));
@@ -121,5 +141,7 @@ where
 
     io.extend_with(ReplaceApi::to_delegate(Replace::new(client.clone())));
 
+    io.extend_with(SecurityApi::to_delegate(Security::new(client.clone())));
+
     io
 }
@@ -8,6 +8,7 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::Encode;
 use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::traits::StorageMapShim;
 use sp_arithmetic::{FixedI128, FixedU128};
@@ -17,9 +18,12 @@ use sp_api::impl_runtime_apis;
 use sp_core::OpaqueMetadata;
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
-    traits::{BlakeTwo256, Block as BlockT, Convert, IdentifyAccount, IdentityLookup, Verify},
+    traits::{
+        BlakeTwo256, Block as BlockT, Convert, Extrinsic as ExtrinsicT, IdentifyAccount,
+        IdentityLookup, Verify,
+    },
     transaction_validity::{TransactionSource, TransactionValidity},
-    ApplyExtrinsicResult, MultiSignature,
+    ApplyExtrinsicResult, MultiSignature, SaturatedConversion,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -45,7 +49,11 @@ pub use sp_runtime::{Perbill, Permill};
 
 // PolkaBTC exports
 pub use btc_relay::bitcoin;
+pub use btc_relay::bitcoin::types::{BlockChain, H256Le};
 pub use btc_relay::Call as RelayCall;
+pub use btc_relay::{
+    BitcoinNetwork, NetworkParameters, RichBlockHeader, TransactionConsumer, VerificationProfile,
+};
 pub use module_exchange_rate_oracle_rpc_runtime_api::BalanceWrapper;
 
 // XCM imports
@@ -420,9 +428,78 @@ impl pallet_balances::Config<pallet_balances::Instance2> for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub BitcoinTestnet: NetworkParameters = BitcoinNetwork::Testnet.parameters();
+    pub BitcoinMainnet: NetworkParameters = BitcoinNetwork::Mainnet.parameters();
+}
+
+/// Relays Bitcoin testnet, used to execute requests
 impl btc_relay::Config for Runtime {
     type Event = Event;
     type WeightInfo = ();
+    type Network = BitcoinTestnet;
+    type OnReorg = (Issue, Redeem, Refund);
+    type OffchainWorker = btc_relay::BitcoindRelayer<Runtime>;
+}
+
+impl btc_relay::OffchainConfig for Runtime {
+    type AuthorityId = btc_relay::crypto::RelayerId;
+}
+
+/// Relays Bitcoin mainnet
+impl btc_relay::Config<btc_relay::Instance1> for Runtime {
+    type Event = Event;
+    type WeightInfo = ();
+    type Network = BitcoinMainnet;
+    type OnReorg = ();
+    type OffchainWorker = ();
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+    type Public = <Signature as Verify>::Signer;
+    type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+    Call: From<C>,
+{
+    type OverarchingCall = Call;
+    type Extrinsic = UncheckedExtrinsic;
+}
+
+/// Signs transactions submitted by off-chain workers with a node-local key
+impl<C> frame_system::offchain::CreateSignedTransaction<C> for Runtime
+where
+    Call: From<C>,
+{
+    fn create_transaction<A: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+        call: Call,
+        public: <Signature as Verify>::Signer,
+        account: AccountId,
+        nonce: Index,
+    ) -> Option<(Call, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+        let period = BlockHashCount::get()
+            .checked_next_power_of_two()
+            .map(|c| c / 2)
+            .unwrap_or(2) as u64;
+        let current_block = System::block_number()
+            .saturated_into::<u64>()
+            .saturating_sub(1);
+        let extra: SignedExtra = (
+            frame_system::CheckSpecVersion::<Runtime>::new(),
+            frame_system::CheckTxVersion::<Runtime>::new(),
+            frame_system::CheckGenesis::<Runtime>::new(),
+            frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+            frame_system::CheckNonce::<Runtime>::from(nonce),
+            frame_system::CheckWeight::<Runtime>::new(),
+            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+        );
+        let raw_payload = SignedPayload::new(call, extra).ok()?;
+        let signature = raw_payload.using_encoded(|payload| A::sign(payload, public))?;
+        let (call, extra, _) = raw_payload.deconstruct();
+        Some((call, (account, signature, extra)))
+    }
 }
 
 pub use collateral::RawEvent as CollateralEvent;
@@ -439,26 +516,46 @@ impl treasury::Config for Runtime {
     type PolkaBTC = pallet_balances::Module<Runtime, pallet_balances::Instance2>;
 }
 
+parameter_types! {
+    pub const MaxStatusHistory: u32 = 100;
+}
+
 impl security::Config for Runtime {
     type Event = Event;
+    type MaxStatusHistory = MaxStatusHistory;
+    // WARNING: this runtime does NOT provide unpredictable secure ids. The collective
+    // flip is derived from the hashes of recent parachain blocks, so collators can
+    // predict it, and bias it by choosing which blocks to author. Neither Aura nor the
+    // rococo-v1 parachain system expose the relay chain (BABE) VRF output, which is
+    // required to make issue ids and deposit addresses unpredictable.
+    type RandomnessSource = RandomnessCollectiveFlip;
 }
 
+pub use security::{Operation, StatusChange, VolumeType};
+
 parameter_types! {
     pub const MinimumDeposit: u32 = 10;
     pub const MinimumStake: u32 = 10;
+    pub const MinimumNomination: u32 = 10;
+    pub const MaxNominatorsPerRelayer: u32 = 64;
     pub const VotingPeriod: BlockNumber = DAYS;
     pub const MaximumMessageSize: u32 = 256;
+    pub const MaxPreviousTransactions: u32 = 10;
 }
 
 pub use staked_relayers::RawEvent as StakedRelayersEvent;
 
 impl staked_relayers::Config for Runtime {
     type Event = Event;
+    type AuthorityId = staked_relayers::crypto::VoteId;
     type WeightInfo = ();
     type MinimumDeposit = MinimumDeposit;
     type MinimumStake = MinimumStake;
+    type MinimumNomination = MinimumNomination;
+    type MaxNominatorsPerRelayer = MaxNominatorsPerRelayer;
     type VotingPeriod = VotingPeriod;
     type MaximumMessageSize = MaximumMessageSize;
+    type MaxPreviousTransactions = MaxPreviousTransactions;
 }
 
 pub use vault_registry::RawEvent as VaultRegistryEvent;
@@ -482,6 +579,7 @@ impl fee::Config for Runtime {
     type Event = Event;
     type UnsignedFixedPoint = FixedU128;
     type WeightInfo = ();
+    type RelayerRewards = StakedRelayers;
 }
 
 impl sla::Config for Runtime {
@@ -540,7 +638,8 @@ macro_rules! construct_polkabtc_runtime {
                 Treasury: treasury::{Module, Call, Storage, Event<T>},
 
                 // Bitcoin SPV
-                BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event},
+                BTCRelay: btc_relay::{Module, Call, Config<T>, Storage, Event<T>},
+                BTCRelayMainnet: btc_relay::<Instance1>::{Module, Call, Config<T>, Storage, Event<T>},
 
                 // Operational
                 Security: security::{Module, Call, Storage, Event},
@@ -596,6 +695,8 @@ pub type SignedExtra = (
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
@@ -782,6 +883,47 @@ impl_runtime_apis! {
         }
     }
 
+    impl module_btc_relay_rpc_runtime_api::BtcRelayApi<
+        Block,
+        H256Le,
+        RichBlockHeader<AccountId>,
+        BlockChain,
+        TransactionConsumer,
+        VerificationProfile,
+    > for Runtime {
+        fn get_best_block() -> H256Le {
+            BTCRelay::get_best_block()
+        }
+
+        fn get_best_block_height() -> u32 {
+            BTCRelay::get_best_block_height()
+        }
+
+        fn get_block_header(block_hash: H256Le) -> Result<RichBlockHeader<AccountId>, DispatchError> {
+            BTCRelay::get_block_header_from_hash(block_hash)
+        }
+
+        fn get_block_header_at_height(block_height: u32) -> Result<RichBlockHeader<AccountId>, DispatchError> {
+            BTCRelay::get_block_header_at_height(block_height)
+        }
+
+        fn get_chains() -> Vec<BlockChain> {
+            BTCRelay::get_chains()
+        }
+
+        fn is_transaction_verifiable(tx_id: H256Le, raw_merkle_proof: Vec<u8>, confirmations: Option<u32>) -> DispatchResult {
+            BTCRelay::_verify_transaction_inclusion(tx_id, raw_merkle_proof, confirmations)
+        }
+
+        fn get_transaction_consumers(tx_id: H256Le) -> Vec<(u32, TransactionConsumer)> {
+            BTCRelay::get_transaction_consumers(tx_id)
+        }
+
+        fn get_verification_profile() -> VerificationProfile {
+            BTCRelay::verification_profile()
+        }
+    }
+
     impl module_exchange_rate_oracle_rpc_runtime_api::ExchangeRateOracleApi<
         Block,
         Balance,
@@ -928,6 +1070,26 @@ impl_runtime_apis! {
         }
     }
 
+    impl module_security_rpc_runtime_api::SecurityApi<
+        Block,
+        Operation,
+        StatusChange<BlockNumber, u64>,
+        VolumeType,
+        Balance,
+    > for Runtime {
+        fn get_paused_operations() -> Vec<Operation> {
+            Security::get_paused_operations().into_iter().collect()
+        }
+
+        fn get_status_history() -> Vec<StatusChange<BlockNumber, u64>> {
+            Security::status_history()
+        }
+
+        fn get_remaining_volume(volume_type: VolumeType) -> Option<BalanceWrapper<Balance>> {
+            Security::get_remaining_volume(volume_type).map(|amount| BalanceWrapper{amount})
+        }
+    }
+
 }
 
 #[cfg(feature = "cumulus-polkadot")]
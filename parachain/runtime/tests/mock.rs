@@ -3,7 +3,7 @@ extern crate hex;
 pub use bitcoin::formatter::{Formattable, TryFormattable};
 pub use bitcoin::types::*;
 pub use btc_parachain_runtime::{AccountId, Call, Event, Runtime};
-pub use btc_relay::{BtcAddress, BtcPublicKey};
+pub use btc_relay::{BtcAddress, BtcPublicKey, VerificationProfile};
 pub use frame_support::{assert_noop, assert_ok};
 pub use mocktopus::mocking::*;
 use primitive_types::{H256, U256};
@@ -26,7 +26,12 @@ pub const CONFIRMATIONS: u32 = 6;
 pub type BTCRelayCall = btc_relay::Call<Runtime>;
 pub type BTCRelayModule = btc_relay::Module<Runtime>;
 pub type BTCRelayError = btc_relay::Error<Runtime>;
-pub type BTCRelayEvent = btc_relay::Event;
+pub type BTCRelayEvent = btc_relay::Event<Runtime>;
+
+pub type BTCRelayMainnetCall = btc_relay::Call<Runtime, btc_relay::Instance1>;
+pub type BTCRelayMainnetModule = btc_relay::Module<Runtime, btc_relay::Instance1>;
+pub type BTCRelayMainnetError = btc_relay::Error<Runtime, btc_relay::Instance1>;
+pub type BTCRelayMainnetEvent = btc_relay::Event<Runtime, btc_relay::Instance1>;
 
 pub fn origin_of(account_id: AccountId) -> <Runtime as frame_system::Config>::Origin {
     <Runtime as frame_system::Config>::Origin::signed(account_id)
@@ -99,6 +104,16 @@ pub fn assert_store_main_chain_header_event(height: u32, hash: H256Le) {
     assert!(events.iter().any(|a| a.event == store_event));
 }
 
+#[allow(dead_code)]
+pub fn assert_store_mainnet_main_chain_header_event(height: u32, hash: H256Le) {
+    let store_event =
+        Event::btc_relay_Instance1(BTCRelayMainnetEvent::StoreMainChainHeader(height, hash));
+    let events = SystemModule::events();
+
+    // store only main chain header
+    assert!(events.iter().any(|a| a.event == store_event));
+}
+
 #[allow(dead_code)]
 pub fn generate_transaction_and_mine(
     address: BtcAddress,
@@ -195,7 +210,8 @@ pub fn generate_transaction_and_mine_with_script_sig(
     let raw_tx = transaction.format_with(true);
 
     assert_ok!(Call::BTCRelay(BTCRelayCall::store_block_header(
-        raw_block_header.try_into().expect("bad block header")
+        raw_block_header.try_into().expect("bad block header"),
+        0
     ))
     .dispatch(origin_of(account_of(ALICE))));
     assert_store_main_chain_header_event(height, block.header.hash().unwrap());
@@ -219,6 +235,7 @@ pub fn generate_transaction_and_mine_with_script_sig(
                 .expect("could not serialize block header");
         assert_ok!(Call::BTCRelay(BTCRelayCall::store_block_header(
             raw_conf_block_header.try_into().expect("bad block header"),
+            0,
         ))
         .dispatch(origin_of(account_of(ALICE))));
 
@@ -298,10 +315,29 @@ impl ExtBuilder {
         btc_relay::GenesisConfig::<Runtime> {
             bitcoin_confirmations: CONFIRMATIONS,
             parachain_confirmations: CONFIRMATIONS,
-            disable_difficulty_check: false,
-            disable_inclusion_check: false,
-            disable_op_return_check: false,
-            disable_relayer_auth: true,
+            checkpoints: vec![],
+            pruning_depth: 0,
+            max_fork_depth: 0,
+            max_pruned_headers_per_block: 0,
+            verification_profile: VerificationProfile {
+                relayer_auth: false,
+                ..Default::default()
+            },
+        }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
+        btc_relay::GenesisConfig::<Runtime, btc_relay::Instance1> {
+            bitcoin_confirmations: CONFIRMATIONS,
+            parachain_confirmations: CONFIRMATIONS,
+            checkpoints: vec![],
+            pruning_depth: 0,
+            max_fork_depth: 0,
+            max_pruned_headers_per_block: 0,
+            verification_profile: VerificationProfile {
+                relayer_auth: false,
+                ..Default::default()
+            },
         }
         .assimilate_storage(&mut storage)
         .unwrap();
@@ -356,6 +392,7 @@ impl ExtBuilder {
             relayer_rewards: FixedU128::checked_from_rational(10, 100).unwrap(), // 10%
             maintainer_rewards: FixedU128::from(0),                            // 0%
             collator_rewards: FixedU128::from(0),                              // 0%
+            header_submission_reward: 0,
         }
         .assimilate_storage(&mut storage)
         .unwrap();
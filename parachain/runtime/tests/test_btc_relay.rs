@@ -4,8 +4,6 @@ mod mock;
 use bitcoin_data::get_bitcoin_testdata;
 use mock::*;
 
-type BTCRelayError = btc_relay::Error<Runtime>;
-
 #[test]
 fn integration_test_submit_block_headers_and_verify_transaction_inclusion() {
     ExtBuilder::build().execute_with(|| {
@@ -18,46 +16,128 @@ fn integration_test_submit_block_headers_and_verify_transaction_inclusion() {
         // known in the parachain. Any block before will be rejected
         let parachain_genesis_height = test_data[0].height;
         let parachain_genesis_header = test_data[0].get_raw_header();
-        assert_ok!(Call::BTCRelay(BTCRelayCall::initialize(
+        assert_ok!(Call::BTCRelayMainnet(BTCRelayMainnetCall::initialize(
             parachain_genesis_header,
             parachain_genesis_height
         ))
         .dispatch(origin_of(account_of(ALICE))));
         for block in test_data.iter().skip(1) {
             assert_ok!(
-                Call::BTCRelay(BTCRelayCall::store_block_header(block.get_raw_header()))
-                    .dispatch(origin_of(account_of(ALICE)))
+                Call::BTCRelayMainnet(BTCRelayMainnetCall::store_block_header(
+                    block.get_raw_header(),
+                    0
+                ))
+                .dispatch(origin_of(account_of(ALICE)))
             );
 
-            assert_store_main_chain_header_event(block.height, block.get_block_hash());
+            assert_store_mainnet_main_chain_header_event(block.height, block.get_block_hash());
         }
         SystemModule::set_block_number(1 + CONFIRMATIONS);
         // verify all transaction
-        let current_height = btc_relay::Module::<Runtime>::get_best_block_height();
+        let current_height = BTCRelayMainnetModule::get_best_block_height();
         for block in test_data.iter() {
             for tx in &block.test_txs {
                 let txid = tx.get_txid();
                 let raw_merkle_proof = tx.get_raw_merkle_proof();
                 if block.height <= current_height - CONFIRMATIONS {
-                    assert_ok!(Call::BTCRelay(BTCRelayCall::verify_transaction_inclusion(
-                        txid,
-                        raw_merkle_proof,
-                        None,
-                    ))
+                    assert_ok!(Call::BTCRelayMainnet(
+                        BTCRelayMainnetCall::verify_transaction_inclusion(
+                            txid,
+                            raw_merkle_proof,
+                            None,
+                        )
+                    )
                     .dispatch(origin_of(account_of(ALICE))));
                 } else {
                     // expect to fail due to insufficient confirmations
                     assert_noop!(
-                        Call::BTCRelay(BTCRelayCall::verify_transaction_inclusion(
+                        Call::BTCRelayMainnet(BTCRelayMainnetCall::verify_transaction_inclusion(
                             txid,
                             raw_merkle_proof,
                             None,
                         ))
                         .dispatch(origin_of(account_of(ALICE))),
-                        BTCRelayError::BitcoinConfirmations
+                        BTCRelayMainnetError::BitcoinConfirmations
                     );
                 }
             }
         }
     })
 }
+
+#[test]
+fn integration_test_relay_instances_are_independent() {
+    ExtBuilder::build().execute_with(|| {
+        let test_data = get_bitcoin_testdata();
+
+        SystemModule::set_block_number(1);
+
+        assert_eq!(
+            BTCRelayModule::network_parameters(),
+            btc_relay::BitcoinNetwork::Testnet.parameters()
+        );
+        assert_eq!(
+            BTCRelayMainnetModule::network_parameters(),
+            btc_relay::BitcoinNetwork::Mainnet.parameters()
+        );
+
+        // mainnet headers are only stored by the mainnet instance
+        assert_ok!(Call::BTCRelayMainnet(BTCRelayMainnetCall::initialize(
+            test_data[0].get_raw_header(),
+            test_data[0].height
+        ))
+        .dispatch(origin_of(account_of(ALICE))));
+        assert_ok!(
+            Call::BTCRelayMainnet(BTCRelayMainnetCall::store_block_header(
+                test_data[1].get_raw_header(),
+                0
+            ))
+            .dispatch(origin_of(account_of(ALICE)))
+        );
+        assert_store_mainnet_main_chain_header_event(
+            test_data[1].height,
+            test_data[1].get_block_hash(),
+        );
+        assert_noop!(
+            BTCRelayModule::get_block_header_from_hash(test_data[1].get_block_hash()),
+            BTCRelayError::BlockNotFound
+        );
+
+        // the testnet instance is initialized from a different block
+        assert_ok!(Call::BTCRelay(BTCRelayCall::initialize(
+            test_data[5].get_raw_header(),
+            test_data[5].height
+        ))
+        .dispatch(origin_of(account_of(ALICE))));
+        assert_noop!(
+            Call::BTCRelay(BTCRelayCall::store_block_header(
+                test_data[2].get_raw_header(),
+                0
+            ))
+            .dispatch(origin_of(account_of(ALICE))),
+            BTCRelayError::BlockNotFound
+        );
+
+        assert_ok!(
+            Call::BTCRelayMainnet(BTCRelayMainnetCall::store_block_header(
+                test_data[2].get_raw_header(),
+                0
+            ))
+            .dispatch(origin_of(account_of(ALICE)))
+        );
+
+        assert_eq!(
+            BTCRelayModule::get_best_block(),
+            test_data[5].get_block_hash()
+        );
+        assert_eq!(BTCRelayModule::get_best_block_height(), test_data[5].height);
+        assert_eq!(
+            BTCRelayMainnetModule::get_best_block(),
+            test_data[2].get_block_hash()
+        );
+        assert_eq!(
+            BTCRelayMainnetModule::get_best_block_height(),
+            test_data[2].height
+        );
+    })
+}
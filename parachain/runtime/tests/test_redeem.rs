@@ -109,6 +109,82 @@ fn integration_test_redeem_polka_btc_execute() {
     });
 }
 
+#[test]
+fn integration_test_redeem_with_used_transaction_fails() {
+    ExtBuilder::build().execute_with(|| {
+        let user = ALICE;
+        let vault = BOB;
+        let collateral_vault = 1_000_000_000_000;
+        let polka_btc = 1_000_000_000_000;
+
+        let user_btc_address = BtcAddress::P2PKH(H160([2; 20]));
+
+        SystemModule::set_block_number(1);
+
+        assert_ok!(ExchangeRateOracleModule::_set_exchange_rate(
+            FixedU128::checked_from_rational(1, 100_000).unwrap()
+        ));
+
+        set_default_thresholds();
+
+        // without OP_RETURN checks, one payment matches both requests
+        assert_ok!(Call::BTCRelay(BTCRelayCall::set_verification_profile(
+            VerificationProfile {
+                relayer_auth: false,
+                op_return_check: false,
+                ..Default::default()
+            }
+        ))
+        .dispatch(frame_system::RawOrigin::Root.into()));
+
+        // create tokens for the vault and user
+        force_issue_tokens(user, vault, collateral_vault, 2 * polka_btc);
+
+        // alice requests to redeem twice the same amount to the same address
+        for _ in 0..2 {
+            assert_ok!(Call::Redeem(RedeemCall::request_redeem(
+                polka_btc,
+                user_btc_address,
+                account_of(vault)
+            ))
+            .dispatch(origin_of(account_of(user))));
+        }
+        let redeem_ids = SystemModule::events()
+            .iter()
+            .filter_map(|r| match r.event {
+                Event::redeem(RedeemEvent::RequestRedeem(id, _, _, _, _, _, _)) => Some(id),
+                _ => None,
+            })
+            .collect::<Vec<H256>>();
+        assert_eq!(redeem_ids.len(), 2);
+
+        let (tx_id, _tx_block_height, merkle_proof, raw_tx) =
+            generate_transaction_and_mine(user_btc_address, polka_btc, Some(redeem_ids[0]));
+
+        SystemModule::set_block_number(1 + CONFIRMATIONS);
+
+        assert_ok!(Call::Redeem(RedeemCall::execute_redeem(
+            redeem_ids[0],
+            tx_id,
+            merkle_proof.clone(),
+            raw_tx.clone()
+        ))
+        .dispatch(origin_of(account_of(vault))));
+
+        // the same payment can not fulfill the second request
+        assert_noop!(
+            Call::Redeem(RedeemCall::execute_redeem(
+                redeem_ids[1],
+                tx_id,
+                merkle_proof,
+                raw_tx
+            ))
+            .dispatch(origin_of(account_of(vault))),
+            BTCRelayError::TransactionAlreadyUsed
+        );
+    });
+}
+
 #[test]
 fn integration_test_redeem_polka_btc_liquidation_redeem() {
     ExtBuilder::build().execute_with(|| {
@@ -85,7 +85,8 @@ fn integration_test_report_vault_theft() {
             account_of(vault),
             tx_id,
             proof,
-            raw_tx
+            raw_tx,
+            vec![]
         ))
         .dispatch(origin_of(account_of(user))));
 
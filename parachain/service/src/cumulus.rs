@@ -128,6 +128,16 @@ async fn start_node_impl(
             block_announce_validator_builder: Some(Box::new(|_| block_announce_validator)),
         })?;
 
+    if parachain_config.offchain_worker.enabled {
+        sc_service::build_offchain_workers(
+            &parachain_config,
+            backend.clone(),
+            task_manager.spawn_handle(),
+            client.clone(),
+            network.clone(),
+        );
+    }
+
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
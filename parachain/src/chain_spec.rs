@@ -1,8 +1,8 @@
 use btc_parachain_runtime::{
-    AccountId, BTCRelayConfig, DOTConfig, ExchangeRateOracleConfig, FeeConfig, GenesisConfig,
-    IssueConfig, PolkaBTCConfig, RedeemConfig, RefundConfig, ReplaceConfig, Signature, SlaConfig,
-    StakedRelayersConfig, SudoConfig, SystemConfig, VaultRegistryConfig, DAYS, MINUTES,
-    WASM_BINARY,
+    AccountId, BTCRelayConfig, BTCRelayMainnetConfig, DOTConfig, ExchangeRateOracleConfig,
+    FeeConfig, GenesisConfig, IssueConfig, PolkaBTCConfig, RedeemConfig, RefundConfig,
+    ReplaceConfig, Signature, SlaConfig, StakedRelayersConfig, SudoConfig, SystemConfig,
+    VaultRegistryConfig, DAYS, MINUTES, WASM_BINARY,
 };
 
 #[cfg(feature = "aura-grandpa")]
@@ -379,6 +379,7 @@ fn testnet_genesis(
             #[cfg(not(feature = "runtime-benchmarks"))]
             gov_id: root_key,
             maturity_period: 10 * MINUTES,
+            unbonding_period: DAYS,
         }),
         exchange_rate_oracle: Some(ExchangeRateOracleConfig {
             authorized_oracles,
@@ -387,10 +388,20 @@ fn testnet_genesis(
         btc_relay: Some(BTCRelayConfig {
             bitcoin_confirmations: 0,
             parachain_confirmations: 0,
-            disable_difficulty_check: true,
-            disable_inclusion_check: false,
-            disable_op_return_check: false,
-            disable_relayer_auth: false,
+            checkpoints: vec![],
+            pruning_depth: 4032, // two difficulty periods
+            max_fork_depth: 144, // one day
+            max_pruned_headers_per_block: 32,
+            verification_profile: Default::default(),
+        }),
+        btc_relay_Instance1: Some(BTCRelayMainnetConfig {
+            bitcoin_confirmations: 6,
+            parachain_confirmations: 0,
+            checkpoints: vec![],
+            pruning_depth: 4032, // two difficulty periods
+            max_fork_depth: 144, // one day
+            max_pruned_headers_per_block: 32,
+            verification_profile: Default::default(),
         }),
         issue: Some(IssueConfig { issue_period: DAYS }),
         redeem: Some(RedeemConfig {
@@ -428,6 +439,7 @@ fn testnet_genesis(
             relayer_rewards: FixedU128::checked_from_rational(3, 100).unwrap(),
             maintainer_rewards: FixedU128::checked_from_rational(20, 100).unwrap(),
             collator_rewards: FixedU128::checked_from_integer(0).unwrap(),
+            header_submission_reward: 0,
         }),
         sla: Some(SlaConfig {
             vault_target_sla: FixedI128::from(100),